@@ -0,0 +1,183 @@
+use std::fmt;
+
+use intern::Lookup;
+
+use crate::{EmbeddedLocation, Location, WithLocation};
+
+/// Errors that can be reported as diagnostics have a stable code, which does not
+/// change if the error message is reworded. Codes are of the form
+/// `error_category/error_name`.
+pub trait ErrorCode {
+    fn error_code(&self) -> &'static str;
+
+    /// Locations other than the location of the error that are relevant to it, e.g.
+    /// the definition that a conflicting definition must be compatible with, each with
+    /// a message describing what is found there.
+    fn related_locations(&self) -> Vec<WithLocation<String>> {
+        vec![]
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// A one-indexed line and column. Columns are counted in UTF-16 code units, as
+/// SARIF and the language server protocol expect by default.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LineAndColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// An error or warning, in a form that can be serialized for editors and CI.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub location: Location,
+    pub related_locations: Vec<WithLocation<String>>,
+}
+
+impl Diagnostic {
+    pub fn new(
+        code: &'static str,
+        severity: Severity,
+        message: String,
+        location: Location,
+    ) -> Diagnostic {
+        Diagnostic {
+            code,
+            severity,
+            message,
+            location,
+            related_locations: vec![],
+        }
+    }
+
+    pub fn with_related_locations(
+        mut self,
+        related_locations: Vec<WithLocation<String>>,
+    ) -> Diagnostic {
+        self.related_locations = related_locations;
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}", self.message, self.location)?;
+        for related_location in &self.related_locations {
+            write!(f, "\n{related_location}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: ErrorCode + fmt::Display> WithLocation<T> {
+    pub fn to_diagnostic(&self, severity: Severity) -> Diagnostic {
+        Diagnostic::new(
+            self.item.error_code(),
+            severity,
+            self.item.to_string(),
+            self.location,
+        )
+        .with_related_locations(self.item.related_locations())
+    }
+}
+
+impl EmbeddedLocation {
+    /// The absolute path of the file containing this location.
+    pub fn file_path(&self) -> &'static str {
+        self.text_source.path.lookup()
+    }
+
+    /// The start and end of this location within the file, as opposed to within
+    /// the text source. file_contents must be the text that was compiled, since the
+    /// file on disk may have changed since.
+    pub fn line_and_column_range(&self, file_contents: &str) -> (LineAndColumn, LineAndColumn) {
+        let offset = self
+            .text_source
+            .span
+            .map(|text_source_span| text_source_span.start as usize)
+            .unwrap_or(0);
+        (
+            line_and_column(file_contents, offset + self.span.start as usize),
+            line_and_column(file_contents, offset + self.span.end as usize),
+        )
+    }
+}
+
+fn line_and_column(text: &str, index: usize) -> LineAndColumn {
+    let mut line = 1;
+    let mut column = 1;
+    for (char_index, char) in text.char_indices() {
+        if char_index >= index {
+            break;
+        }
+        if char == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += char.len_utf16();
+        }
+    }
+    LineAndColumn { line, column }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::diagnostic::{line_and_column, LineAndColumn};
+
+    #[test]
+    fn line_and_column_is_one_indexed() {
+        let text = "abc\ndef\n";
+        assert_eq!(
+            line_and_column(text, 0),
+            LineAndColumn { line: 1, column: 1 }
+        );
+        assert_eq!(
+            line_and_column(text, 2),
+            LineAndColumn { line: 1, column: 3 }
+        );
+        assert_eq!(
+            line_and_column(text, 4),
+            LineAndColumn { line: 2, column: 1 }
+        );
+        assert_eq!(
+            line_and_column(text, 6),
+            LineAndColumn { line: 2, column: 3 }
+        );
+    }
+
+    #[test]
+    fn line_and_column_counts_utf16_code_units() {
+        // é is one UTF-16 code unit and two bytes, 𝕏 is two UTF-16 code units
+        // and four bytes.
+        let text = "é𝕏x\n";
+        assert_eq!(
+            line_and_column(text, 2),
+            LineAndColumn { line: 1, column: 2 }
+        );
+        assert_eq!(
+            line_and_column(text, 6),
+            LineAndColumn { line: 1, column: 4 }
+        );
+        assert_eq!(
+            line_and_column(text, 7),
+            LineAndColumn { line: 1, column: 5 }
+        );
+    }
+}
@@ -1,9 +1,11 @@
+mod diagnostic;
 mod location;
 mod span;
 mod string_key_types;
 mod text_with_carats;
 mod traits;
 
+pub use diagnostic::*;
 pub use location::*;
 pub use span::*;
 pub use string_key_types::*;
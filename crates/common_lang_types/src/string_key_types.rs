@@ -78,6 +78,8 @@ string_key_conversion!(from: ScalarFieldName, to: SelectableFieldName);
 
 string_key_newtype!(LinkedFieldName);
 string_key_conversion!(from: LinkedFieldName, to: SelectableFieldName);
+// The keys of input object values are the names of the input object's fields.
+string_key_conversion!(from: ValueKeyName, to: InputValueName);
 
 string_key_newtype!(ScalarFieldAlias);
 string_key_newtype!(LinkedFieldAlias);
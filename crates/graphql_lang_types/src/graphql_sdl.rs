@@ -4,11 +4,11 @@ use crate::{GraphQLDirective, TypeAnnotation};
 
 use super::{write_arguments, write_directives, ConstantValue};
 use common_lang_types::{
-    DescriptionValue, DirectiveName, EnumLiteralValue, GraphQLInterfaceTypeName,
-    GraphQLObjectTypeName, GraphQLScalarTypeName, GraphQLUnionTypeName, InputTypeName,
-    InputValueName, SelectableFieldName, UnvalidatedTypeName, WithLocation, WithSpan,
+    DescriptionValue, DirectiveName, EnumLiteralValue, GraphQLEnumTypeName,
+    GraphQLInputObjectTypeName, GraphQLInterfaceTypeName, GraphQLObjectTypeName,
+    GraphQLScalarTypeName, GraphQLUnionTypeName, InputTypeName, InputValueName,
+    SelectableFieldName, UnvalidatedTypeName, WithLocation, WithSpan,
 };
-use intern::{string_key::Intern, Lookup};
 use strum::EnumString;
 
 // also Schema
@@ -97,12 +97,12 @@ pub enum GraphQLTypeSystemExtensionOrDefinition {
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub enum GraphQLTypeSystemExtension {
     ObjectTypeExtension(GraphQLObjectTypeExtension),
-    // ScalarTypeExtension
-    // InterfaceTypeExtension
-    // UnionTypeExtension
-    // EnumTypeExtension
-    // InputObjectTypeExtension
-    // SchemaExtension
+    ScalarTypeExtension(GraphQLScalarTypeExtension),
+    InterfaceTypeExtension(GraphQLInterfaceTypeExtension),
+    UnionTypeExtension(GraphQLUnionTypeExtension),
+    EnumTypeExtension(GraphQLEnumTypeExtension),
+    InputObjectTypeExtension(GraphQLInputObjectTypeExtension),
+    SchemaExtension(GraphQLSchemaExtension),
 }
 
 impl From<GraphQLObjectTypeExtension> for GraphQLTypeSystemExtension {
@@ -111,6 +111,42 @@ impl From<GraphQLObjectTypeExtension> for GraphQLTypeSystemExtension {
     }
 }
 
+impl From<GraphQLScalarTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(scalar_type_extension: GraphQLScalarTypeExtension) -> Self {
+        Self::ScalarTypeExtension(scalar_type_extension)
+    }
+}
+
+impl From<GraphQLInterfaceTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(interface_type_extension: GraphQLInterfaceTypeExtension) -> Self {
+        Self::InterfaceTypeExtension(interface_type_extension)
+    }
+}
+
+impl From<GraphQLUnionTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(union_type_extension: GraphQLUnionTypeExtension) -> Self {
+        Self::UnionTypeExtension(union_type_extension)
+    }
+}
+
+impl From<GraphQLEnumTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(enum_type_extension: GraphQLEnumTypeExtension) -> Self {
+        Self::EnumTypeExtension(enum_type_extension)
+    }
+}
+
+impl From<GraphQLInputObjectTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(input_object_type_extension: GraphQLInputObjectTypeExtension) -> Self {
+        Self::InputObjectTypeExtension(input_object_type_extension)
+    }
+}
+
+impl From<GraphQLSchemaExtension> for GraphQLTypeSystemExtension {
+    fn from(schema_extension: GraphQLSchemaExtension) -> Self {
+        Self::SchemaExtension(schema_extension)
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct GraphQLObjectTypeDefinition {
     pub description: Option<WithSpan<DescriptionValue>>,
@@ -128,6 +164,49 @@ pub struct GraphQLObjectTypeExtension {
     pub fields: Vec<WithLocation<GraphQLFieldDefinition>>,
 }
 
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLScalarTypeExtension {
+    pub name: WithLocation<GraphQLScalarTypeName>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLInterfaceTypeExtension {
+    pub name: WithLocation<GraphQLInterfaceTypeName>,
+    pub interfaces: Vec<WithLocation<GraphQLInterfaceTypeName>>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+    pub fields: Vec<WithLocation<GraphQLFieldDefinition>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLUnionTypeExtension {
+    pub name: WithLocation<GraphQLUnionTypeName>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+    pub union_member_types: Vec<WithLocation<GraphQLObjectTypeName>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLEnumTypeExtension {
+    pub name: WithLocation<GraphQLEnumTypeName>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+    pub enum_value_definitions: Vec<WithLocation<GraphQLEnumValueDefinition>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLInputObjectTypeExtension {
+    pub name: WithLocation<GraphQLInputObjectTypeName>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+    pub fields: Vec<WithLocation<GraphQLInputValueDefinition>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLSchemaExtension {
+    pub query: Option<WithLocation<GraphQLObjectTypeName>>,
+    pub subscription: Option<WithLocation<GraphQLObjectTypeName>>,
+    pub mutation: Option<WithLocation<GraphQLObjectTypeName>>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct GraphQLScalarTypeDefinition {
     pub description: Option<WithSpan<DescriptionValue>>,
@@ -147,7 +226,7 @@ pub struct GraphQLInterfaceTypeDefinition {
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct GraphQLInputObjectTypeDefinition {
     pub description: Option<WithSpan<DescriptionValue>>,
-    pub name: WithLocation<GraphQLInterfaceTypeName>,
+    pub name: WithLocation<GraphQLInputObjectTypeName>,
     pub directives: Vec<GraphQLDirective<ConstantValue>>,
     pub fields: Vec<WithLocation<GraphQLInputValueDefinition>>,
 }
@@ -218,20 +297,6 @@ pub struct GraphQLUnionTypeDefinition {
     pub union_member_types: Vec<WithLocation<GraphQLObjectTypeName>>,
 }
 
-impl From<GraphQLInputValueDefinition> for GraphQLFieldDefinition {
-    fn from(value: GraphQLInputValueDefinition) -> Self {
-        Self {
-            description: value.description,
-            // TODO make this zero cost?
-            name: value.name.map(|x| x.lookup().intern().into()),
-            type_: value.type_.map(|x| x.lookup().intern().into()),
-            // Input object fields do not take arguments
-            arguments: vec![],
-            directives: value.directives,
-        }
-    }
-}
-
 /// A server field definition on an object or interface
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct GraphQLFieldDefinition {
@@ -277,8 +342,19 @@ impl fmt::Display for GraphQLInputValueDefinition {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum RootOperationKind {
     Query,
     Subscription,
     Mutation,
 }
+
+impl fmt::Display for RootOperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RootOperationKind::Query => write!(f, "query"),
+            RootOperationKind::Subscription => write!(f, "subscription"),
+            RootOperationKind::Mutation => write!(f, "mutation"),
+        }
+    }
+}
@@ -66,6 +66,56 @@ impl<TValue> TypeAnnotation<TValue> {
             },
         }
     }
+
+    pub fn is_nullable(&self) -> bool {
+        !matches!(self, TypeAnnotation::NonNull(_))
+    }
+
+    /// If a TypeAnnotation is of the form [X] or [X]!, returns Some(X). Otherwise,
+    /// returns None.
+    pub fn list_item_type(&self) -> Option<&TypeAnnotation<TValue>> {
+        match self {
+            TypeAnnotation::Named(_) => None,
+            TypeAnnotation::List(list) => Some(&list.0),
+            TypeAnnotation::NonNull(non_null) => match non_null.as_ref() {
+                NonNullTypeAnnotation::Named(_) => None,
+                NonNullTypeAnnotation::List(list) => Some(&list.0),
+            },
+        }
+    }
+
+    /// The levels of a type are the value itself, then its list items, then their
+    /// list items, and so on. Each level for which unwrapped_levels contains true is
+    /// made non-null. Entries beyond the innermost level are ignored.
+    pub fn unwrap_levels(self, unwrapped_levels: &[bool]) -> TypeAnnotation<TValue> {
+        let (is_unwrapped, inner_unwrapped_levels) = match unwrapped_levels.split_first() {
+            Some(split) => split,
+            None => return self,
+        };
+
+        let non_null = match self {
+            TypeAnnotation::Named(named) => {
+                if !*is_unwrapped {
+                    return TypeAnnotation::Named(named);
+                }
+                NonNullTypeAnnotation::Named(named)
+            }
+            TypeAnnotation::List(list) => {
+                let list = ListTypeAnnotation(list.0.unwrap_levels(inner_unwrapped_levels));
+                if !*is_unwrapped {
+                    return TypeAnnotation::List(Box::new(list));
+                }
+                NonNullTypeAnnotation::List(list)
+            }
+            TypeAnnotation::NonNull(non_null) => match *non_null {
+                NonNullTypeAnnotation::Named(named) => NonNullTypeAnnotation::Named(named),
+                NonNullTypeAnnotation::List(list) => NonNullTypeAnnotation::List(
+                    ListTypeAnnotation(list.0.unwrap_levels(inner_unwrapped_levels)),
+                ),
+            },
+        };
+        TypeAnnotation::NonNull(Box::new(non_null))
+    }
 }
 
 impl<TValue: fmt::Display> fmt::Display for TypeAnnotation<TValue> {
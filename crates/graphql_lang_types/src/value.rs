@@ -3,6 +3,7 @@ use std::fmt;
 use common_lang_types::{
     EnumLiteralValue, StringLiteralValue, ValueKeyName, VariableName, WithLocation, WithSpan,
 };
+use intern::Lookup;
 
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum ConstantValue {
@@ -54,7 +55,7 @@ impl fmt::Display for ConstantValue {
         match self {
             ConstantValue::Int(value) => f.write_fmt(format_args!("{}", value)),
             ConstantValue::Float(value) => f.write_fmt(format_args!("{}", value)),
-            ConstantValue::String(value) => f.write_fmt(format_args!("\"{}\"", value)),
+            ConstantValue::String(value) => f.write_str(&print_string_literal(value.lookup())),
             ConstantValue::Boolean(value) => f.write_fmt(format_args!("{}", value)),
             ConstantValue::Null => f.write_str("null"),
             ConstantValue::Enum(value) => f.write_fmt(format_args!("{}", value)),
@@ -84,6 +85,92 @@ impl fmt::Display for Value {
     }
 }
 
+/// Print a string value as a double-quoted string literal. Characters are escaped
+/// exactly as `JSON.stringify` escapes them, so the result is a valid GraphQL string
+/// value, as well as a valid JSON and JavaScript string.
+pub fn print_string_literal(value: &str) -> String {
+    let mut string_literal = String::with_capacity(value.len() + 2);
+    string_literal.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => string_literal.push_str("\\\""),
+            '\\' => string_literal.push_str("\\\\"),
+            '\u{8}' => string_literal.push_str("\\b"),
+            '\u{c}' => string_literal.push_str("\\f"),
+            '\n' => string_literal.push_str("\\n"),
+            '\r' => string_literal.push_str("\\r"),
+            '\t' => string_literal.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                string_literal.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => string_literal.push(c),
+        }
+    }
+    string_literal.push('"');
+    string_literal
+}
+
+/// Decode the escape sequences of a string value, given its source without the
+/// surrounding quotes. The lexer has already rejected invalid escape sequences.
+/// Unicode escapes that do not form a valid code point (e.g. lone surrogates) are
+/// replaced with U+FFFD.
+pub fn unescape_string_literal(source: &str) -> String {
+    let mut value = String::with_capacity(source.len());
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('b') => value.push('\u{8}'),
+            Some('f') => value.push('\u{c}'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('u') => {
+                let code_unit = parse_unicode_escape_digits(&mut chars);
+                let code_point = match code_unit {
+                    Some(high @ 0xD800..=0xDBFF) => {
+                        // A surrogate pair is written as two consecutive escapes.
+                        let mut lookahead = chars.clone();
+                        let low = match (lookahead.next(), lookahead.next()) {
+                            (Some('\\'), Some('u')) => parse_unicode_escape_digits(&mut lookahead),
+                            _ => None,
+                        };
+                        match low {
+                            Some(low @ 0xDC00..=0xDFFF) => {
+                                chars = lookahead;
+                                Some(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+                            }
+                            _ => None,
+                        }
+                    }
+                    code_unit => code_unit,
+                };
+                value.push(
+                    code_point
+                        .and_then(char::from_u32)
+                        .unwrap_or(char::REPLACEMENT_CHARACTER),
+                );
+            }
+            // \", \\ and \/ stand for the escaped character itself
+            Some(escaped) => value.push(escaped),
+            None => value.push('\\'),
+        }
+    }
+    value
+}
+
+fn parse_unicode_escape_digits(chars: &mut impl Iterator<Item = char>) -> Option<u32> {
+    let digits: String = chars.take(4).collect();
+    if digits.len() == 4 {
+        u32::from_str_radix(&digits, 16).ok()
+    } else {
+        None
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct FloatValue(u64);
 
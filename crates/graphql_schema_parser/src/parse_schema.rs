@@ -11,15 +11,17 @@ use intern::{
 };
 
 use graphql_lang_types::{
-    ConstantValue, DirectiveLocation, GraphQLDirective, GraphQLDirectiveDefinition,
-    GraphQLEnumDefinition, GraphQLEnumValueDefinition, GraphQLFieldDefinition,
-    GraphQLInputObjectTypeDefinition, GraphQLInputValueDefinition, GraphQLInterfaceTypeDefinition,
-    GraphQLObjectTypeDefinition, GraphQLObjectTypeExtension, GraphQLScalarTypeDefinition,
-    GraphQLSchemaDefinition, GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument,
+    unescape_string_literal, ConstantValue, DirectiveLocation, GraphQLDirective,
+    GraphQLDirectiveDefinition, GraphQLEnumDefinition, GraphQLEnumTypeExtension,
+    GraphQLEnumValueDefinition, GraphQLFieldDefinition, GraphQLInputObjectTypeDefinition,
+    GraphQLInputObjectTypeExtension, GraphQLInputValueDefinition, GraphQLInterfaceTypeDefinition,
+    GraphQLInterfaceTypeExtension, GraphQLObjectTypeDefinition, GraphQLObjectTypeExtension,
+    GraphQLScalarTypeDefinition, GraphQLScalarTypeExtension, GraphQLSchemaDefinition,
+    GraphQLSchemaExtension, GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument,
     GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
-    GraphQLTypeSystemExtensionOrDefinition, GraphQLUnionTypeDefinition, ListTypeAnnotation,
-    NameValuePair, NamedTypeAnnotation, NonNullTypeAnnotation, RootOperationKind, TypeAnnotation,
-    ValueType,
+    GraphQLTypeSystemExtensionOrDefinition, GraphQLUnionTypeDefinition, GraphQLUnionTypeExtension,
+    ListTypeAnnotation, NameValuePair, NamedTypeAnnotation, NonNullTypeAnnotation,
+    RootOperationKind, TypeAnnotation, ValueType,
 };
 
 use crate::ParseResult;
@@ -110,9 +112,21 @@ fn parse_type_system_extension(
             match identifier.item {
                 "type" => parse_object_type_extension(tokens, text_source)
                     .map(GraphQLTypeSystemExtension::from),
+                "scalar" => parse_scalar_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
+                "interface" => parse_interface_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
+                "union" => parse_union_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
+                "enum" => parse_enum_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
+                "input" => parse_input_object_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
+                "schema" => parse_schema_extension(tokens, identifier.span, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
                 _ => Err(WithSpan::new(
-                    SchemaParseError::TopLevelSchemaDeclarationExpected {
-                        found_text: identifier.to_string(),
+                    SchemaParseError::TypeSystemExtensionExpected {
+                        found_text: identifier.item.to_string(),
                     },
                     identifier.span,
                 )),
@@ -194,21 +208,193 @@ fn parse_object_type_extension(
 ) -> ParseResult<GraphQLObjectTypeExtension> {
     let name = tokens
         .parse_string_key_type(TokenKind::Identifier)
-        .map(|with_span| with_span.to_with_location(text_source))
         .map_err(|with_span| with_span.map(SchemaParseError::from))?;
 
     let interfaces = parse_implements_interfaces_if_present(tokens, text_source)?;
     let directives = parse_constant_directives(tokens, text_source)?;
     let fields = parse_optional_fields(tokens, text_source)?;
 
+    if interfaces.is_empty() && directives.is_empty() && fields.is_empty() {
+        return Err(empty_type_system_extension_error(name));
+    }
+
     Ok(GraphQLObjectTypeExtension {
-        name,
+        name: name.to_with_location(text_source),
         interfaces,
         directives,
         fields,
     })
 }
 
+/// The state of the PeekableLexer is that it has processed the "scalar" keyword
+fn parse_scalar_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLScalarTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?;
+
+    let directives = parse_constant_directives(tokens, text_source)?;
+
+    if directives.is_empty() {
+        return Err(empty_type_system_extension_error(name));
+    }
+
+    Ok(GraphQLScalarTypeExtension {
+        name: name.to_with_location(text_source),
+        directives,
+    })
+}
+
+/// The state of the PeekableLexer is that it has processed the "interface" keyword
+fn parse_interface_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLInterfaceTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?;
+
+    let interfaces = parse_implements_interfaces_if_present(tokens, text_source)?;
+    let directives = parse_constant_directives(tokens, text_source)?;
+    let fields = parse_optional_fields(tokens, text_source)?;
+
+    if interfaces.is_empty() && directives.is_empty() && fields.is_empty() {
+        return Err(empty_type_system_extension_error(name));
+    }
+
+    Ok(GraphQLInterfaceTypeExtension {
+        name: name.to_with_location(text_source),
+        interfaces,
+        directives,
+        fields,
+    })
+}
+
+/// The state of the PeekableLexer is that it has processed the "union" keyword
+fn parse_union_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLUnionTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?;
+
+    let directives = parse_constant_directives(tokens, text_source)?;
+
+    let union_member_types = if tokens.parse_token_of_kind(TokenKind::Equals).is_ok() {
+        parse_union_member_types(tokens, text_source)?
+    } else {
+        vec![]
+    };
+
+    if directives.is_empty() && union_member_types.is_empty() {
+        return Err(empty_type_system_extension_error(name));
+    }
+
+    Ok(GraphQLUnionTypeExtension {
+        name: name.to_with_location(text_source),
+        directives,
+        union_member_types,
+    })
+}
+
+/// The state of the PeekableLexer is that it has processed the "enum" keyword
+fn parse_enum_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLEnumTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?;
+
+    let directives = parse_constant_directives(tokens, text_source)?;
+    let enum_value_definitions = parse_enum_value_definitions(tokens, text_source)?;
+
+    if directives.is_empty() && enum_value_definitions.is_empty() {
+        return Err(empty_type_system_extension_error(name));
+    }
+
+    Ok(GraphQLEnumTypeExtension {
+        name: name.to_with_location(text_source),
+        directives,
+        enum_value_definitions,
+    })
+}
+
+/// The state of the PeekableLexer is that it has processed the "input" keyword
+fn parse_input_object_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLInputObjectTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?;
+
+    let directives = parse_constant_directives(tokens, text_source)?;
+    let fields = parse_optional_enclosed_items(
+        tokens,
+        text_source,
+        TokenKind::OpenBrace,
+        TokenKind::CloseBrace,
+        parse_argument_definition,
+    )?;
+
+    if directives.is_empty() && fields.is_empty() {
+        return Err(empty_type_system_extension_error(name));
+    }
+
+    Ok(GraphQLInputObjectTypeExtension {
+        name: name.to_with_location(text_source),
+        directives,
+        fields,
+    })
+}
+
+/// The state of the PeekableLexer is that it has processed the "schema" keyword
+fn parse_schema_extension(
+    tokens: &mut PeekableLexer,
+    schema_keyword_span: Span,
+    text_source: TextSource,
+) -> ParseResult<GraphQLSchemaExtension> {
+    let directives = parse_constant_directives(tokens, text_source)?;
+
+    let root_operation_types = if tokens.parse_token_of_kind(TokenKind::OpenBrace).is_ok() {
+        Some(parse_root_operation_types(tokens, text_source)?)
+    } else {
+        None
+    };
+
+    if directives.is_empty() && root_operation_types.is_none() {
+        return Err(WithSpan::new(
+            SchemaParseError::EmptyTypeSystemExtension {
+                type_name: "schema".to_string(),
+            },
+            schema_keyword_span,
+        ));
+    }
+
+    let (query, subscription, mutation) = root_operation_types.unwrap_or_default();
+    Ok(GraphQLSchemaExtension {
+        query,
+        subscription,
+        mutation,
+        directives,
+    })
+}
+
+fn empty_type_system_extension_error<T: std::fmt::Display>(
+    name: WithSpan<T>,
+) -> WithSpan<SchemaParseError> {
+    WithSpan::new(
+        SchemaParseError::EmptyTypeSystemExtension {
+            type_name: name.item.to_string(),
+        },
+        name.span,
+    )
+}
+
 /// The state of the PeekableLexer is that it has processed the "interface" keyword
 fn parse_interface_type_definition(
     tokens: &mut PeekableLexer,
@@ -472,6 +658,29 @@ fn parse_schema_definition(
         .parse_token_of_kind(TokenKind::OpenBrace)
         .map_err(|with_span| with_span.map(SchemaParseError::from))?;
 
+    let (query, subscription, mutation) = parse_root_operation_types(tokens, text_source)?;
+
+    Ok(GraphQLSchemaDefinition {
+        description,
+        query,
+        subscription,
+        mutation,
+        directives,
+    })
+}
+
+type RootOperationTypes = (
+    Option<WithLocation<GraphQLObjectTypeName>>,
+    Option<WithLocation<GraphQLObjectTypeName>>,
+    Option<WithLocation<GraphQLObjectTypeName>>,
+);
+
+/// The state of the PeekableLexer is that it has processed the opening curly brace.
+/// Returns the query, subscription and mutation types, in that order.
+fn parse_root_operation_types(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<RootOperationTypes> {
     let mut query_type = None;
     let mut mutation_type = None;
     let mut subscription_type = None;
@@ -495,13 +704,7 @@ fn parse_schema_definition(
         }
     }
 
-    Ok(GraphQLSchemaDefinition {
-        description,
-        query: query_type,
-        subscription: subscription_type,
-        mutation: mutation_type,
-        directives,
-    })
+    Ok((query_type, subscription_type, mutation_type))
 }
 
 fn reassign_or_error(
@@ -740,7 +943,9 @@ fn parse_constant_value(
                     let without_quotes = with_quotes.map(|string_literal| {
                         let inner_str = &string_literal.lookup();
                         let len = inner_str.len();
-                        let without_quotes = (&inner_str[1..(len - 1)]).intern().into();
+                        let without_quotes = unescape_string_literal(&inner_str[1..(len - 1)])
+                            .intern()
+                            .into();
                         without_quotes
                     });
                     without_quotes.map(ConstantValue::String)
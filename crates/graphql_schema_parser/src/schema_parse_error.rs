@@ -1,4 +1,4 @@
-use common_lang_types::WithSpan;
+use common_lang_types::{ErrorCode, WithSpan};
 use thiserror::Error;
 
 use super::peekable_lexer::LowLevelParseError;
@@ -17,6 +17,12 @@ pub enum SchemaParseError {
     #[error("Expected extend, scalar, type, interface, union, enum, input object, schema or directive, found \"{found_text}\"")]
     TopLevelSchemaDeclarationOrExtensionExpected { found_text: String },
 
+    #[error("Expected scalar, type, interface, union, enum, input or schema after extend, found \"{found_text}\"")]
+    TypeSystemExtensionExpected { found_text: String },
+
+    #[error("The extension of {type_name} must add at least one directive, interface, field, enum value, union member or root operation type")]
+    EmptyTypeSystemExtension { type_name: String },
+
     #[error("Unable to parse constant value")]
     UnableToParseConstantValue,
 
@@ -42,6 +48,42 @@ pub enum SchemaParseError {
     RootOperationTypeRedefined,
 }
 
+impl ErrorCode for SchemaParseError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            SchemaParseError::ParseError { .. } => "schema_parse/parse_error",
+            SchemaParseError::TopLevelSchemaDeclarationExpected { .. } => {
+                "schema_parse/top_level_schema_declaration_expected"
+            }
+            SchemaParseError::TopLevelSchemaDeclarationOrExtensionExpected { .. } => {
+                "schema_parse/top_level_schema_declaration_or_extension_expected"
+            }
+            SchemaParseError::TypeSystemExtensionExpected { .. } => {
+                "schema_parse/type_system_extension_expected"
+            }
+            SchemaParseError::EmptyTypeSystemExtension { .. } => {
+                "schema_parse/empty_type_system_extension"
+            }
+            SchemaParseError::UnableToParseConstantValue => {
+                "schema_parse/unable_to_parse_constant_value"
+            }
+            SchemaParseError::InvalidIntValue { .. } => "schema_parse/invalid_int_value",
+            SchemaParseError::InvalidFloatValue { .. } => "schema_parse/invalid_float_value",
+            SchemaParseError::ExpectedTypeAnnotation => "schema_parse/expected_type_annotation",
+            SchemaParseError::ExpectedDirectiveLocation { .. } => {
+                "schema_parse/expected_directive_location"
+            }
+            SchemaParseError::EnumValueTrueFalseNull => "schema_parse/enum_value_true_false_null",
+            SchemaParseError::ExpectedRootOperationType => {
+                "schema_parse/expected_root_operation_type"
+            }
+            SchemaParseError::RootOperationTypeRedefined => {
+                "schema_parse/root_operation_type_redefined"
+            }
+        }
+    }
+}
+
 impl From<LowLevelParseError> for SchemaParseError {
     fn from(error: LowLevelParseError) -> Self {
         SchemaParseError::ParseError { error }
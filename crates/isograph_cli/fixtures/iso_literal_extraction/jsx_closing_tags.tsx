@@ -0,0 +1,28 @@
+import { iso } from '@iso';
+
+const Link = ({ href }) => <a href={href}>Home</a>; export const Title = iso(`field Query.Title { name }`)(title);
+
+export const Header = iso(`
+  field Query.Header {
+    name
+    options
+  }
+`)(function Header(data) {
+  return (
+    <header>
+      <h1 className="title">{data.name}</h1>
+      <Select<Option> options={data.options} />
+      <>
+        <Link href="/" />
+      </>
+    </header>
+  );
+});
+
+export const Footer = iso(`
+  field Query.Footer {
+    name
+  }
+`)(function Footer(data) {
+  return <footer>{data.name}</footer>;
+});
@@ -0,0 +1,25 @@
+import { iso } from '@iso';
+
+export const Shortcuts = iso(`
+  field Query.Shortcuts {
+    name
+  }
+`)(function Shortcuts(data) {
+  return (
+    <ul>
+      <li>Press ` to open the console.</li>
+      <li>
+        Don't press Escape, {data.name}. It's
+        "not" what you want.
+      </li>
+    </ul>
+  );
+});
+
+export const Console = iso(`
+  field Query.Console {
+    name
+  }
+`)(function Console(data) {
+  return <pre title="Don't">{data.name} isn't iso(`field Query.Fake {}`)</pre>;
+});
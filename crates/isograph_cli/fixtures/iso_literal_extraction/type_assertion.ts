@@ -0,0 +1,12 @@
+import { iso } from '@iso';
+
+const input = <HTMLInputElement>document.getElementById('name');
+const identity = <T,>(value: T) => value;
+
+export const Foo = iso(`
+  field Query.Foo {
+    name
+  }
+`)(function Foo(data) {
+  return data.name;
+});
@@ -1,11 +1,13 @@
 use std::collections::HashMap;
 
-use common_lang_types::{IsographObjectTypeName, SelectableFieldName};
+use common_lang_types::{
+    DescriptionValue, IsographObjectTypeName, SelectableFieldName, StringLiteralValue,
+};
 use isograph_schema::{ClientFieldVariant, ObjectTypeAndFieldNames};
 
 use crate::generate_artifacts::{
-    ClientFieldOutputType, EntrypointArtifactInfo, JavaScriptImports, ReaderArtifactInfo,
-    RefetchArtifactInfo,
+    format_jsdoc, ClientFieldOutputType, EntrypointArtifactInfo, JavaScriptImports,
+    ReaderArtifactInfo, RefetchArtifactInfo,
 };
 
 impl<'schema> EntrypointArtifactInfo<'schema> {
@@ -16,16 +18,25 @@ impl<'schema> EntrypointArtifactInfo<'schema> {
             refetch_query_artifact_import,
             query_name,
             parent_type,
+            variables_type,
+            input_object_types,
+            variable_defaults,
+            custom_scalar_import_statement,
         } = self;
         let entrypoint_params_typename = format!("{}__{}__param", parent_type.name, query_name);
         let entrypoint_output_type_name =
             format!("{}__{}__outputType", parent_type.name, query_name);
+        let entrypoint_variables_typename =
+            format!("{}__{}__variables", parent_type.name, query_name);
         format!(
             "import type {{IsographEntrypoint, \
             NormalizationAst, RefetchQueryArtifactWrapper}} from '@isograph/react';\n\
             import type {{{entrypoint_params_typename}, {entrypoint_output_type_name}}} from './reader';\n\
             import readerResolver from './reader';\n\
+            {custom_scalar_import_statement}\
             {refetch_query_artifact_import}\n\n\
+            export type {entrypoint_variables_typename} = {variables_type};\n\n\
+            {input_object_types}\
             const queryText = '{query_text}';\n\n\
             const normalizationAst: NormalizationAst = {normalization_ast};\n\
             const artifact: IsographEntrypoint<\n\
@@ -35,6 +46,7 @@ impl<'schema> EntrypointArtifactInfo<'schema> {
             {}kind: \"Entrypoint\",\n\
             {}queryText,\n\
             {}normalizationAst,\n\
+            {}variableDefaults: {variable_defaults},\n\
             {}nestedRefetchQueries,\n\
             {}readerArtifact: readerResolver,\n\
             }};\n\n\
@@ -46,6 +58,7 @@ impl<'schema> EntrypointArtifactInfo<'schema> {
             "  ",
             "  ",
             "  ",
+            "  ",
         )
     }
 }
@@ -58,9 +71,12 @@ impl<'schema> ReaderArtifactInfo<'schema> {
             client_field_output_type,
             reader_ast,
             nested_client_field_artifact_imports,
+            custom_scalar_import_statement,
             parent_type,
             client_field_variant: resolver_variant,
             client_field_name: resolver_field_name,
+            client_field_description,
+            client_field_deprecation_reason,
             ..
         } = self;
         let nested_client_field_import_statement = nested_client_field_names_to_import_statement(
@@ -71,6 +87,8 @@ impl<'schema> ReaderArtifactInfo<'schema> {
             parent_type.name,
             resolver_field_name,
             client_field_output_type,
+            client_field_description,
+            client_field_deprecation_reason,
         );
 
         // We are not modeling this well, I think.
@@ -87,6 +105,7 @@ impl<'schema> ReaderArtifactInfo<'schema> {
             "import type {{ReaderArtifact, ReaderAst, ExtractSecondParam}} from '@isograph/react';\n\
             {function_import_statement}\n\
             {nested_client_field_import_statement}\n\
+            {custom_scalar_import_statement}\
             {output_type_text}\n\n\
             const readerAst: ReaderAst<{reader_param_type}> = {reader_ast};\n\n\
             export type {reader_param_type} = {client_field_parameter_type};\n\n\
@@ -200,10 +219,13 @@ fn get_output_type_text(
     parent_type_name: IsographObjectTypeName,
     field_name: SelectableFieldName,
     output_type: ClientFieldOutputType,
+    description: Option<DescriptionValue>,
+    deprecation_reason: Option<StringLiteralValue>,
 ) -> String {
+    let jsdoc = format_jsdoc(description, deprecation_reason, 0);
     format!(
         "// the type, when read out (either via useLazyReference or via graph)\n\
-        export type {}__{}__outputType = {};",
-        parent_type_name, field_name, output_type
+        {}export type {}__{}__outputType = {};",
+        jsdoc, parent_type_name, field_name, output_type
     )
 }
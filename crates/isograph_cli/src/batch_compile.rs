@@ -1,4 +1,5 @@
 use std::{
+    collections::{HashMap, HashSet},
     path::PathBuf,
     str::Utf8Error,
     time::{Duration, Instant},
@@ -6,33 +7,55 @@ use std::{
 
 use colored::Colorize;
 use common_lang_types::{
-    FilePath, Location, SourceFileName, Span, TextSource, WithLocation, WithSpan,
+    Diagnostic, ErrorCode, FilePath, Location, Severity, SourceFileName, Span, TextSource,
+    WithLocation, WithSpan,
 };
 use graphql_schema_parser::{parse_schema, parse_schema_extensions, SchemaParseError};
 use intern::string_key::Intern;
-use isograph_config::CompilerConfig;
+use isograph_config::{CompilerConfig, ProjectFiles};
 use isograph_lang_parser::{
     parse_iso_literal, IsoLiteralExtractionResult, IsographLiteralParseError,
 };
 use isograph_lang_types::{ClientFieldDeclaration, EntrypointTypeAndField};
 use isograph_schema::{
-    ProcessClientFieldDeclarationError, Schema, UnvalidatedSchema, ValidateSchemaError,
+    ProcessClientFieldDeclarationError, Schema, TypeRefinementMap, UnvalidatedSchema,
+    ValidateSchemaError, ValidatedSchema,
 };
 use pretty_duration::pretty_duration;
 use thiserror::Error;
 
 use crate::{
-    generate_artifacts::{generate_and_write_artifacts, GenerateArtifactsError},
+    check_artifacts::ArtifactDiff,
+    compiler_state::{CachedSource, CompilerState, PreviousCompilation, ServerSchema, SourceFile},
+    diagnostics::{print_diagnostics, DiagnosticsFormat, LocatedDiagnostic},
+    generate_artifacts::{
+        generate_and_check_artifacts, generate_and_write_artifacts, GenerateArtifactsError,
+    },
+    iso_literal_extraction::{extract_iso_literal_from_file_content, IsoLiteralExtraction},
     isograph_literals::{
-        extract_iso_literal_from_file_content, read_files_in_folder, IsoLiteralExtraction,
+        is_ignore_file, is_relevant_changed_path, is_valid_project_file, read_file,
+        read_files_in_folder,
     },
     schema::read_schema_file,
+    write_artifacts::ArtifactWriteCounts,
 };
 
+/// What to do once the schema and client fields have been validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompileMode {
+    /// Generate artifacts and write them to disk.
+    WriteArtifacts,
+    /// Generate artifacts and compare them to the artifacts on disk, without
+    /// writing anything. Fails if any artifact is missing, changed or extra.
+    CheckArtifacts,
+    /// Stop after validating the schema, client fields and entrypoints.
+    ValidateOnly,
+}
+
 pub(crate) struct CompilationStats {
     pub client_field_count: usize,
     pub entrypoint_count: usize,
-    pub total_artifacts_written: usize,
+    pub artifact_counts: ArtifactWriteCounts,
 }
 pub(crate) struct WithDuration<T> {
     pub elapsed_time: Duration,
@@ -50,36 +73,123 @@ impl<T> WithDuration<T> {
     }
 }
 
-pub(crate) fn compile_and_print(
+/// Compile each project and print the results. In a machine-readable diagnostics
+/// format, the diagnostics of all projects are printed as a single document.
+/// Returns whether every project compiled successfully.
+pub(crate) fn compile_and_print<'a>(
+    projects: impl Iterator<Item = (&'a CompilerConfig, &'a mut CompilerState)>,
+    compile_mode: CompileMode,
+    diagnostics_format: Option<DiagnosticsFormat>,
+) -> bool {
+    let mut diagnostics = vec![];
+    let mut succeeded = true;
+    for (config, compiler_state) in projects {
+        if compile_project_and_print(
+            config,
+            compiler_state,
+            compile_mode,
+            diagnostics_format,
+            &mut diagnostics,
+        )
+        .is_err()
+        {
+            succeeded = false;
+        }
+    }
+
+    if let Some(diagnostics_format) = diagnostics_format {
+        print_diagnostics(diagnostics_format, &diagnostics);
+    }
+    succeeded
+}
+
+/// Compile a single project. In a machine-readable diagnostics format, the warnings
+/// and errors are added to diagnostics instead of being printed.
+fn compile_project_and_print(
     config: &CompilerConfig,
+    compiler_state: &mut CompilerState,
+    compile_mode: CompileMode,
+    diagnostics_format: Option<DiagnosticsFormat>,
+    diagnostics: &mut Vec<LocatedDiagnostic>,
 ) -> Result<CompilationStats, BatchCompileError> {
-    eprintln!("{}", "Starting to compile.".cyan());
+    match &config.project_name {
+        Some(project_name) => {
+            eprintln!("{}", format!("Starting to compile {project_name}.").cyan())
+        }
+        None => eprintln!("{}", "Starting to compile.".cyan()),
+    }
 
-    let result = handle_compile_command(config);
+    let mut warnings = vec![];
+    let result = handle_compile_command(config, compiler_state, compile_mode, &mut warnings);
     let elapsed_time = result.elapsed_time;
 
+    match diagnostics_format {
+        Some(_) => {
+            let errors = match &result.item {
+                Ok(_) => vec![],
+                Err(err) => err.to_diagnostics(),
+            };
+            diagnostics.extend(
+                warnings
+                    .into_iter()
+                    .chain(errors)
+                    .map(|diagnostic| LocatedDiagnostic::new(diagnostic, config, compiler_state)),
+            );
+        }
+        None => {
+            for warning in warnings {
+                eprintln!("{}\n{}\n", "Warning:".yellow(), warning);
+            }
+        }
+    }
+
     match result.item {
         Ok(stats) => {
+            let artifact_summary = match compile_mode {
+                CompileMode::WriteArtifacts => {
+                    format!(
+                        "wrote {} artifacts ({} unchanged, {} deleted)",
+                        stats.artifact_counts.written,
+                        stats.artifact_counts.unchanged,
+                        stats.artifact_counts.deleted
+                    )
+                }
+                CompileMode::CheckArtifacts => "all artifacts are up to date".to_string(),
+                CompileMode::ValidateOnly => "did not generate artifacts".to_string(),
+            };
             eprintln!(
+                "{}",
+                format!(
+                    "Successfully compiled {} client fields and {} entrypoints, and {}, in {}.\n",
+                    stats.client_field_count,
+                    stats.entrypoint_count,
+                    artifact_summary,
+                    pretty_duration(&elapsed_time, None)
+                )
+                .bright_green()
+            );
+            Ok(stats)
+        }
+        Err(err) => {
+            if diagnostics_format.is_some() {
+                // The errors are printed to stdout once all projects are compiled
+                eprintln!(
                     "{}",
                     format!(
-                        "Successfully compiled {} client fields and {} entrypoints, and wrote {} artifacts, in {}.\n",
-                        stats.client_field_count,
-                        stats.entrypoint_count,
-                        stats.total_artifacts_written,
+                        "Error when compiling. Compilation took {}.",
                         pretty_duration(&elapsed_time, None)
                     )
-                    .bright_green()
+                    .bright_red()
                 );
-            Ok(stats)
-        }
-        Err(err) => {
-            eprintln!(
-                "{}\n{}\n{}",
-                "Error when compiling.\n".bright_red(),
-                err,
-                format!("Compilation took {}.", pretty_duration(&elapsed_time, None)).bright_red()
-            );
+            } else {
+                eprintln!(
+                    "{}\n{}\n{}",
+                    "Error when compiling.\n".bright_red(),
+                    err,
+                    format!("Compilation took {}.", pretty_duration(&elapsed_time, None))
+                        .bright_red()
+                );
+            }
             Err(err)
         }
     }
@@ -87,118 +197,518 @@ pub(crate) fn compile_and_print(
 
 pub(crate) fn handle_compile_command(
     config: &CompilerConfig,
+    compiler_state: &mut CompilerState,
+    compile_mode: CompileMode,
+    warnings: &mut Vec<Diagnostic>,
 ) -> WithDuration<Result<CompilationStats, BatchCompileError>> {
     WithDuration::new(|| {
-        let content = read_schema_file(&config.schema)?;
-        let schema_text_source = TextSource {
-            path: config
-                .schema
-                .to_str()
-                .expect("Expected schema to be valid string")
-                .intern()
-                .into(),
-            span: None,
+        let (schema, stats) = create_unvalidated_schema(config, compiler_state, warnings)?;
+
+        // If none of the inputs changed since the previous compilation wrote the
+        // artifacts (e.g. a file was saved without changes, or only code outside of
+        // iso literals was edited), the artifacts are up to date, so we skip
+        // validation and artifact generation.
+        let inputs_hash = compiler_state.inputs_hash();
+        if compile_mode == CompileMode::WriteArtifacts {
+            if let Some(previous_compilation) = &compiler_state.previous_compilation {
+                if previous_compilation.inputs_hash == inputs_hash {
+                    warnings.extend(previous_compilation.validation_warnings.iter().cloned());
+                    return Ok(CompilationStats {
+                        artifact_counts: ArtifactWriteCounts {
+                            unchanged: previous_compilation.artifact_count,
+                            ..Default::default()
+                        },
+                        ..stats
+                    });
+                }
+            }
+        }
+        compiler_state.previous_compilation = None;
+
+        let warning_count = warnings.len();
+        let validated_schema = Schema::validate_and_construct(schema, &config.options, warnings)?;
+
+        let artifact_counts = match compile_mode {
+            CompileMode::WriteArtifacts => {
+                let artifact_counts = generate_and_write_artifacts(
+                    &validated_schema,
+                    &config.project_root,
+                    &config.artifact_directory,
+                    &mut compiler_state.artifact_hashes,
+                )?;
+                compiler_state.previous_compilation = Some(PreviousCompilation {
+                    inputs_hash,
+                    validation_warnings: warnings[warning_count..].to_vec(),
+                    artifact_count: artifact_counts.written + artifact_counts.unchanged,
+                });
+                artifact_counts
+            }
+            CompileMode::CheckArtifacts => {
+                let artifact_diff = generate_and_check_artifacts(
+                    &validated_schema,
+                    &config.project_root,
+                    &config.artifact_directory,
+                )?;
+                if !artifact_diff.is_empty() {
+                    return Err(BatchCompileError::ArtifactsAreOutOfDate(artifact_diff));
+                }
+                ArtifactWriteCounts::default()
+            }
+            CompileMode::ValidateOnly => ArtifactWriteCounts::default(),
         };
-        let type_system_document = parse_schema(&content, schema_text_source)
-            .map_err(|with_span| with_span.to_with_location(schema_text_source))?;
 
-        let type_extension_documents = config
-            .schema_extensions
-            .iter()
-            .map(|schema_extension_path| {
-                let extension_text_source = TextSource {
-                    path: schema_extension_path
-                        .to_str()
-                        .expect("Expected schema extension to be valid string")
-                        .intern()
-                        .into(),
-                    span: None,
-                };
-                let extension_content = read_schema_file(schema_extension_path)?;
-                let type_extension_document =
-                    parse_schema_extensions(&extension_content, extension_text_source)
-                        .map_err(|with_span| with_span.to_with_location(extension_text_source))?;
-                Ok(type_extension_document)
-            })
-            .collect::<Result<Vec<_>, BatchCompileError>>()?;
+        Ok(CompilationStats {
+            artifact_counts,
+            ..stats
+        })
+    })
+}
 
-        let mut schema = UnvalidatedSchema::new();
+/// Parse and validate the schema, schema extensions and iso literals. The
+/// artifact counts of the returned stats are always zero. Warnings are added to
+/// warnings, whether or not this succeeds.
+pub(crate) fn create_validated_schema(
+    config: &CompilerConfig,
+    compiler_state: &mut CompilerState,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<(ValidatedSchema, CompilationStats), BatchCompileError> {
+    let (schema, stats) = create_unvalidated_schema(config, compiler_state, warnings)?;
+    let validated_schema = Schema::validate_and_construct(schema, &config.options, warnings)?;
+    Ok((validated_schema, stats))
+}
 
-        let original_outcome =
-            schema.process_graphql_type_system_document(type_system_document, config.options)?;
+/// Parse and process the schema, schema extensions and iso literals, without
+/// validating the client fields and entrypoints. Only files that changed since
+/// the previous compilation are re-read and re-parsed, and the processed server
+/// schema is reused unless the schema or a schema extension changed.
+fn create_unvalidated_schema(
+    config: &CompilerConfig,
+    compiler_state: &mut CompilerState,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<(UnvalidatedSchema, CompilationStats), BatchCompileError> {
+    update_schema(&config.schema, compiler_state)?;
 
-        // TODO validate here! We should not allow a situation in which a base schema is invalid,
-        // but is made valid by the presence of schema extensions.
+    let previous_schema_extension_count = compiler_state.schema_extensions.len();
+    compiler_state
+        .schema_extensions
+        .retain(|path, _| config.schema_extensions.contains(path));
+    if compiler_state.schema_extensions.len() != previous_schema_extension_count {
+        compiler_state.server_schema = None;
+    }
+    for schema_extension_path in config.schema_extensions.iter() {
+        update_schema_extension(schema_extension_path, compiler_state)?;
+    }
 
-        for extension_document in type_extension_documents {
-            let _extension_outcome = schema
-                .process_graphql_type_extension_document(extension_document, config.options)?;
-            // TODO extend the process_graphql_outcome.type_refinement_map and the one
-            // from the extensions? Does that even make sense?
-            // TODO validate that we didn't define any new root types (as they are ignored)
+    let server_schema = match compiler_state.server_schema.take() {
+        Some(server_schema) => {
+            warnings.extend(server_schema.warnings.iter().cloned());
+            server_schema
         }
-
-        // TODO the ordering should be:
-        // - process schema
-        // - validate
-        // - process schema extension
-        // - validate
-        // - add mutation fields
-        // - process parsed iso field definitions
-        // - validate client fields
-        if let Some(mutation_id) = &original_outcome.root_types.mutation {
-            schema.create_mutation_fields_from_expose_field_directives(
-                *mutation_id,
-                config.options,
-            )?;
+        None => {
+            let mut server_schema_warnings = vec![];
+            let result = create_server_schema(config, compiler_state, &mut server_schema_warnings);
+            warnings.extend(server_schema_warnings.iter().cloned());
+            let (schema, supertype_to_subtype_map) = result?;
+            ServerSchema {
+                schema,
+                supertype_to_subtype_map,
+                warnings: server_schema_warnings,
+            }
         }
+    };
+    // Processing iso literals adds client fields to the schema, so we process them
+    // in a copy, and keep the server schema for the next compilation.
+    let mut schema = server_schema.schema.clone();
+    let supertype_to_subtype_map = server_schema.supertype_to_subtype_map.clone();
+    compiler_state.server_schema = Some(server_schema);
 
-        let canonicalized_root_path = {
-            let current_dir = std::env::current_dir().expect("current_dir should exist");
-            let joined = current_dir.join(&config.project_root);
-            joined
-                .canonicalize()
-                .map_err(|message| BatchCompileError::UnableToLoadSchema {
-                    path: joined.clone(),
-                    message,
-                })?
-        };
+    let canonicalized_root_path = {
+        let current_dir = std::env::current_dir().expect("current_dir should exist");
+        let joined = current_dir.join(&config.project_root);
+        joined
+            .canonicalize()
+            .map_err(|message| BatchCompileError::UnableToLoadSchema {
+                path: joined.clone(),
+                message,
+            })?
+    };
 
-        // TODO return an iterator
-        let project_files = read_files_in_folder(&canonicalized_root_path)?;
-
-        let (client_field_declarations, parsed_entrypoints) =
-            extract_iso_literals(project_files, canonicalized_root_path)
-                .map_err(BatchCompileError::from)?;
-        let client_field_count = client_field_declarations.len();
-        let entrypoint_count = parsed_entrypoints.len();
-
-        process_client_fields_and_entrypoints(
-            &mut schema,
-            client_field_declarations,
-            parsed_entrypoints,
-        )?;
+    compiler_state.canonicalized_root_path = Some(canonicalized_root_path.clone());
+    update_source_files(
+        compiler_state,
+        &canonicalized_root_path,
+        &config.project_files,
+    )?;
+    parse_source_files(compiler_state, &canonicalized_root_path)
+        .map_err(BatchCompileError::from)?;
 
-        schema.add_fields_to_subtypes(
-            &original_outcome
-                .type_refinement_maps
-                .supertype_to_subtype_map,
-        )?;
+    let (client_field_declarations, parsed_entrypoints) =
+        get_iso_literals_from_source_files(compiler_state);
+    let client_field_count = client_field_declarations.len();
+    let entrypoint_count = parsed_entrypoints.len();
 
-        let validated_schema = Schema::validate_and_construct(schema)?;
+    process_client_fields_and_entrypoints(
+        &mut schema,
+        client_field_declarations,
+        parsed_entrypoints,
+    )?;
 
-        let total_artifacts_written = generate_and_write_artifacts(
-            &validated_schema,
-            &config.project_root,
-            &config.artifact_directory,
-        )?;
+    schema.add_fields_to_subtypes(&supertype_to_subtype_map)?;
 
-        Ok(CompilationStats {
+    Ok((
+        schema,
+        CompilationStats {
             client_field_count,
             entrypoint_count,
-            total_artifacts_written,
+            artifact_counts: ArtifactWriteCounts::default(),
+        },
+    ))
+}
+
+/// Process the parsed schema and schema extensions, i.e. everything that does not
+/// depend on iso literals.
+fn create_server_schema(
+    config: &CompilerConfig,
+    compiler_state: &CompilerState,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<(UnvalidatedSchema, TypeRefinementMap), BatchCompileError> {
+    let type_system_document = compiler_state
+        .schema
+        .as_ref()
+        .and_then(|schema| schema.parsed.clone())
+        .expect("Expected schema to be parsed. This is indicative of a bug in Isograph.");
+
+    let mut schema = UnvalidatedSchema::new();
+
+    let original_outcome = schema.process_graphql_type_system_document(
+        type_system_document,
+        &config.options,
+        warnings,
+    )?;
+    let mut supertype_to_subtype_map = original_outcome
+        .type_refinement_maps
+        .supertype_to_subtype_map;
+
+    // We should not allow a situation in which a base schema is invalid, but is made
+    // valid by the presence of schema extensions. So, validate the schema before
+    // processing extensions, and again after processing each extension document.
+    schema.validate_type_system()?;
+
+    for schema_extension_path in config.schema_extensions.iter() {
+        let extension_document = compiler_state
+            .schema_extensions
+            .get(schema_extension_path)
+            .and_then(|schema_extension| schema_extension.parsed.clone())
+            .expect(
+                "Expected schema extension to be parsed. \
+                This is indicative of a bug in Isograph.",
+            );
+        let extension_outcome = schema.process_graphql_type_extension_document(
+            extension_document,
+            &config.options,
+            warnings,
+        )?;
+        schema.validate_type_system()?;
+        for (supertype_id, subtype_ids) in extension_outcome
+            .type_refinement_maps
+            .supertype_to_subtype_map
+        {
+            supertype_to_subtype_map
+                .entry(supertype_id)
+                .or_default()
+                .extend(subtype_ids);
+        }
+    }
+
+    if let Some(mutation_id) = schema.mutation_type_id {
+        schema.create_mutation_fields_from_expose_field_directives(mutation_id)?;
+    }
+
+    Ok((schema, supertype_to_subtype_map))
+}
+
+/// Read the schema, and re-parse it only if its content has changed since the
+/// previous compilation, or if it could not be parsed. If it is re-parsed, the
+/// server schema must be re-created.
+fn update_schema(
+    schema_path: &PathBuf,
+    compiler_state: &mut CompilerState,
+) -> Result<(), BatchCompileError> {
+    let content = read_schema_file(schema_path)?;
+    if let Some(cached_schema) = &compiler_state.schema {
+        if cached_schema.content == content && cached_schema.parsed.is_some() {
+            return Ok(());
+        }
+    }
+    compiler_state.server_schema = None;
+
+    let schema_text_source = TextSource {
+        path: schema_path
+            .to_str()
+            .expect("Expected schema to be valid string")
+            .intern()
+            .into(),
+        span: None,
+    };
+
+    // The content is kept even if it cannot be parsed, so that the parse error
+    // can be located.
+    let (parsed, result) = match parse_schema(&content, schema_text_source) {
+        Ok(type_system_document) => (Some(type_system_document), Ok(())),
+        Err(with_span) => (
+            None,
+            Err(with_span.to_with_location(schema_text_source).into()),
+        ),
+    };
+    compiler_state.schema = Some(CachedSource { content, parsed });
+    result
+}
+
+/// Read a schema extension, and re-parse it only if its content has changed
+/// since the previous compilation, or if it could not be parsed. If it is
+/// re-parsed, the server schema must be re-created.
+fn update_schema_extension(
+    schema_extension_path: &PathBuf,
+    compiler_state: &mut CompilerState,
+) -> Result<(), BatchCompileError> {
+    let extension_content = read_schema_file(schema_extension_path)?;
+    if let Some(cached_extension) = compiler_state.schema_extensions.get(schema_extension_path) {
+        if cached_extension.content == extension_content && cached_extension.parsed.is_some() {
+            return Ok(());
+        }
+    }
+    compiler_state.server_schema = None;
+
+    let extension_text_source = TextSource {
+        path: schema_extension_path
+            .to_str()
+            .expect("Expected schema extension to be valid string")
+            .intern()
+            .into(),
+        span: None,
+    };
+
+    let (parsed, result) = match parse_schema_extensions(&extension_content, extension_text_source)
+    {
+        Ok(type_extension_document) => (Some(type_extension_document), Ok(())),
+        Err(with_span) => (
+            None,
+            Err(with_span.to_with_location(extension_text_source).into()),
+        ),
+    };
+    compiler_state.schema_extensions.insert(
+        schema_extension_path.clone(),
+        CachedSource {
+            content: extension_content,
+            parsed,
+        },
+    );
+    result
+}
+
+/// Bring compiler_state.source_files up to date with the files on disk and the
+/// documents open in an editor. Files that are new or whose content changed are
+/// left without iso literals, so that parse_source_files (re-)parses them.
+fn update_source_files(
+    compiler_state: &mut CompilerState,
+    canonicalized_root_path: &PathBuf,
+    project_files: &ProjectFiles,
+) -> Result<(), BatchCompileError> {
+    update_source_files_from_disk(compiler_state, canonicalized_root_path, project_files)?;
+
+    let source_files = compiler_state
+        .source_files
+        .as_mut()
+        .expect("Expected source files to exist. This is indicative of a bug in Isograph.");
+
+    // The unsaved content of open documents takes precedence over the content on disk.
+    // Open documents that are not project files (e.g. because they are ignored) are
+    // skipped.
+    for (absolute_path, content) in compiler_state.open_documents.iter() {
+        let relative_path = match absolute_path.strip_prefix(canonicalized_root_path) {
+            Ok(relative_path) => relative_path,
+            Err(_) => continue,
+        };
+        if let Some(source_file) = source_files.get_mut(relative_path) {
+            source_file.update_content(content.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// Bring compiler_state.source_files up to date with the files on disk.
+///
+/// Changed paths that cannot affect the project files, such as paths within
+/// excluded directories or paths that are ignored, are skipped. If we have not yet
+/// walked the project root, or if a remaining changed path is a directory, a
+/// .gitignore or .ignore file, or a new project file, we walk the entire project
+/// root. Otherwise, we only re-read the files that the watcher reported as changed.
+///
+/// If this fails, the changed paths are kept, so that the next compilation
+/// handles them again.
+fn update_source_files_from_disk(
+    compiler_state: &mut CompilerState,
+    canonicalized_root_path: &PathBuf,
+    project_files: &ProjectFiles,
+) -> Result<(), BatchCompileError> {
+    let changed_paths = std::mem::take(&mut compiler_state.changed_paths);
+    let result = update_source_files_from_changed_paths(
+        compiler_state,
+        &changed_paths,
+        canonicalized_root_path,
+        project_files,
+    );
+    if result.is_err() {
+        compiler_state.changed_paths.extend(changed_paths);
+    }
+    result
+}
+
+fn update_source_files_from_changed_paths(
+    compiler_state: &mut CompilerState,
+    changed_paths: &HashSet<PathBuf>,
+    canonicalized_root_path: &PathBuf,
+    project_files: &ProjectFiles,
+) -> Result<(), BatchCompileError> {
+    let changed_paths: Vec<_> = changed_paths
+        .iter()
+        .filter(|changed_path| {
+            is_relevant_changed_path(changed_path, canonicalized_root_path, project_files)
         })
-    })
+        .collect();
+
+    let requires_full_walk = match &compiler_state.source_files {
+        None => true,
+        Some(source_files) => changed_paths.iter().any(|changed_path| {
+            changed_path.is_dir()
+                || is_ignore_file(changed_path)
+                || (changed_path.is_file()
+                    && is_valid_project_file(changed_path, canonicalized_root_path, project_files)
+                    && changed_path
+                        .strip_prefix(canonicalized_root_path)
+                        .map(|relative_path| !source_files.contains_key(relative_path))
+                        .unwrap_or(false))
+        }),
+    };
+
+    if requires_full_walk {
+        // TODO return an iterator
+        let files = read_files_in_folder(canonicalized_root_path, project_files)?;
+
+        let mut previous_source_files = compiler_state.source_files.take().unwrap_or_default();
+        let mut source_files = HashMap::new();
+        for (relative_path, content) in files {
+            let source_file = match previous_source_files.remove(&relative_path) {
+                Some(mut previous_source_file) => {
+                    previous_source_file.update_content(content);
+                    previous_source_file
+                }
+                None => SourceFile::new(content),
+            };
+            source_files.insert(relative_path, source_file);
+        }
+
+        compiler_state.source_files = Some(source_files);
+        return Ok(());
+    }
+
+    let source_files = compiler_state
+        .source_files
+        .as_mut()
+        .expect("Expected source files to exist. This is indicative of a bug in Isograph.");
+
+    for changed_path in changed_paths {
+        if changed_path.is_file() {
+            if !is_valid_project_file(changed_path, canonicalized_root_path, project_files) {
+                continue;
+            }
+            let (relative_path, content) =
+                read_file(changed_path.clone(), canonicalized_root_path)?;
+            match source_files.get_mut(&relative_path) {
+                Some(source_file) => source_file.update_content(content),
+                None => {
+                    source_files.insert(relative_path, SourceFile::new(content));
+                }
+            }
+        } else {
+            // The file or directory was deleted
+            let relative_path = changed_path.strip_prefix(canonicalized_root_path)?;
+            source_files.retain(|path, _| !path.starts_with(relative_path));
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse the source files that are new, that have changed, or that could not be
+/// parsed during the previous compilation.
+fn parse_source_files(
+    compiler_state: &mut CompilerState,
+    canonicalized_root_path: &PathBuf,
+) -> Result<(), Vec<WithLocation<IsographLiteralParseError>>> {
+    let source_files = compiler_state
+        .source_files
+        .as_mut()
+        .expect("Expected source files to exist. This is indicative of a bug in Isograph.");
+    let mut isograph_literal_parse_errors = vec![];
+
+    for (relative_path, source_file) in source_files.iter_mut() {
+        if source_file.iso_literals.is_some() {
+            continue;
+        }
+        match extract_iso_literals_from_file(
+            relative_path,
+            &source_file.content,
+            canonicalized_root_path,
+        ) {
+            Ok(iso_literals) => source_file.iso_literals = Some(iso_literals),
+            Err(errors) => isograph_literal_parse_errors.extend(errors),
+        }
+    }
+
+    if isograph_literal_parse_errors.is_empty() {
+        Ok(())
+    } else {
+        Err(isograph_literal_parse_errors)
+    }
+}
+
+fn get_iso_literals_from_source_files(
+    compiler_state: &CompilerState,
+) -> (
+    Vec<(WithSpan<ClientFieldDeclaration>, TextSource)>,
+    Vec<(WithSpan<EntrypointTypeAndField>, TextSource)>,
+) {
+    let mut client_field_declarations_and_text_sources = vec![];
+    let mut entrypoint_declarations_and_text_sources = vec![];
+
+    // Sort, so that the order in which client fields are processed (and thus the
+    // order of any errors) does not depend on the order in which files changed.
+    let mut source_files: Vec<_> = compiler_state
+        .source_files
+        .iter()
+        .flat_map(|source_files| source_files.iter())
+        .collect();
+    source_files.sort_by(|(path_1, _), (path_2, _)| path_1.cmp(path_2));
+
+    for (_, source_file) in source_files {
+        for (extraction_result, text_source) in source_file.iso_literals.iter().flatten() {
+            match extraction_result {
+                IsoLiteralExtractionResult::ClientFieldDeclaration(decl) => {
+                    client_field_declarations_and_text_sources.push((decl.clone(), *text_source))
+                }
+                IsoLiteralExtractionResult::EntrypointDeclaration(decl) => {
+                    entrypoint_declarations_and_text_sources.push((decl.clone(), *text_source))
+                }
+            }
+        }
+    }
+
+    (
+        client_field_declarations_and_text_sources,
+        entrypoint_declarations_and_text_sources,
+    )
 }
 
 fn process_client_fields_and_entrypoints(
@@ -227,55 +737,39 @@ fn process_client_fields_and_entrypoints(
     }
 }
 
-fn extract_iso_literals(
-    project_files: Vec<(PathBuf, String)>,
-    canonicalized_root_path: PathBuf,
+fn extract_iso_literals_from_file(
+    file_path: &PathBuf,
+    file_content: &str,
+    canonicalized_root_path: &PathBuf,
 ) -> Result<
-    (
-        Vec<(WithSpan<ClientFieldDeclaration>, TextSource)>,
-        Vec<(WithSpan<EntrypointTypeAndField>, TextSource)>,
-    ),
+    Vec<(IsoLiteralExtractionResult, TextSource)>,
     Vec<WithLocation<IsographLiteralParseError>>,
 > {
     let mut isograph_literal_parse_errors = vec![];
-    let mut client_field_declarations_and_text_sources = vec![];
-    let mut entrypoint_declarations_and_text_sources = vec![];
+    let mut extraction_results_and_text_sources = vec![];
 
-    for (file_path, file_content) in project_files {
-        // TODO don't intern unless there's a match
-        let interned_file_path = file_path.to_string_lossy().into_owned().intern().into();
+    // TODO don't intern unless there's a match
+    let interned_file_path = file_path.to_string_lossy().into_owned().intern().into();
 
-        let file_name = canonicalized_root_path
-            .join(file_path)
-            .to_str()
-            .expect("file_path should be a valid string")
-            .intern()
-            .into();
-
-        for iso_literal_extraction in extract_iso_literal_from_file_content(&file_content) {
-            match process_iso_literal_extraction(
-                iso_literal_extraction,
-                file_name,
-                interned_file_path,
-            ) {
-                Ok((extraction_result, text_source)) => match extraction_result {
-                    IsoLiteralExtractionResult::ClientFieldDeclaration(decl) => {
-                        client_field_declarations_and_text_sources.push((decl, text_source))
-                    }
-                    IsoLiteralExtractionResult::EntrypointDeclaration(decl) => {
-                        entrypoint_declarations_and_text_sources.push((decl, text_source))
-                    }
-                },
-                Err(e) => isograph_literal_parse_errors.push(e),
+    let file_name = canonicalized_root_path
+        .join(file_path)
+        .to_str()
+        .expect("file_path should be a valid string")
+        .intern()
+        .into();
+
+    for iso_literal_extraction in extract_iso_literal_from_file_content(file_content) {
+        match process_iso_literal_extraction(iso_literal_extraction, file_name, interned_file_path)
+        {
+            Ok(extraction_result_and_text_source) => {
+                extraction_results_and_text_sources.push(extraction_result_and_text_source)
             }
+            Err(e) => isograph_literal_parse_errors.push(e),
         }
     }
 
     if isograph_literal_parse_errors.is_empty() {
-        Ok((
-            client_field_declarations_and_text_sources,
-            entrypoint_declarations_and_text_sources,
-        ))
+        Ok(extraction_results_and_text_sources)
     } else {
         Err(isograph_literal_parse_errors)
     }
@@ -289,6 +783,7 @@ fn process_iso_literal_extraction(
     let IsoLiteralExtraction {
         iso_literal_text,
         iso_literal_start_index,
+        iso_call_span,
         has_associated_js_function,
         const_export_name,
         has_paren,
@@ -301,10 +796,27 @@ fn process_iso_literal_extraction(
         )),
     };
 
+    // The iso call is not within the iso literal, so errors about it are located
+    // relative to the entire file.
+    let iso_call_location = Location::new(
+        TextSource {
+            path: file_name,
+            span: None,
+        },
+        iso_call_span,
+    );
+
     if !has_paren {
         return Err(WithLocation::new(
             IsographLiteralParseError::ExpectedParenthesesAroundIsoLiteral,
-            Location::new(text_source, Span::todo_generated()),
+            iso_call_location,
+        ));
+    }
+
+    if iso_literal_text.trim().is_empty() {
+        return Err(WithLocation::new(
+            IsographLiteralParseError::EmptyIsoLiteral,
+            iso_call_location,
         ));
     }
 
@@ -323,7 +835,7 @@ fn process_iso_literal_extraction(
         if !has_associated_js_function {
             return Err(WithLocation::new(
                 IsographLiteralParseError::ExpectedAssociatedJsFunction,
-                Location::new(text_source, Span::todo_generated()),
+                iso_call_location,
             ));
         }
     }
@@ -354,6 +866,9 @@ pub(crate) enum BatchCompileError {
     #[error("Unable to traverse directory.\nReason: {0}")]
     UnableToTraverseDirectory(#[from] std::io::Error),
 
+    #[error("Unable to walk the project root.\nReason: {0}")]
+    UnableToWalkProjectRoot(#[from] ignore::Error),
+
     #[error("Unable to parse schema.\n\n{0}")]
     UnableToParseSchema(#[from] WithLocation<SchemaParseError>),
 
@@ -393,7 +908,10 @@ pub(crate) enum BatchCompileError {
     #[error(
         "{} when validating schema, client fields and entrypoint declarations.{}",
         if messages.len() == 1 { "Error" } else { "Errors" },
-        messages.into_iter().map(|x| format!("\n\n{x}")).collect::<String>()
+        messages
+            .into_iter()
+            .map(|x| format!("\n\n{}", x.to_diagnostic(Severity::Error)))
+            .collect::<String>()
     )]
     UnableToValidateSchema {
         messages: Vec<WithLocation<isograph_schema::ValidateSchemaError>>,
@@ -404,6 +922,86 @@ pub(crate) enum BatchCompileError {
 
     #[error("Unable to convert file {path:?} to utf8.\nDetailed reason: {reason}")]
     UnableToConvertToString { path: PathBuf, reason: Utf8Error },
+
+    #[error("The artifacts on disk are out of date. Re-run the compiler to update them.\n{0}")]
+    ArtifactsAreOutOfDate(ArtifactDiff),
+}
+
+impl BatchCompileError {
+    /// Errors that carry locations are converted to one diagnostic per location.
+    /// Other errors are converted to a single diagnostic without a location.
+    pub(crate) fn to_diagnostics(&self) -> Vec<Diagnostic> {
+        match self {
+            BatchCompileError::UnableToParseSchema(error) => {
+                vec![error.to_diagnostic(Severity::Error)]
+            }
+            BatchCompileError::UnableToParseIsographLiterals { messages } => messages
+                .iter()
+                .map(|message| message.to_diagnostic(Severity::Error))
+                .collect(),
+            BatchCompileError::UnableToCreateSchema(error) => {
+                vec![error.to_diagnostic(Severity::Error)]
+            }
+            BatchCompileError::ErrorWhenProcessingClientFieldDeclaration { messages } => messages
+                .iter()
+                .map(|message| message.to_diagnostic(Severity::Error))
+                .collect(),
+            BatchCompileError::ErrorWhenProcessingEntrypointDeclaration(error) => {
+                vec![error.to_diagnostic(Severity::Error)]
+            }
+            BatchCompileError::UnableToValidateSchema { messages } => messages
+                .iter()
+                .map(|message| message.to_diagnostic(Severity::Error))
+                .collect(),
+            _ => vec![Diagnostic::new(
+                self.error_code(),
+                Severity::Error,
+                self.to_string(),
+                Location::generated(),
+            )],
+        }
+    }
+}
+
+impl ErrorCode for BatchCompileError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            BatchCompileError::UnableToLoadSchema { .. } => "batch_compile/unable_to_load_schema",
+            BatchCompileError::SchemaNotAFile { .. } => "batch_compile/schema_not_a_file",
+            BatchCompileError::ProjectRootNotADirectory { .. } => {
+                "batch_compile/project_root_not_a_directory"
+            }
+            BatchCompileError::UnableToReadFile { .. } => "batch_compile/unable_to_read_file",
+            BatchCompileError::UnableToTraverseDirectory(_) => {
+                "batch_compile/unable_to_traverse_directory"
+            }
+            BatchCompileError::UnableToWalkProjectRoot(_) => {
+                "batch_compile/unable_to_walk_project_root"
+            }
+            BatchCompileError::UnableToParseSchema(error) => error.item.error_code(),
+            BatchCompileError::UnableToParseIsographLiterals { .. } => {
+                "batch_compile/unable_to_parse_isograph_literals"
+            }
+            BatchCompileError::UnableToCreateSchema(error) => error.item.error_code(),
+            BatchCompileError::ErrorWhenProcessingClientFieldDeclaration { .. } => {
+                "batch_compile/error_when_processing_client_field_declaration"
+            }
+            BatchCompileError::ErrorWhenProcessingEntrypointDeclaration(error) => {
+                error.item.error_code()
+            }
+            BatchCompileError::UnableToStripPrefix(_) => "batch_compile/unable_to_strip_prefix",
+            BatchCompileError::UnableToValidateSchema { .. } => {
+                "batch_compile/unable_to_validate_schema"
+            }
+            BatchCompileError::UnableToPrint(_) => "batch_compile/unable_to_print",
+            BatchCompileError::UnableToConvertToString { .. } => {
+                "batch_compile/unable_to_convert_to_string"
+            }
+            BatchCompileError::ArtifactsAreOutOfDate(_) => {
+                "batch_compile/artifacts_are_out_of_date"
+            }
+        }
+    }
 }
 
 impl From<Vec<WithLocation<IsographLiteralParseError>>> for BatchCompileError {
@@ -423,3 +1021,299 @@ impl From<Vec<WithLocation<ProcessClientFieldDeclarationError>>> for BatchCompil
         BatchCompileError::ErrorWhenProcessingClientFieldDeclaration { messages }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    use isograph_config::ProjectFiles;
+    use tempfile::TempDir;
+
+    use super::{parse_source_files, update_source_files_from_disk};
+    use crate::{
+        compiler_state::{CompilerState, SourceFile},
+        isograph_literals::is_relevant_changed_path,
+    };
+
+    const AVATAR: &str = "export const Avatar = iso(`\n  field User.Avatar {\n    name\n  }\n`)\
+        (function Avatar(data) {\n  return data.name;\n});\n";
+
+    /// A compiler state containing the given source files, parsed as if they had
+    /// been read from /project.
+    fn parsed_compiler_state(source_files: &[(&str, &str)]) -> CompilerState {
+        let mut compiler_state = CompilerState::default();
+        compiler_state.source_files = Some(
+            source_files
+                .iter()
+                .map(|(path, content)| (PathBuf::from(path), SourceFile::new(content.to_string())))
+                .collect(),
+        );
+        parse_source_files(&mut compiler_state, &PathBuf::from("/project"))
+            .expect("Expected source files to parse");
+        compiler_state
+    }
+
+    /// A project root containing the given files.
+    fn project_root(files: &[(&str, &str)]) -> (TempDir, PathBuf) {
+        let temp_dir = tempfile::tempdir().expect("Expected temp dir to be created");
+        let canonicalized_root_path = temp_dir
+            .path()
+            .canonicalize()
+            .expect("Expected temp dir to be canonicalized");
+        for (relative_path, content) in files {
+            write_file(&canonicalized_root_path, relative_path, content.as_bytes());
+        }
+        (temp_dir, canonicalized_root_path)
+    }
+
+    fn write_file(canonicalized_root_path: &Path, relative_path: &str, content: &[u8]) {
+        let path = canonicalized_root_path.join(relative_path);
+        fs::create_dir_all(path.parent().expect("Expected file to have a parent"))
+            .expect("Expected directory to be created");
+        fs::write(path, content).expect("Expected file to be written");
+    }
+
+    /// Mark the given paths as changed and bring the source files up to date with
+    /// the files on disk. Returns the path and content of each source file, sorted.
+    fn update_source_files(
+        compiler_state: &mut CompilerState,
+        canonicalized_root_path: &PathBuf,
+        changed_paths: &[&str],
+    ) -> Vec<(String, String)> {
+        compiler_state.mark_paths_as_changed(
+            changed_paths
+                .iter()
+                .map(|changed_path| canonicalized_root_path.join(changed_path)),
+        );
+        update_source_files_from_disk(
+            compiler_state,
+            canonicalized_root_path,
+            &ProjectFiles::default(),
+        )
+        .expect("Expected source files to be updated");
+
+        let mut source_files: Vec<_> = compiler_state
+            .source_files
+            .iter()
+            .flatten()
+            .map(|(path, source_file)| {
+                (
+                    path.to_string_lossy().into_owned(),
+                    source_file.content.clone(),
+                )
+            })
+            .collect();
+        source_files.sort();
+        source_files
+    }
+
+    fn source_files(files: &[(&str, &str)]) -> Vec<(String, String)> {
+        files
+            .iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn inputs_hash_ignores_edits_outside_of_iso_literals() {
+        let edited_avatar = AVATAR.replace("data.name;", "data.name ?? 'Anonymous';");
+        assert_eq!(
+            parsed_compiler_state(&[("Avatar.tsx", AVATAR)]).inputs_hash(),
+            parsed_compiler_state(&[("Avatar.tsx", &edited_avatar)]).inputs_hash()
+        );
+    }
+
+    #[test]
+    fn inputs_hash_ignores_files_without_iso_literals() {
+        assert_eq!(
+            parsed_compiler_state(&[("Avatar.tsx", AVATAR)]).inputs_hash(),
+            parsed_compiler_state(&[("Avatar.tsx", AVATAR), ("utils.ts", "export const x = 1;")])
+                .inputs_hash()
+        );
+    }
+
+    #[test]
+    fn inputs_hash_changes_if_an_iso_literal_changes() {
+        let edited_avatar = AVATAR.replace("    name\n", "    id\n    name\n");
+        assert_ne!(
+            parsed_compiler_state(&[("Avatar.tsx", AVATAR)]).inputs_hash(),
+            parsed_compiler_state(&[("Avatar.tsx", &edited_avatar)]).inputs_hash()
+        );
+    }
+
+    #[test]
+    fn inputs_hash_changes_if_an_iso_literal_moves() {
+        // Diagnostics are located against the position of the iso literal.
+        let edited_avatar = format!("// The avatar of a user\n{AVATAR}");
+        assert_ne!(
+            parsed_compiler_state(&[("Avatar.tsx", AVATAR)]).inputs_hash(),
+            parsed_compiler_state(&[("Avatar.tsx", &edited_avatar)]).inputs_hash()
+        );
+    }
+
+    #[test]
+    fn source_file_is_reparsed_only_if_its_content_changed() {
+        let mut compiler_state = parsed_compiler_state(&[("Avatar.tsx", AVATAR)]);
+        let source_file = compiler_state
+            .source_files
+            .as_mut()
+            .and_then(|source_files| source_files.get_mut(Path::new("Avatar.tsx")))
+            .expect("Expected Avatar.tsx to be a source file");
+
+        source_file.update_content(AVATAR.to_string());
+        assert!(source_file.iso_literals.is_some());
+
+        source_file.update_content(AVATAR.replace("data.name;", "null;"));
+        assert!(source_file.iso_literals.is_none());
+    }
+
+    #[test]
+    fn first_compilation_walks_the_project_root() {
+        let (_temp_dir, root) = project_root(&[
+            ("a.ts", "a"),
+            ("components/b.tsx", "b"),
+            ("node_modules/library/c.ts", "c"),
+            ("__isograph/d.ts", "d"),
+        ]);
+        let mut compiler_state = CompilerState::default();
+
+        assert_eq!(
+            update_source_files(&mut compiler_state, &root, &[]),
+            source_files(&[("a.ts", "a"), ("components/b.tsx", "b")])
+        );
+    }
+
+    #[test]
+    fn ignored_directories_and_other_file_extensions_are_skipped_when_walking() {
+        let (_temp_dir, root) = project_root(&[
+            (".gitignore", "dist/\n"),
+            ("a.ts", "a"),
+            ("b.mts", "b"),
+            ("dist/c.ts", "c"),
+            ("packages/app/node_modules/library/d.ts", "d"),
+        ]);
+        let mut compiler_state = CompilerState::default();
+
+        assert_eq!(
+            update_source_files(&mut compiler_state, &root, &[]),
+            source_files(&[("a.ts", "a")])
+        );
+    }
+
+    #[test]
+    fn changed_paths_are_relevant_unless_skipped_when_walking() {
+        let (_temp_dir, root) = project_root(&[
+            (".gitignore", "dist/\n"),
+            ("a.ts", "a"),
+            ("b.mts", "b"),
+            ("components/c.tsx", "c"),
+            ("dist/d.ts", "d"),
+            ("node_modules/library/e.ts", "e"),
+            ("__isograph/Query/f.ts", "f"),
+        ]);
+        let is_relevant = |relative_path: &str| {
+            is_relevant_changed_path(&root.join(relative_path), &root, &ProjectFiles::default())
+        };
+
+        assert!(is_relevant("a.ts"));
+        assert!(is_relevant("components"));
+        assert!(is_relevant("components/c.tsx"));
+        assert!(is_relevant(".gitignore"));
+        // Deleted paths are relevant, since their source files must be forgotten.
+        assert!(is_relevant("deleted.ts"));
+
+        assert!(!is_relevant("b.mts"));
+        assert!(!is_relevant("dist"));
+        assert!(!is_relevant("dist/d.ts"));
+        assert!(!is_relevant("node_modules"));
+        assert!(!is_relevant("node_modules/library/e.ts"));
+        assert!(!is_relevant("__isograph/Query/f.ts"));
+        assert!(!is_relevant_changed_path(
+            Path::new("/outside.ts"),
+            &root,
+            &ProjectFiles::default()
+        ));
+    }
+
+    #[test]
+    fn only_changed_files_are_reread() {
+        let (_temp_dir, root) = project_root(&[("a.ts", "a"), ("b.ts", "b")]);
+        let mut compiler_state = CompilerState::default();
+        update_source_files(&mut compiler_state, &root, &[]);
+
+        write_file(&root, "a.ts", b"a2");
+        write_file(&root, "b.ts", b"b2");
+        assert_eq!(
+            update_source_files(&mut compiler_state, &root, &["a.ts"]),
+            source_files(&[("a.ts", "a2"), ("b.ts", "b")])
+        );
+    }
+
+    #[test]
+    fn new_file_causes_the_project_root_to_be_walked() {
+        let (_temp_dir, root) = project_root(&[("a.ts", "a"), ("b.ts", "b")]);
+        let mut compiler_state = CompilerState::default();
+        update_source_files(&mut compiler_state, &root, &[]);
+
+        write_file(&root, "b.ts", b"b2");
+        write_file(&root, "c.ts", b"c");
+        assert_eq!(
+            update_source_files(&mut compiler_state, &root, &["c.ts"]),
+            source_files(&[("a.ts", "a"), ("b.ts", "b2"), ("c.ts", "c")])
+        );
+    }
+
+    #[test]
+    fn deleted_files_and_directories_are_forgotten() {
+        let (_temp_dir, root) = project_root(&[
+            ("a.ts", "a"),
+            ("b.ts", "b"),
+            ("components/c.tsx", "c"),
+            ("components/d.tsx", "d"),
+        ]);
+        let mut compiler_state = CompilerState::default();
+        update_source_files(&mut compiler_state, &root, &[]);
+
+        fs::remove_file(root.join("a.ts")).expect("Expected file to be removed");
+        fs::remove_dir_all(root.join("components")).expect("Expected directory to be removed");
+        assert_eq!(
+            update_source_files(&mut compiler_state, &root, &["a.ts", "components"]),
+            source_files(&[("b.ts", "b")])
+        );
+    }
+
+    #[test]
+    fn changes_within_excluded_directories_are_skipped() {
+        let (_temp_dir, root) = project_root(&[("a.ts", "a")]);
+        let mut compiler_state = CompilerState::default();
+        update_source_files(&mut compiler_state, &root, &[]);
+
+        // Had the project root been walked, the change to a.ts would be picked up.
+        write_file(&root, "a.ts", b"a2");
+        write_file(&root, "node_modules/library/b.ts", b"b");
+        assert_eq!(
+            update_source_files(&mut compiler_state, &root, &["node_modules/library/b.ts"]),
+            source_files(&[("a.ts", "a")])
+        );
+    }
+
+    #[test]
+    fn changed_paths_are_kept_if_reading_fails() {
+        let (_temp_dir, root) = project_root(&[("a.ts", "a")]);
+        let mut compiler_state = CompilerState::default();
+        update_source_files(&mut compiler_state, &root, &[]);
+
+        write_file(&root, "a.ts", &[0xff, 0xfe]);
+        compiler_state.mark_paths_as_changed(std::iter::once(root.join("a.ts")));
+        assert!(update_source_files_from_disk(
+            &mut compiler_state,
+            &root,
+            &ProjectFiles::default()
+        )
+        .is_err());
+        assert!(compiler_state.changed_paths.contains(&root.join("a.ts")));
+    }
+}
@@ -0,0 +1,86 @@
+use std::{collections::HashSet, fmt, path::PathBuf};
+
+use crate::{
+    generate_artifacts::{GenerateArtifactsError, PathAndContent},
+    write_artifacts::{artifact_content_matches, visit_artifact_files},
+};
+
+/// The differences between the artifacts that would be generated and the
+/// artifacts that are currently on disk. Paths are relative to the artifact
+/// directory.
+#[derive(Debug, Default)]
+pub(crate) struct ArtifactDiff {
+    pub(crate) missing: Vec<PathBuf>,
+    pub(crate) changed: Vec<PathBuf>,
+    pub(crate) extra: Vec<PathBuf>,
+}
+
+impl ArtifactDiff {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.changed.is_empty() && self.extra.is_empty()
+    }
+}
+
+impl fmt::Display for ArtifactDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (label, paths) in [
+            ("Missing", &self.missing),
+            ("Changed", &self.changed),
+            ("Extra", &self.extra),
+        ] {
+            if !paths.is_empty() {
+                write!(f, "\n{label}:")?;
+                for path in paths {
+                    write!(f, "\n  {}", path.display())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare the artifacts that would be generated with the contents of the artifact
+/// directory, without writing anything.
+pub(crate) fn diff_artifacts_against_disk(
+    paths_and_contents: impl Iterator<Item = PathAndContent>,
+    artifact_directory: &PathBuf,
+) -> Result<ArtifactDiff, GenerateArtifactsError> {
+    let mut diff = ArtifactDiff::default();
+    let mut expected_paths = HashSet::new();
+
+    for path_and_content in paths_and_contents {
+        let relative_file_path = path_and_content
+            .relative_directory
+            .join(&format!("{}.ts", path_and_content.file_name_prefix));
+        let absolute_file_path = artifact_directory.join(&relative_file_path);
+
+        if !absolute_file_path.is_file() {
+            diff.missing.push(relative_file_path.clone());
+        } else if !artifact_content_matches(&absolute_file_path, &path_and_content.file_content)? {
+            diff.changed.push(relative_file_path.clone());
+        }
+
+        expected_paths.insert(relative_file_path);
+    }
+
+    if artifact_directory.exists() {
+        visit_artifact_files(artifact_directory, &mut |dir_entry| {
+            let path = dir_entry.path();
+            if let Ok(relative_file_path) = path.strip_prefix(artifact_directory) {
+                if !expected_paths.contains(relative_file_path) {
+                    diff.extra.push(relative_file_path.to_path_buf());
+                }
+            }
+        })
+        .map_err(|e| GenerateArtifactsError::UnableToReadArtifactFile {
+            path: artifact_directory.clone(),
+            message: e,
+        })?;
+    }
+
+    diff.missing.sort();
+    diff.changed.sort();
+    diff.extra.sort();
+
+    Ok(diff)
+}
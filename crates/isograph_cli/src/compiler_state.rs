@@ -0,0 +1,182 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use common_lang_types::{Diagnostic, TextSource};
+use graphql_lang_types::{GraphQLTypeSystemDocument, GraphQLTypeSystemExtensionDocument};
+use isograph_config::CompilerConfig;
+use isograph_lang_parser::IsoLiteralExtractionResult;
+use isograph_schema::{TypeRefinementMap, UnvalidatedSchema};
+
+use crate::write_artifacts::ArtifactHashes;
+
+/// State that is kept in memory between compilations. In batch mode, a fresh
+/// CompilerState is used for the single compilation. In watch mode, the same
+/// CompilerState is reused, so that only files that have changed since the
+/// previous compilation are re-read and re-parsed, the server schema is only
+/// re-processed if the schema or a schema extension changed, and only changed
+/// artifacts are written.
+///
+/// If none of the iso literals changed (e.g. because only the body of a component
+/// was edited), validation and artifact generation are skipped. Otherwise, we do
+/// not track which client fields depend on the changed files, so the entire schema
+/// is re-validated and every artifact is regenerated.
+#[derive(Default)]
+pub(crate) struct CompilerState {
+    pub(crate) schema: Option<CachedSource<GraphQLTypeSystemDocument>>,
+    pub(crate) schema_extensions:
+        HashMap<PathBuf, CachedSource<GraphQLTypeSystemExtensionDocument>>,
+    /// None if the schema or a schema extension changed since it was processed.
+    pub(crate) server_schema: Option<ServerSchema>,
+    /// Keyed by the path relative to the project root. If this is None, we must
+    /// walk the entire project root.
+    pub(crate) source_files: Option<HashMap<PathBuf, SourceFile>>,
+    /// The canonicalized project root, against which source_files are keyed.
+    pub(crate) canonicalized_root_path: Option<PathBuf>,
+    /// Absolute paths that the watcher reported as changed since the last compilation.
+    pub(crate) changed_paths: HashSet<PathBuf>,
+    /// The unsaved content of documents that are open in an editor, keyed by
+    /// absolute path. Only the language server populates this.
+    pub(crate) open_documents: HashMap<PathBuf, String>,
+    /// None if we have not yet written artifacts, or if writing them failed.
+    pub(crate) artifact_hashes: Option<ArtifactHashes>,
+    pub(crate) previous_compilation: Option<PreviousCompilation>,
+}
+
+/// The schema after processing the schema and schema extensions, but before
+/// processing iso literals.
+pub(crate) struct ServerSchema {
+    pub(crate) schema: UnvalidatedSchema,
+    pub(crate) supertype_to_subtype_map: TypeRefinementMap,
+    /// The warnings issued while processing, which are reported again whenever the
+    /// server schema is reused.
+    pub(crate) warnings: Vec<Diagnostic>,
+}
+
+/// The most recent compilation that successfully wrote artifacts.
+pub(crate) struct PreviousCompilation {
+    /// See CompilerState::inputs_hash
+    pub(crate) inputs_hash: u64,
+    /// The warnings issued while validating, which are reported again if the next
+    /// compilation is skipped.
+    pub(crate) validation_warnings: Vec<Diagnostic>,
+    pub(crate) artifact_count: usize,
+}
+
+pub(crate) struct CachedSource<T> {
+    pub(crate) content: String,
+    /// None if the content could not be parsed.
+    pub(crate) parsed: Option<T>,
+}
+
+pub(crate) struct SourceFile {
+    /// The content that was compiled, which diagnostics are located against.
+    pub(crate) content: String,
+    /// None if the file contained an iso literal that could not be parsed.
+    /// Such files are re-parsed on every compilation, so that the errors are
+    /// reported until they are fixed.
+    pub(crate) iso_literals: Option<Vec<(IsoLiteralExtractionResult, TextSource)>>,
+}
+
+impl CompilerState {
+    pub(crate) fn mark_paths_as_changed(&mut self, paths: impl Iterator<Item = PathBuf>) {
+        self.changed_paths.extend(paths);
+    }
+
+    /// A hash of the schema, schema extensions and the iso literals in the source
+    /// files, as of the most recent compilation. If this has not changed, neither
+    /// have the artifacts.
+    pub(crate) fn inputs_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        if let Some(schema) = &self.schema {
+            schema.content.hash(&mut hasher);
+        }
+
+        let mut schema_extensions: Vec<_> = self.schema_extensions.iter().collect();
+        schema_extensions.sort_by(|(path_1, _), (path_2, _)| path_1.cmp(path_2));
+        for (path, schema_extension) in schema_extensions {
+            path.hash(&mut hasher);
+            schema_extension.content.hash(&mut hasher);
+        }
+
+        let mut source_files: Vec<_> = self.source_files.iter().flatten().collect();
+        source_files.sort_by(|(path_1, _), (path_2, _)| path_1.cmp(path_2));
+        for (path, source_file) in source_files {
+            source_file.hash_iso_literals(path, &mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// The text of a file as of the most recent compilation, i.e. the text against
+    /// which the spans of diagnostics were computed. Returns None for files that
+    /// were not compiled, e.g. because they could not be read.
+    pub(crate) fn file_contents(&self, config: &CompilerConfig, path: &Path) -> Option<&str> {
+        if path == config.schema.as_path() {
+            return self.schema.as_ref().map(|schema| schema.content.as_str());
+        }
+        if let Some(schema_extension) = self.schema_extensions.get(path) {
+            return Some(&schema_extension.content);
+        }
+        let relative_path = path
+            .strip_prefix(self.canonicalized_root_path.as_ref()?)
+            .ok()?;
+        self.source_files
+            .as_ref()?
+            .get(relative_path)
+            .map(|source_file| source_file.content.as_str())
+    }
+}
+
+impl SourceFile {
+    pub(crate) fn new(content: String) -> Self {
+        SourceFile {
+            content,
+            iso_literals: None,
+        }
+    }
+
+    /// Replace the content of this file. If the content changed, the file must be
+    /// re-parsed.
+    pub(crate) fn update_content(&mut self, content: String) {
+        if self.content != content {
+            *self = SourceFile::new(content);
+        }
+    }
+
+    /// Artifacts only depend on a source file through its iso literals, so files
+    /// without iso literals are not hashed, and editing a file outside of its iso
+    /// literals does not change the hash. The position of each iso literal is
+    /// hashed, since diagnostics are located against it. If the file has not been
+    /// parsed, its entire content is hashed.
+    fn hash_iso_literals(&self, path: &Path, hasher: &mut DefaultHasher) {
+        let iso_literals = match &self.iso_literals {
+            Some(iso_literals) => iso_literals,
+            None => {
+                path.hash(hasher);
+                self.content.hash(hasher);
+                return;
+            }
+        };
+        if iso_literals.is_empty() {
+            return;
+        }
+
+        path.hash(hasher);
+        for (extraction_result, text_source) in iso_literals {
+            text_source.hash(hasher);
+            if let Some(span) = text_source.span {
+                self.content[span.start as usize..span.end as usize].hash(hasher);
+            }
+            // The const export name is not part of the iso literal, but it is part
+            // of the client field declaration.
+            if let IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration) =
+                extraction_result
+            {
+                client_field_declaration.item.const_export_name.hash(hasher);
+            }
+        }
+    }
+}
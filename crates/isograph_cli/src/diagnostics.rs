@@ -0,0 +1,301 @@
+use std::{path::Path, str::FromStr};
+
+use common_lang_types::{Diagnostic, LineAndColumn, Location, Severity};
+use isograph_config::CompilerConfig;
+use lsp_types::Url;
+use serde_json::{json, Value};
+
+use crate::compiler_state::CompilerState;
+
+/// Machine-readable formats in which errors and warnings can be printed,
+/// for use by editors and CI annotators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiagnosticsFormat {
+    Json,
+    Sarif,
+}
+
+impl FromStr for DiagnosticsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(DiagnosticsFormat::Json),
+            "sarif" => Ok(DiagnosticsFormat::Sarif),
+            _ => Err(format!(
+                "Unknown diagnostics format \"{s}\". Expected json or sarif."
+            )),
+        }
+    }
+}
+
+type LineAndColumnRange = (LineAndColumn, LineAndColumn);
+
+/// A diagnostic, and the ranges of its location and related locations within the
+/// text that was compiled. A range is None if the location is generated, or if the
+/// file containing it was not compiled.
+pub(crate) struct LocatedDiagnostic {
+    diagnostic: Diagnostic,
+    range: Option<LineAndColumnRange>,
+    related_ranges: Vec<Option<LineAndColumnRange>>,
+}
+
+impl LocatedDiagnostic {
+    /// The range must be computed before the compiler state changes, i.e. right
+    /// after the compilation that issued the diagnostic.
+    pub(crate) fn new(
+        diagnostic: Diagnostic,
+        config: &CompilerConfig,
+        compiler_state: &CompilerState,
+    ) -> Self {
+        let range = line_and_column_range(diagnostic.location, config, compiler_state);
+        let related_ranges = diagnostic
+            .related_locations
+            .iter()
+            .map(|related_location| {
+                line_and_column_range(related_location.location, config, compiler_state)
+            })
+            .collect();
+        LocatedDiagnostic {
+            diagnostic,
+            range,
+            related_ranges,
+        }
+    }
+}
+
+fn line_and_column_range(
+    location: Location,
+    config: &CompilerConfig,
+    compiler_state: &CompilerState,
+) -> Option<LineAndColumnRange> {
+    match location {
+        Location::Embedded(location) => compiler_state
+            .file_contents(config, Path::new(location.file_path()))
+            .map(|file_contents| location.line_and_column_range(file_contents)),
+        Location::Generated => None,
+    }
+}
+
+/// Print the diagnostics to stdout, in the given format.
+pub(crate) fn print_diagnostics(format: DiagnosticsFormat, diagnostics: &[LocatedDiagnostic]) {
+    let output = match format {
+        DiagnosticsFormat::Json => diagnostics_to_json(diagnostics),
+        DiagnosticsFormat::Sarif => {
+            // Code scanning tools resolve paths relative to the checkout, which is
+            // usually the directory the compiler is run from.
+            let source_root = std::env::current_dir()
+                .ok()
+                .and_then(|current_dir| Url::from_directory_path(current_dir).ok());
+            diagnostics_to_sarif(diagnostics, source_root.as_ref())
+        }
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&output).expect("Expected diagnostics to be serializable")
+    );
+}
+
+fn diagnostics_to_json(diagnostics: &[LocatedDiagnostic]) -> Value {
+    let diagnostics: Vec<_> = diagnostics
+        .iter()
+        .map(
+            |LocatedDiagnostic {
+                 diagnostic,
+                 range,
+                 related_ranges,
+             }| {
+                let related_locations: Vec<_> = diagnostic
+                    .related_locations
+                    .iter()
+                    .zip(related_ranges)
+                    .map(|(related_location, related_range)| {
+                        json!({
+                            "message": related_location.item,
+                            "file": file_path(related_location.location),
+                            "range": json_range(*related_range),
+                        })
+                    })
+                    .collect();
+                json!({
+                    "code": diagnostic.code,
+                    "severity": diagnostic.severity.as_str(),
+                    "message": diagnostic.message,
+                    "file": file_path(diagnostic.location),
+                    "range": json_range(*range),
+                    "relatedLocations": related_locations,
+                })
+            },
+        )
+        .collect();
+
+    json!({ "diagnostics": diagnostics })
+}
+
+fn file_path(location: Location) -> Option<&'static str> {
+    match location {
+        Location::Embedded(location) => Some(location.file_path()),
+        Location::Generated => None,
+    }
+}
+
+fn json_range(range: Option<LineAndColumnRange>) -> Option<Value> {
+    range.map(|(start, end)| {
+        json!({
+            "start": { "line": start.line, "column": start.column },
+            "end": { "line": end.line, "column": end.column },
+        })
+    })
+}
+
+/// The uriBaseId of artifact locations within the source root.
+const SOURCE_ROOT_URI_BASE_ID: &str = "%SRCROOT%";
+
+fn diagnostics_to_sarif(diagnostics: &[LocatedDiagnostic], source_root: Option<&Url>) -> Value {
+    let mut rule_ids: Vec<_> = diagnostics
+        .iter()
+        .map(|located_diagnostic| located_diagnostic.diagnostic.code)
+        .collect();
+    rule_ids.sort();
+    rule_ids.dedup();
+
+    let results: Vec<_> = diagnostics
+        .iter()
+        .map(
+            |LocatedDiagnostic {
+                 diagnostic,
+                 range,
+                 related_ranges,
+             }| {
+                let locations: Vec<_> =
+                    sarif_physical_location(diagnostic.location, *range, source_root)
+                        .map(|physical_location| json!({ "physicalLocation": physical_location }))
+                        .into_iter()
+                        .collect();
+                let related_locations: Vec<_> = diagnostic
+                    .related_locations
+                    .iter()
+                    .zip(related_ranges)
+                    .enumerate()
+                    .map(|(index, (related_location, related_range))| {
+                        let mut sarif_location = json!({
+                            "id": index,
+                            "message": { "text": related_location.item },
+                        });
+                        if let Some(physical_location) = sarif_physical_location(
+                            related_location.location,
+                            *related_range,
+                            source_root,
+                        ) {
+                            sarif_location["physicalLocation"] = physical_location;
+                        }
+                        sarif_location
+                    })
+                    .collect();
+                json!({
+                    "ruleId": diagnostic.code,
+                    "level": match diagnostic.severity {
+                        Severity::Error => "error",
+                        Severity::Warning => "warning",
+                    },
+                    "message": { "text": diagnostic.message },
+                    "locations": locations,
+                    "relatedLocations": related_locations,
+                })
+            },
+        )
+        .collect();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "isograph",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rule_ids
+                    .into_iter()
+                    .map(|rule_id| json!({ "id": rule_id }))
+                    .collect::<Vec<_>>(),
+            }
+        },
+        "results": results,
+    });
+    if let Some(source_root) = source_root {
+        run["originalUriBaseIds"] = json!({
+            SOURCE_ROOT_URI_BASE_ID: { "uri": source_root.as_str() },
+        });
+    }
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [run],
+    })
+}
+
+fn sarif_physical_location(
+    location: Location,
+    range: Option<LineAndColumnRange>,
+    source_root: Option<&Url>,
+) -> Option<Value> {
+    match location {
+        Location::Embedded(location) => {
+            let mut physical_location = json!({
+                "artifactLocation": sarif_artifact_location(location.file_path(), source_root),
+            });
+            // Columns are UTF-16 code units, which is SARIF's default columnKind.
+            if let Some((start, end)) = range {
+                physical_location["region"] = json!({
+                    "startLine": start.line,
+                    "startColumn": start.column,
+                    "endLine": end.line,
+                    "endColumn": end.column,
+                });
+            }
+            Some(physical_location)
+        }
+        Location::Generated => None,
+    }
+}
+
+/// A file within the source root is located by a relative URI, so that the SARIF
+/// log does not depend on where the repository is checked out. Any other file is
+/// located by its file:// URL.
+fn sarif_artifact_location(file_path: &str, source_root: Option<&Url>) -> Value {
+    let file_url = match Url::from_file_path(file_path) {
+        Ok(file_url) => file_url,
+        // File paths are absolute, so this should not happen.
+        Err(()) => return json!({ "uri": file_path }),
+    };
+    match source_root.and_then(|source_root| file_url.as_str().strip_prefix(source_root.as_str())) {
+        Some(relative_uri) => json!({
+            "uri": relative_uri,
+            "uriBaseId": SOURCE_ROOT_URI_BASE_ID,
+        }),
+        None => json!({ "uri": file_url.as_str() }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Url;
+    use serde_json::json;
+
+    use super::sarif_artifact_location;
+
+    #[test]
+    fn sarif_artifact_location_is_relative_to_the_source_root() {
+        let source_root = Url::from_directory_path("/repo").unwrap();
+        assert_eq!(
+            sarif_artifact_location("/repo/src/My Component.tsx", Some(&source_root)),
+            json!({ "uri": "src/My%20Component.tsx", "uriBaseId": "%SRCROOT%" })
+        );
+        assert_eq!(
+            sarif_artifact_location("/elsewhere/src/Component.tsx", Some(&source_root)),
+            json!({ "uri": "file:///elsewhere/src/Component.tsx" })
+        );
+        assert_eq!(
+            sarif_artifact_location("/repo/src/Component.tsx", None),
+            json!({ "uri": "file:///repo/src/Component.tsx" })
+        );
+    }
+}
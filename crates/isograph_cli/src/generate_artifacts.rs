@@ -1,6 +1,6 @@
 use std::{
     cmp::Ordering,
-    collections::{hash_map::Entry, HashMap, HashSet},
+    collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display},
     io,
     path::PathBuf,
@@ -8,30 +8,38 @@ use std::{
 };
 
 use common_lang_types::{
-    HasName, IsographObjectTypeName, Location, QueryOperationName, SelectableFieldName, Span,
-    UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
+    DescriptionValue, HasName, IsographObjectTypeName, Location, QueryOperationName,
+    SelectableFieldName, Span, StringLiteralValue, UnvalidatedTypeName, VariableName, WithLocation,
+    WithSpan,
 };
 use graphql_lang_types::{
-    GraphQLInputValueDefinition, ListTypeAnnotation, NamedTypeAnnotation, NonNullTypeAnnotation,
-    TypeAnnotation,
+    print_string_literal, ConstantValue, GraphQLInputValueDefinition, ListTypeAnnotation,
+    NamedTypeAnnotation, NonNullTypeAnnotation, RootOperationKind, TypeAnnotation,
 };
 use intern::{string_key::Intern, Lookup};
+use isograph_config::CustomScalarTypeImportPath;
 use isograph_lang_types::{
-    ClientFieldId, NonConstantValue, SelectableFieldId, Selection, SelectionFieldArgument,
-    ServerFieldSelection, VariableDefinition,
+    unwrapped_levels, ClientFieldId, FragmentDirectiveUsage, InputObjectId, NonConstantValue,
+    ObjectId, ScalarId, SelectableFieldId, Selection, SelectionFieldArgument, ServerFieldSelection,
+    Unwrap, VariableDefinition,
 };
 use isograph_schema::{
     create_merged_selection_set, into_name_and_arguments, refetched_paths_for_client_field,
     ArtifactQueueItem, ClientFieldActionKind, ClientFieldVariant, FieldDefinitionLocation,
-    FieldMapItem, MergedLinkedFieldSelection, MergedScalarFieldSelection, MergedSelectionSet,
-    MergedServerFieldSelection, MutationFieldResolverInfo, NameAndArguments,
-    ObjectTypeAndFieldNames, PathToRefetchField, RefetchFieldResolverInfo, RequiresRefinement,
-    RootRefetchedPath, ValidatedClientField, ValidatedSchema, ValidatedSchemaObject,
-    ValidatedSelection, ValidatedVariableDefinition, ENTRYPOINT, READER,
+    FieldMapItem, MergedInlineFragmentSelection, MergedLinkedFieldSelection,
+    MergedScalarFieldSelection, MergedSelectionSet, MergedServerFieldSelection,
+    MutationFieldResolverInfo, NameAndArguments, ObjectTypeAndFieldNames, PathToRefetchField,
+    RefetchFieldResolverInfo, RequiresRefinement, RootRefetchedPath, SchemaEnum,
+    ValidatedClientField, ValidatedSchema, ValidatedSchemaObject, ValidatedSelection,
+    ValidatedServerFieldSelection, ValidatedTypeRefinementSelection, ValidatedVariableDefinition,
+    ENTRYPOINT, READER,
 };
 use thiserror::Error;
 
-use crate::write_artifacts::write_to_disk;
+use crate::{
+    check_artifacts::{diff_artifacts_against_disk, ArtifactDiff},
+    write_artifacts::{write_to_disk, ArtifactHashes, ArtifactWriteCounts},
+};
 
 type NestedClientFieldImports = HashMap<ObjectTypeAndFieldNames, JavaScriptImports>;
 
@@ -57,12 +65,27 @@ pub(crate) fn generate_and_write_artifacts(
     schema: &ValidatedSchema,
     project_root: &PathBuf,
     artifact_directory: &PathBuf,
-) -> Result<usize, GenerateArtifactsError> {
+    previous_artifact_hashes: &mut Option<ArtifactHashes>,
+) -> Result<ArtifactWriteCounts, GenerateArtifactsError> {
     let paths_and_contents =
         get_artifact_path_and_contents(schema, project_root, artifact_directory);
-    let artifact_count = write_to_disk(paths_and_contents, artifact_directory)?;
+    let artifact_counts = write_to_disk(
+        paths_and_contents,
+        artifact_directory,
+        previous_artifact_hashes,
+    )?;
+
+    Ok(artifact_counts)
+}
 
-    Ok(artifact_count)
+pub(crate) fn generate_and_check_artifacts(
+    schema: &ValidatedSchema,
+    project_root: &PathBuf,
+    artifact_directory: &PathBuf,
+) -> Result<ArtifactDiff, GenerateArtifactsError> {
+    let paths_and_contents =
+        get_artifact_path_and_contents(schema, project_root, artifact_directory);
+    diff_artifacts_against_disk(paths_and_contents, artifact_directory)
 }
 
 fn build_iso_overload_for_entrypoint<'schema>(
@@ -102,14 +125,14 @@ fn build_iso_overload_for_client_defined_field(
         client_field.type_and_field.field_name,
     );
     let formatted_field = format!(
-        "field {}.{}",
+        "{}.{}",
         client_field.type_and_field.type_name, client_field.type_and_field.field_name
     );
     if matches!(client_field.variant, ClientFieldVariant::Component) {
         s.push_str(&format!(
             "
 export function iso<T>(
-  param: T & MatchesWhitespaceAndString<'{}', T>
+  param: T & MatchesFieldDeclaration<'{}', T>
 ): IdentityWithParamComponent<{}__param>;\n",
             formatted_field,
             client_field.type_and_field.underscore_separated(),
@@ -118,7 +141,7 @@ export function iso<T>(
         s.push_str(&format!(
             "
 export function iso<T>(
-  param: T & MatchesWhitespaceAndString<'{}', T>
+  param: T & MatchesFieldDeclaration<'{}', T>
 ): IdentityWithParam<{}__param>;\n",
             formatted_field,
             client_field.type_and_field.underscore_separated(),
@@ -146,7 +169,22 @@ type Whitespace<In> = In extends `${WhitespaceCharacter}${infer In}`
 type MatchesWhitespaceAndString<
   TString extends string,
   T
-> = Whitespace<T> extends `${TString}${string}` ? T : never;\n",
+> = Whitespace<T> extends `${TString}${string}` ? T : never;
+
+type WithoutDescription<In> = In extends `\"\"\"${string}\"\"\"${infer In}`
+  ? Whitespace<In>
+  : In extends `\"${string}\"${infer In}`
+  ? Whitespace<In>
+  : In;
+
+type MatchesFieldDeclaration<
+  TTypeAndField extends string,
+  T
+> = Whitespace<T> extends `field${infer In}`
+  ? WithoutDescription<Whitespace<In>> extends `${TTypeAndField}${string}`
+    ? T
+    : never
+  : never;\n",
     );
 
     let client_defined_field_overloads = sorted_client_defined_fields(schema)
@@ -304,6 +342,7 @@ fn get_artifact_infos<'schema>(
             *client_field_id,
             &mut artifact_queue,
             &mut encountered_client_field_ids,
+            artifact_directory,
         )));
 
         // We also need to generate reader artifacts for the entrypoint client fields themselves
@@ -312,16 +351,11 @@ fn get_artifact_infos<'schema>(
 
     for client_defined_field in client_defined_fields(schema) {
         if encountered_client_field_ids.insert(client_defined_field.id) {
-            // What are we doing here?
-            // We are generating, and throwing away, an entrypoint artifact. This has the effect of
-            // encountering selected __refetch fields. Refetch fields reachable from orphaned
-            // client fields still need reader (well... type) artifacts generated.
-            //
-            // Anyway, this sucks and should be improved.
-            let _ = generate_entrypoint_artifact(
+            // Refetch fields and client fields reachable from orphaned client fields
+            // still need reader (well... type) artifacts generated.
+            encounter_client_fields_reachable_from(
                 schema,
-                client_defined_field.id,
-                &mut vec![],
+                client_defined_field,
                 &mut encountered_client_field_ids,
             );
         }
@@ -498,6 +532,7 @@ fn generate_refetchable_query_text<'schema>(
                     span: Span::todo_generated(),
                 }),
             ))),
+            default_value: None,
         },
         span: Span::todo_generated(),
     });
@@ -539,6 +574,7 @@ fn generate_mutation_query_text<'schema>(
                             .get(&type_name.into())
                             .expect("Expected type to be found, this indicates a bug in Isograph")
                     }),
+                    default_value: argument.item.default_value.clone(),
                 },
                 span: Span::todo_generated(),
             });
@@ -594,34 +630,56 @@ fn get_aliased_mutation_field_name(
     s
 }
 
+/// Add the client fields (including selected __refetch fields) that are reachable
+/// from a client field that is not an entrypoint to encountered_client_field_ids.
+/// The client field need not be defined on a fetchable root type, so unlike
+/// generate_entrypoint_artifact, this does not generate any query text.
+fn encounter_client_fields_reachable_from(
+    schema: &ValidatedSchema,
+    client_field: &ValidatedClientField,
+    encountered_client_field_ids: &mut HashSet<ClientFieldId>,
+) {
+    if let Some((ref selection_set, _)) = client_field.selection_set_and_unwraps {
+        let parent_object = schema.schema_data.object(client_field.parent_object_id);
+        create_merged_selection_set(
+            schema,
+            parent_object.into(),
+            selection_set,
+            Some(&mut vec![]),
+            Some(encountered_client_field_ids),
+            client_field,
+        );
+    }
+}
+
 fn generate_entrypoint_artifact<'schema>(
     schema: &'schema ValidatedSchema,
     client_field_id: ClientFieldId,
     artifact_queue: &mut Vec<ArtifactQueueItem>,
     encountered_cliend_field_ids: &mut HashSet<ClientFieldId>,
+    artifact_directory: &PathBuf,
 ) -> EntrypointArtifactInfo<'schema> {
     let top_level_client_field = schema.client_field(client_field_id);
     if let Some((ref selection_set, _)) = top_level_client_field.selection_set_and_unwraps {
         let query_name = top_level_client_field.name.into();
+        let root_object = schema
+            .schema_data
+            .object(top_level_client_field.parent_object_id);
+        let operation_kind = schema
+            .fetchable_operation_kind(top_level_client_field.parent_object_id)
+            .expect("Expected entrypoint to be defined on a fetchable root type");
 
         let (merged_selection_set, root_refetched_paths) = create_merged_selection_set(
             schema,
-            // TODO here we are assuming that the client field is only on the Query type.
-            // That restriction should be loosened.
-            schema
-                .schema_data
-                .object(schema.query_type_id.expect("expect query type to exist"))
-                .into(),
+            root_object.into(),
             selection_set,
             Some(artifact_queue),
             Some(encountered_cliend_field_ids),
             &top_level_client_field,
         );
 
-        let query_object = schema
-            .query_object()
-            .expect("Expected query object to exist");
         let query_text = generate_query_text(
+            operation_kind,
             query_name,
             schema,
             &merged_selection_set,
@@ -632,12 +690,31 @@ fn generate_entrypoint_artifact<'schema>(
 
         let normalization_ast = generate_normalization_ast(schema, &merged_selection_set, 0);
 
+        let mut custom_scalar_imports = BTreeSet::new();
+        let (variables_type, input_object_types) = generate_entrypoint_variables_type(
+            schema,
+            &top_level_client_field.variable_definitions,
+            &format!("{}__{}", root_object.name, query_name),
+            &mut custom_scalar_imports,
+        );
+        let variable_defaults =
+            generate_entrypoint_variable_defaults(&top_level_client_field.variable_definitions);
+        let custom_scalar_import_statement = generate_custom_scalar_import_statement(
+            schema,
+            &custom_scalar_imports,
+            artifact_directory,
+        );
+
         EntrypointArtifactInfo {
             query_text,
             query_name,
-            parent_type: query_object.into(),
+            parent_type: root_object.into(),
             normalization_ast,
             refetch_query_artifact_import: refetch_query_artifact_imports,
+            variables_type,
+            input_object_types,
+            variable_defaults,
+            custom_scalar_import_statement,
         }
     } else {
         // TODO convert to error
@@ -645,6 +722,155 @@ fn generate_entrypoint_artifact<'schema>(
     }
 }
 
+/// Variables that have a default value, or are nullable, may be omitted. The types of
+/// input objects that are (transitively) used by the variables are declared alongside
+/// the variables type, since input objects can refer to themselves.
+fn generate_entrypoint_variables_type(
+    schema: &ValidatedSchema,
+    variable_definitions: &[WithSpan<ValidatedVariableDefinition>],
+    type_name_prefix: &str,
+    custom_scalar_imports: &mut CustomScalarImports,
+) -> (EntrypointVariablesType, EntrypointInputObjectTypes) {
+    let mut input_object_ids = vec![];
+    let mut variables_type = "{\n".to_string();
+    for variable_definition in variable_definitions.iter() {
+        let variable_definition = &variable_definition.item;
+        let is_optional = variable_definition.default_value.is_some()
+            || !matches!(variable_definition.type_, TypeAnnotation::NonNull(_));
+        let type_annotation = variable_definition.type_.clone().map(|input_type_id| {
+            print_input_type(
+                schema,
+                input_type_id,
+                type_name_prefix,
+                custom_scalar_imports,
+                &mut input_object_ids,
+            )
+        });
+        variables_type.push_str(&format!(
+            "  {}{}: {},\n",
+            variable_definition.name.item,
+            if is_optional { "?" } else { "" },
+            print_type_annotation(&type_annotation)
+        ));
+    }
+    variables_type.push_str("}");
+
+    // input_object_ids grows as the fields of input objects are visited
+    let mut input_object_types = String::new();
+    let mut index = 0;
+    while let Some(input_object_id) = input_object_ids.get(index).copied() {
+        index += 1;
+        let input_object = schema.schema_data.input_object(input_object_id);
+        let mut input_object_type = "{\n".to_string();
+        for field in input_object.fields.iter() {
+            let field = &field.item;
+            let is_optional =
+                field.default_value.is_some() || !matches!(field.type_, TypeAnnotation::NonNull(_));
+            let type_annotation = field.type_.clone().map(|input_type_name| {
+                let input_type_name: UnvalidatedTypeName = input_type_name.into();
+                let input_type_id = *schema
+                    .schema_data
+                    .defined_types
+                    .get(&input_type_name)
+                    .expect(
+                        "Expected input object field type to exist. \
+                        This is indicative of a bug in Isograph.",
+                    );
+                print_input_type(
+                    schema,
+                    input_type_id,
+                    type_name_prefix,
+                    custom_scalar_imports,
+                    &mut input_object_ids,
+                )
+            });
+            input_object_type.push_str(&format!(
+                "  {}{}: {},\n",
+                field.name.item,
+                if is_optional { "?" } else { "" },
+                print_type_annotation(&type_annotation)
+            ));
+        }
+        input_object_type.push_str("}");
+        input_object_types.push_str(&format!(
+            "export type {}__{} = {};\n\n",
+            type_name_prefix, input_object.name.item, input_object_type
+        ));
+    }
+
+    (
+        EntrypointVariablesType(variables_type),
+        EntrypointInputObjectTypes(input_object_types),
+    )
+}
+
+/// Print the TypeScript type of a variable or of a field of an input object. Input
+/// objects are referred to by name, and their ids are collected so that their types
+/// can be declared.
+fn print_input_type(
+    schema: &ValidatedSchema,
+    input_type_id: SelectableFieldId,
+    type_name_prefix: &str,
+    custom_scalar_imports: &mut CustomScalarImports,
+    input_object_ids: &mut Vec<InputObjectId>,
+) -> String {
+    match input_type_id {
+        SelectableFieldId::Scalar(scalar_id) => {
+            let scalar = schema.schema_data.scalar(scalar_id);
+            if scalar.javascript_import_path.is_some() {
+                custom_scalar_imports.insert(scalar_id);
+            }
+            scalar.javascript_name.to_string()
+        }
+        SelectableFieldId::Enum(enum_id) => print_enum_union(schema.schema_data.enum_(enum_id)),
+        SelectableFieldId::InputObject(input_object_id) => {
+            if !input_object_ids.contains(&input_object_id) {
+                input_object_ids.push(input_object_id);
+            }
+            format!(
+                "{}__{}",
+                type_name_prefix,
+                schema.schema_data.input_object(input_object_id).name.item
+            )
+        }
+        SelectableFieldId::Object(_) => {
+            panic!(
+                "Variables cannot have object types. \
+                This is indicative of a bug in Isograph."
+            )
+        }
+    }
+}
+
+/// The runtime merges the default values of variables into the variables that are
+/// passed to an entrypoint, so that conditions and store keys that depend on an
+/// omitted variable see the same value as the server.
+fn generate_entrypoint_variable_defaults(
+    variable_definitions: &[WithSpan<ValidatedVariableDefinition>],
+) -> EntrypointVariableDefaults {
+    let variable_defaults = variable_definitions
+        .iter()
+        .filter_map(|variable_definition| {
+            let variable_definition = &variable_definition.item;
+            variable_definition
+                .default_value
+                .as_ref()
+                .map(|default_value| {
+                    format!(
+                        "{}: {}",
+                        variable_definition.name.item,
+                        serialize_constant_value_for_js(&default_value.item)
+                    )
+                })
+        })
+        .collect::<Vec<_>>();
+    if variable_defaults.is_empty() {
+        EntrypointVariableDefaults("{}".to_string())
+    } else {
+        EntrypointVariableDefaults(format!("{{ {} }}", variable_defaults.join(", ")))
+    }
+}
+
 fn generate_reader_artifact<'schema>(
     schema: &'schema ValidatedSchema,
     client_field: &ValidatedClientField,
@@ -654,15 +880,11 @@ fn generate_reader_artifact<'schema>(
     if let Some((selection_set, _)) = &client_field.selection_set_and_unwraps {
         let parent_type = schema.schema_data.object(client_field.parent_object_id);
         let mut nested_client_field_artifact_imports = HashMap::new();
+        let mut custom_scalar_imports = BTreeSet::new();
 
         let (_merged_selection_set, root_refetched_paths) = create_merged_selection_set(
             schema,
-            // TODO here we are assuming that the client field is only on the Query type.
-            // That restriction should be loosened.
-            schema
-                .schema_data
-                .object(schema.query_type_id.expect("expect query type to exist"))
-                .into(),
+            parent_type.into(),
             selection_set,
             None,
             None,
@@ -671,6 +893,7 @@ fn generate_reader_artifact<'schema>(
 
         let reader_ast = generate_reader_ast(
             schema,
+            client_field.parent_object_id,
             selection_set,
             0,
             &mut nested_client_field_artifact_imports,
@@ -683,8 +906,14 @@ fn generate_reader_artifact<'schema>(
             &client_field.variant,
             parent_type.into(),
             &mut nested_client_field_artifact_imports,
+            &mut custom_scalar_imports,
             0,
         );
+        let custom_scalar_import_statement = generate_custom_scalar_import_statement(
+            schema,
+            &custom_scalar_imports,
+            artifact_directory,
+        );
         let client_field_output_type = generate_output_type(client_field);
         let function_import_statement = generate_function_import_statement(
             &client_field.action_kind,
@@ -696,10 +925,13 @@ fn generate_reader_artifact<'schema>(
             client_field_name: client_field.name,
             reader_ast,
             nested_client_field_artifact_imports,
+            custom_scalar_import_statement,
             function_import_statement,
             client_field_output_type,
             client_field_parameter_type,
             client_field_variant: client_field.variant.clone(),
+            client_field_description: client_field.description,
+            client_field_deprecation_reason: client_field.deprecation_reason,
         }
     } else {
         panic!("Unsupported: client fields not on query with no selection set")
@@ -738,6 +970,22 @@ derive_display!(QueryText);
 pub(crate) struct ClientFieldFunctionImportStatement(pub String);
 derive_display!(ClientFieldFunctionImportStatement);
 
+#[derive(Debug)]
+pub(crate) struct CustomScalarImportStatement(pub String);
+derive_display!(CustomScalarImportStatement);
+
+#[derive(Debug)]
+pub(crate) struct EntrypointVariablesType(pub String);
+derive_display!(EntrypointVariablesType);
+
+#[derive(Debug)]
+pub(crate) struct EntrypointInputObjectTypes(pub String);
+derive_display!(EntrypointInputObjectTypes);
+
+#[derive(Debug)]
+pub(crate) struct EntrypointVariableDefaults(pub String);
+derive_display!(EntrypointVariableDefaults);
+
 #[derive(Debug)]
 pub(crate) struct ClientFieldOutputType(pub String);
 derive_display!(ClientFieldOutputType);
@@ -765,6 +1013,10 @@ pub(crate) struct EntrypointArtifactInfo<'schema> {
     pub query_text: QueryText,
     pub normalization_ast: NormalizationAst,
     pub refetch_query_artifact_import: RefetchQueryArtifactImport,
+    pub variables_type: EntrypointVariablesType,
+    pub input_object_types: EntrypointInputObjectTypes,
+    pub variable_defaults: EntrypointVariableDefaults,
+    pub custom_scalar_import_statement: CustomScalarImportStatement,
 }
 
 impl<'schema> EntrypointArtifactInfo<'schema> {
@@ -790,11 +1042,14 @@ pub(crate) struct ReaderArtifactInfo<'schema> {
     pub parent_type: &'schema ValidatedSchemaObject,
     pub(crate) client_field_name: SelectableFieldName,
     pub nested_client_field_artifact_imports: NestedClientFieldImports,
+    pub custom_scalar_import_statement: CustomScalarImportStatement,
     pub client_field_output_type: ClientFieldOutputType,
     pub reader_ast: ReaderAst,
     pub client_field_parameter_type: ClientFieldParameterType,
     pub function_import_statement: ClientFieldFunctionImportStatement,
     pub client_field_variant: ClientFieldVariant,
+    pub client_field_description: Option<DescriptionValue>,
+    pub client_field_deprecation_reason: Option<StringLiteralValue>,
 }
 
 impl<'schema> ReaderArtifactInfo<'schema> {
@@ -849,6 +1104,7 @@ impl RefetchArtifactInfo {
 }
 
 fn generate_query_text(
+    operation_kind: RootOperationKind,
     query_name: QueryOperationName,
     schema: &ValidatedSchema,
     merged_selection_set: &MergedSelectionSet,
@@ -858,7 +1114,10 @@ fn generate_query_text(
 
     let variable_text = write_variables_to_string(schema, query_variables.iter());
 
-    query_text.push_str(&format!("query {} {} {{\\\n", query_name, variable_text));
+    query_text.push_str(&format!(
+        "{} {} {} {{\\\n",
+        operation_kind, query_name, variable_text
+    ));
     write_selections_for_query_text(&mut query_text, schema, &merged_selection_set, 1);
     query_text.push_str("}");
     QueryText(query_text)
@@ -928,6 +1187,12 @@ fn write_variables_to_string<'a>(
         // We should find a way to make WithLocation not impl display, without making error's hard
         // to work with.
         variable_text.push_str(&format!("${}: {}", variable.item.name.item, x));
+        if let Some(default_value) = &variable.item.default_value {
+            variable_text.push_str(&format!(
+                " = {}",
+                escape_for_query_text(&default_value.item.to_string())
+            ));
+        }
     }
 
     if empty {
@@ -948,6 +1213,12 @@ pub enum GenerateArtifactsError {
 
     #[error("Unable to delete directory at path {path:?}.\nReason: {message:?}")]
     UnableToDeleteDirectory { path: PathBuf, message: io::Error },
+
+    #[error("Unable to read artifact file at path {path:?}.\nReason: {message:?}")]
+    UnableToReadArtifactFile { path: PathBuf, message: io::Error },
+
+    #[error("Unable to delete stale artifact file at path {path:?}.\nReason: {message:?}")]
+    UnableToDeleteArtifactFile { path: PathBuf, message: io::Error },
 }
 
 fn write_selections_for_query_text(
@@ -965,7 +1236,8 @@ fn write_selections_for_query_text(
                 }
                 let name = scalar_field.name.item;
                 let arguments = get_serialized_arguments_for_query_text(&scalar_field.arguments);
-                query_text.push_str(&format!("{}{},\\\n", name, arguments));
+                let directives = get_serialized_directives_for_query_text(&scalar_field.directives);
+                query_text.push_str(&format!("{}{}{},\\\n", name, arguments, directives));
             }
             MergedServerFieldSelection::LinkedField(linked_field) => {
                 query_text.push_str(&format!("{}", "  ".repeat(indentation_level as usize)));
@@ -975,7 +1247,8 @@ fn write_selections_for_query_text(
                 }
                 let name = linked_field.name.item;
                 let arguments = get_serialized_arguments_for_query_text(&linked_field.arguments);
-                query_text.push_str(&format!("{}{} {{\\\n", name, arguments));
+                let directives = get_serialized_directives_for_query_text(&linked_field.directives);
+                query_text.push_str(&format!("{}{}{} {{\\\n", name, arguments, directives));
                 write_selections_for_query_text(
                     query_text,
                     schema,
@@ -987,6 +1260,23 @@ fn write_selections_for_query_text(
                     "  ".repeat(indentation_level as usize)
                 ));
             }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                query_text.push_str(&format!(
+                    "{}... on {} {{\\\n",
+                    "  ".repeat(indentation_level as usize),
+                    inline_fragment.type_to_refine_to
+                ));
+                write_selections_for_query_text(
+                    query_text,
+                    schema,
+                    &inline_fragment.selection_set,
+                    indentation_level + 1,
+                );
+                query_text.push_str(&format!(
+                    "{}}},\\\n",
+                    "  ".repeat(indentation_level as usize)
+                ));
+            }
         }
     }
 }
@@ -997,15 +1287,23 @@ fn generate_client_field_parameter_type(
     variant: &ClientFieldVariant,
     parent_type: &ValidatedSchemaObject,
     nested_client_field_imports: &mut NestedClientFieldImports,
+    custom_scalar_imports: &mut CustomScalarImports,
     indentation_level: u8,
 ) -> ClientFieldParameterType {
-    // TODO use unwraps
     let mut client_field_parameter_type = "{\n".to_string();
+    let mut type_refinements = vec![];
     for selection in selection_set.iter() {
+        let field = match &selection.item {
+            Selection::ServerField(field) => field,
+            Selection::TypeRefinement(type_refinement) => {
+                type_refinements.push(type_refinement);
+                continue;
+            }
+        };
         write_query_types_from_selection(
             schema,
             &mut client_field_parameter_type,
-            selection,
+            field,
             // Variant "unwrapping" only matters for the top-level parameter type,
             // doing it for nested selections is leads to situations where linked fields
             // show up as linkedField: { data: /* actualLinkedFields */ }
@@ -1013,11 +1311,23 @@ fn generate_client_field_parameter_type(
             &ClientFieldVariant::Eager,
             parent_type,
             nested_client_field_imports,
+            custom_scalar_imports,
             indentation_level + 1,
         );
     }
     client_field_parameter_type.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
 
+    if !type_refinements.is_empty() {
+        client_field_parameter_type.push_str(&generate_type_refinements_parameter_type(
+            schema,
+            &type_refinements,
+            parent_type,
+            nested_client_field_imports,
+            custom_scalar_imports,
+            indentation_level,
+        ));
+    }
+
     if variant == &ClientFieldVariant::Component {
         client_field_parameter_type = format!(
             "{}{}",
@@ -1029,115 +1339,329 @@ fn generate_client_field_parameter_type(
     ClientFieldParameterType(client_field_parameter_type)
 }
 
+/// Type refinements are represented as a union discriminated by __typename, which
+/// is intersected with the fields selected on the parent type. Each member of the
+/// union contains the fields of the type refinements (including repeated and nested
+/// ones) that apply to its concrete types. Concrete types to which the same type
+/// refinements apply share a member of the union.
+fn generate_type_refinements_parameter_type(
+    schema: &ValidatedSchema,
+    type_refinements: &[&ValidatedTypeRefinementSelection],
+    parent_type: &ValidatedSchemaObject,
+    nested_client_field_imports: &mut NestedClientFieldImports,
+    custom_scalar_imports: &mut CustomScalarImports,
+    indentation_level: u8,
+) -> String {
+    let mut concrete_types_and_selections: Vec<(
+        Vec<ObjectId>,
+        Vec<&ValidatedServerFieldSelection>,
+    )> = vec![];
+    for concrete_type_id in schema.schema_data.concrete_type_ids(parent_type.id) {
+        let mut selections = vec![];
+        for type_refinement in type_refinements {
+            collect_type_refinement_selections(
+                schema,
+                type_refinement,
+                concrete_type_id,
+                &mut selections,
+            );
+        }
+
+        let existing_union_member =
+            concrete_types_and_selections
+                .iter_mut()
+                .find(|(_, existing_selections)| {
+                    existing_selections.len() == selections.len()
+                        && existing_selections.iter().zip(selections.iter()).all(
+                            |(existing_selection, selection)| {
+                                std::ptr::eq(*existing_selection, *selection)
+                            },
+                        )
+                });
+        match existing_union_member {
+            Some((concrete_type_ids, _)) => concrete_type_ids.push(concrete_type_id),
+            None => concrete_types_and_selections.push((vec![concrete_type_id], selections)),
+        }
+    }
+
+    let mut union_members = vec![];
+    for (concrete_type_ids, selections) in concrete_types_and_selections {
+        let typename = concrete_type_ids
+            .iter()
+            .map(|concrete_type_id| {
+                format!("'{}'", schema.schema_data.object(*concrete_type_id).name)
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+        if selections.is_empty() {
+            union_members.push(format!("{{ __typename: {} }}", typename));
+            continue;
+        }
+
+        let mut union_member = format!(
+            "{{\n{}__typename: {},\n",
+            "  ".repeat((indentation_level + 1) as usize),
+            typename
+        );
+        // The fields of an interface are also fields of the concrete types that
+        // implement it, so we can look up every selected field on a concrete type.
+        let concrete_type = schema.schema_data.object(concrete_type_ids[0]);
+        // A field that is selected in several type refinements is only written once.
+        let mut written_names_and_aliases = HashSet::new();
+        for selection in selections {
+            if !written_names_and_aliases.insert(selection.name_or_alias().item) {
+                continue;
+            }
+            write_query_types_from_selection(
+                schema,
+                &mut union_member,
+                selection,
+                &ClientFieldVariant::Eager,
+                concrete_type,
+                nested_client_field_imports,
+                custom_scalar_imports,
+                indentation_level + 1,
+            );
+        }
+        union_member.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
+        union_members.push(union_member);
+    }
+
+    if union_members.is_empty() {
+        // There are no concrete types, so no object of the parent type can exist.
+        return String::new();
+    }
+    format!(" & ({})", union_members.join(" | "))
+}
+
+/// Collect the fields that a type refinement selects, if it applies to objects of
+/// the concrete type, including the fields of nested type refinements that apply.
+fn collect_type_refinement_selections<'a>(
+    schema: &ValidatedSchema,
+    type_refinement: &'a ValidatedTypeRefinementSelection,
+    concrete_type_id: ObjectId,
+    selections: &mut Vec<&'a ValidatedServerFieldSelection>,
+) {
+    if !schema
+        .schema_data
+        .concrete_type_ids(type_refinement.associated_data.parent_object_id)
+        .contains(&concrete_type_id)
+    {
+        return;
+    }
+
+    for selection in type_refinement.selection_set.iter() {
+        match &selection.item {
+            Selection::ServerField(field) => selections.push(field),
+            Selection::TypeRefinement(nested_type_refinement) => {
+                collect_type_refinement_selections(
+                    schema,
+                    nested_type_refinement,
+                    concrete_type_id,
+                    selections,
+                );
+            }
+        }
+    }
+}
+
 fn write_query_types_from_selection(
     schema: &ValidatedSchema,
     query_type_declaration: &mut String,
-    selection: &WithSpan<ValidatedSelection>,
+    field: &ValidatedServerFieldSelection,
     variant: &ClientFieldVariant,
     parent_type: &ValidatedSchemaObject,
     nested_client_field_imports: &mut NestedClientFieldImports,
+    custom_scalar_imports: &mut CustomScalarImports,
     indentation_level: u8,
 ) {
     query_type_declaration.push_str(&format!("{}", "  ".repeat(indentation_level as usize)));
 
-    match &selection.item {
-        Selection::ServerField(field) => match field {
-            ServerFieldSelection::ScalarField(scalar_field) => {
-                match scalar_field.associated_data {
-                    FieldDefinitionLocation::Server(_server_field) => {
-                        let parent_field = parent_type
-                            .encountered_fields
-                            .get(&scalar_field.name.item.into())
-                            .expect("parent_field should exist 1")
-                            .as_server_field()
-                            .expect("parent_field should exist and be server field");
-                        let field = schema.field(*parent_field);
-                        let name_or_alias = scalar_field.name_or_alias().item;
-
-                        // TODO there should be a clever way to print without cloning
-                        let output_type = field.associated_data.clone().map(|output_type_id| {
-                            // TODO not just scalars, enums as well. Both should have a javascript name
-                            let scalar_id =
-                                if let SelectableFieldId::Scalar(scalar) = output_type_id {
-                                    scalar
-                                } else {
-                                    panic!("output_type_id should be a scalar");
-                                };
-                            schema.schema_data.scalar(scalar_id).javascript_name
-                        });
-                        query_type_declaration.push_str(&format!(
-                            "{}: {},\n",
-                            name_or_alias,
-                            print_type_annotation(&output_type)
-                        ));
-                    }
-                    FieldDefinitionLocation::Client(client_field_id) => {
-                        let client_field = schema.client_field(client_field_id);
-
-                        match nested_client_field_imports.entry(client_field.type_and_field) {
-                            Entry::Occupied(mut occupied) => {
-                                occupied.get_mut().types.push(ResolverImportType {
+    match field {
+        ServerFieldSelection::ScalarField(scalar_field) => {
+            match scalar_field.associated_data {
+                FieldDefinitionLocation::Server(_server_field) => {
+                    let parent_field = parent_type
+                        .encountered_fields
+                        .get(&scalar_field.name.item.into())
+                        .expect("parent_field should exist 1")
+                        .as_server_field()
+                        .expect("parent_field should exist and be server field");
+                    let field = schema.field(*parent_field);
+                    let name_or_alias = scalar_field.name_or_alias().item;
+
+                    // TODO there should be a clever way to print without cloning
+                    let output_type =
+                        field
+                            .associated_data
+                            .clone()
+                            .map(|output_type_id| match output_type_id {
+                                SelectableFieldId::Scalar(scalar_id) => {
+                                    let scalar = schema.schema_data.scalar(scalar_id);
+                                    if scalar.javascript_import_path.is_some() {
+                                        custom_scalar_imports.insert(scalar_id);
+                                    }
+                                    scalar.javascript_name.to_string()
+                                }
+                                SelectableFieldId::Enum(enum_id) => {
+                                    print_enum_union(schema.schema_data.enum_(enum_id))
+                                }
+                                SelectableFieldId::Object(_)
+                                | SelectableFieldId::InputObject(_) => {
+                                    panic!("output_type_id should be a scalar or an enum")
+                                }
+                            });
+                    query_type_declaration.push_str(&format_jsdoc(
+                        field.description,
+                        field.deprecation_reason(),
+                        indentation_level,
+                    ));
+                    let output_type =
+                        output_type.unwrap_levels(&unwrapped_levels(&scalar_field.unwraps));
+                    query_type_declaration.push_str(&format!(
+                        "{}{}: {},\n",
+                        name_or_alias,
+                        optional_key_marker(&scalar_field.directives),
+                        print_type_annotation(&output_type)
+                    ));
+                }
+                FieldDefinitionLocation::Client(client_field_id) => {
+                    let client_field = schema.client_field(client_field_id);
+
+                    match nested_client_field_imports.entry(client_field.type_and_field) {
+                        Entry::Occupied(mut occupied) => {
+                            occupied.get_mut().types.push(ResolverImportType {
+                                globally_unique_type_name: ResolverImportName(format!(
+                                    "{}__outputType",
+                                    client_field.type_and_field.underscore_separated()
+                                )),
+                            });
+                        }
+                        Entry::Vacant(vacant) => {
+                            vacant.insert(JavaScriptImports {
+                                default_import: false,
+                                types: vec![ResolverImportType {
                                     globally_unique_type_name: ResolverImportName(format!(
                                         "{}__outputType",
                                         client_field.type_and_field.underscore_separated()
                                     )),
-                                });
-                            }
-                            Entry::Vacant(vacant) => {
-                                vacant.insert(JavaScriptImports {
-                                    default_import: false,
-                                    types: vec![ResolverImportType {
-                                        globally_unique_type_name: ResolverImportName(format!(
-                                            "{}__outputType",
-                                            client_field.type_and_field.underscore_separated()
-                                        )),
-                                    }],
-                                });
-                            }
+                                }],
+                            });
                         }
-
-                        query_type_declaration.push_str(&format!(
-                            "{}: {}__outputType,\n",
-                            scalar_field.name_or_alias().item,
-                            client_field.type_and_field.underscore_separated()
-                        ));
                     }
-                }
-            }
-            ServerFieldSelection::LinkedField(linked_field) => {
-                let parent_field = parent_type
-                    .encountered_fields
-                    .get(&linked_field.name.item.into())
-                    .expect("parent_field should exist 2")
-                    .as_server_field()
-                    .expect("Parent field should exist and be server field");
-                let field = schema.field(*parent_field);
-                let name_or_alias = linked_field.name_or_alias().item;
-                let type_annotation = field.associated_data.clone().map(|output_type_id| {
-                    // TODO Or interface or union type
-                    let object_id = if let SelectableFieldId::Object(object) = output_type_id {
-                        object
-                    } else {
-                        panic!("output_type_id should be a object");
-                    };
-                    let object = schema.schema_data.object(object_id);
-                    let inner = generate_client_field_parameter_type(
-                        schema,
-                        &linked_field.selection_set,
-                        &variant,
-                        object.into(),
-                        nested_client_field_imports,
+
+                    query_type_declaration.push_str(&format_jsdoc(
+                        client_field.description,
+                        client_field.deprecation_reason,
                         indentation_level,
-                    );
-                    inner
-                });
-                query_type_declaration.push_str(&format!(
-                    "{}: {},\n",
-                    name_or_alias,
-                    print_type_annotation(&type_annotation),
-                ));
+                    ));
+                    query_type_declaration.push_str(&format!(
+                        "{}: {}__outputType,\n",
+                        scalar_field.name_or_alias().item,
+                        client_field.type_and_field.underscore_separated()
+                    ));
+                }
             }
-        },
+        }
+        ServerFieldSelection::LinkedField(linked_field) => {
+            let parent_field = parent_type
+                .encountered_fields
+                .get(&linked_field.name.item.into())
+                .expect("parent_field should exist 2")
+                .as_server_field()
+                .expect("Parent field should exist and be server field");
+            let field = schema.field(*parent_field);
+            let name_or_alias = linked_field.name_or_alias().item;
+            let type_annotation = field.associated_data.clone().map(|output_type_id| {
+                // TODO Or interface or union type
+                let object_id = if let SelectableFieldId::Object(object) = output_type_id {
+                    object
+                } else {
+                    panic!("output_type_id should be a object");
+                };
+                let object = schema.schema_data.object(object_id);
+                let inner = generate_client_field_parameter_type(
+                    schema,
+                    &linked_field.selection_set,
+                    &variant,
+                    object.into(),
+                    nested_client_field_imports,
+                    custom_scalar_imports,
+                    indentation_level,
+                );
+                inner
+            });
+            let type_annotation =
+                type_annotation.unwrap_levels(&unwrapped_levels(&linked_field.unwraps));
+            query_type_declaration.push_str(&format_jsdoc(
+                field.description,
+                field.deprecation_reason(),
+                indentation_level,
+            ));
+            query_type_declaration.push_str(&format!(
+                "{}{}: {},\n",
+                name_or_alias,
+                optional_key_marker(&linked_field.directives),
+                print_type_annotation(&type_annotation),
+            ));
+        }
+    }
+}
+
+/// Descriptions and deprecation reasons are emitted as JSDoc comments, so that they
+/// show up in editor hovers. This is written after the indentation of the documented
+/// line, and ends with that same indentation. If there is neither, this is empty.
+pub(crate) fn format_jsdoc(
+    description: Option<DescriptionValue>,
+    deprecation_reason: Option<StringLiteralValue>,
+    indentation_level: u8,
+) -> String {
+    if description.is_none() && deprecation_reason.is_none() {
+        return String::new();
+    }
+
+    let mut lines = vec![];
+    if let Some(description) = description {
+        lines.extend(description.lookup().lines().map(|line| line.to_string()));
+    }
+    if let Some(deprecation_reason) = deprecation_reason {
+        lines.push(format!("@deprecated {}", deprecation_reason));
+    }
+
+    let indent = "  ".repeat(indentation_level as usize);
+    let mut jsdoc = "/**\n".to_string();
+    for line in lines {
+        jsdoc.push_str(format!("{indent} * {}", line.replace("*/", "*\\/")).trim_end());
+        jsdoc.push('\n');
+    }
+    jsdoc.push_str(&format!("{indent} */\n{indent}"));
+    jsdoc
+}
+
+/// Fields selected with `!` carry the levels that were unwrapped, so that the runtime
+/// can throw if one of them is null. Other fields omit this key.
+fn get_serialized_unwraps(unwraps: &[WithSpan<Unwrap>], indentation_level: u8) -> String {
+    let unwrapped_levels = unwrapped_levels(unwraps);
+    if !unwrapped_levels.contains(&true) {
+        return String::new();
+    }
+    format!(
+        "{}unwraps: [{}],\n",
+        "  ".repeat(indentation_level as usize),
+        unwrapped_levels
+            .iter()
+            .map(|is_unwrapped| is_unwrapped.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Fields selected with @skip or @include may not be present, so their keys are optional.
+fn optional_key_marker(directives: &[WithSpan<FragmentDirectiveUsage>]) -> &'static str {
+    if directives.is_empty() {
+        ""
+    } else {
+        "?"
     }
 }
 
@@ -1180,6 +1704,49 @@ fn print_non_null_type_annotation<T: Display>(non_null: &NonNullTypeAnnotation<T
     }
 }
 
+/// An enum is typed as the union of its values, e.g. `'ACTIVE' | 'INACTIVE'`.
+fn print_enum_union(schema_enum: &SchemaEnum) -> String {
+    schema_enum
+        .values
+        .iter()
+        .map(|enum_value_definition| format!("'{}'", enum_value_definition.item.value.item))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// The scalars whose TypeScript types must be imported by a reader artifact.
+type CustomScalarImports = BTreeSet<ScalarId>;
+
+fn generate_custom_scalar_import_statement(
+    schema: &ValidatedSchema,
+    custom_scalar_imports: &CustomScalarImports,
+    artifact_directory: &PathBuf,
+) -> CustomScalarImportStatement {
+    // Scalars that share a TypeScript type must only import it once.
+    let import_statements: BTreeSet<_> = custom_scalar_imports
+        .iter()
+        .filter_map(|scalar_id| {
+            let scalar = schema.schema_data.scalar(*scalar_id);
+            let import_path = match scalar.javascript_import_path.as_ref()? {
+                CustomScalarTypeImportPath::Module(module) => module.clone(),
+                // See generate_function_import_statement for why we diff against Type/Field
+                CustomScalarTypeImportPath::File(path) => {
+                    pathdiff::diff_paths(path, artifact_directory.join("Type/Field"))
+                        .expect("Relative path should work")
+                        .to_str()
+                        .expect("This path should be stringifiable. This probably is indicative of a bug in Isograph.")
+                        .to_string()
+                }
+            };
+            Some(format!(
+                "import type {{ {} }} from '{}';\n",
+                scalar.javascript_name, import_path
+            ))
+        })
+        .collect();
+    CustomScalarImportStatement(import_statements.into_iter().collect())
+}
+
 fn generate_function_import_statement(
     action_kind: &ClientFieldActionKind,
     project_root: &PathBuf,
@@ -1297,6 +1864,7 @@ pub struct JavaScriptImports {
 
 fn generate_reader_ast<'schema>(
     schema: &'schema ValidatedSchema,
+    parent_object_id: ObjectId,
     selection_set: &'schema Vec<WithSpan<ValidatedSelection>>,
     indentation_level: u8,
     nested_client_field_imports: &mut NestedClientFieldImports,
@@ -1305,6 +1873,7 @@ fn generate_reader_ast<'schema>(
 ) -> ReaderAst {
     generate_reader_ast_with_path(
         schema,
+        parent_object_id,
         selection_set,
         indentation_level,
         nested_client_field_imports,
@@ -1317,6 +1886,7 @@ fn generate_reader_ast<'schema>(
 
 fn generate_reader_ast_with_path<'schema>(
     schema: &'schema ValidatedSchema,
+    parent_object_id: ObjectId,
     selection_set: &'schema Vec<WithSpan<ValidatedSelection>>,
     indentation_level: u8,
     nested_client_field_imports: &mut NestedClientFieldImports,
@@ -1329,6 +1899,7 @@ fn generate_reader_ast_with_path<'schema>(
         let s = generate_reader_ast_node(
             item,
             schema,
+            parent_object_id,
             indentation_level + 1,
             nested_client_field_imports,
             &root_refetched_paths,
@@ -1343,11 +1914,54 @@ fn generate_reader_ast_with_path<'schema>(
 fn generate_reader_ast_node(
     selection: &WithSpan<ValidatedSelection>,
     schema: &ValidatedSchema,
+    parent_object_id: ObjectId,
     indentation_level: u8,
     nested_client_field_imports: &mut NestedClientFieldImports,
     // TODO use this to generate usedRefetchQueries
     root_refetched_paths: &[RootRefetchedPath],
     path: &mut Vec<NameAndArguments>,
+) -> String {
+    let directives: &[WithSpan<FragmentDirectiveUsage>] = match &selection.item {
+        Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+            &scalar_field.directives
+        }
+        Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+            &linked_field.directives
+        }
+        Selection::TypeRefinement(_) => &[],
+    };
+    if directives.is_empty() {
+        return generate_unconditional_reader_ast_node(
+            selection,
+            schema,
+            parent_object_id,
+            indentation_level,
+            nested_client_field_imports,
+            root_refetched_paths,
+            path,
+        );
+    }
+
+    let inner_reader_ast_node = generate_unconditional_reader_ast_node(
+        selection,
+        schema,
+        parent_object_id,
+        indentation_level + 2,
+        nested_client_field_imports,
+        root_refetched_paths,
+        path,
+    );
+    generate_conditional_ast_node(&inner_reader_ast_node, directives, indentation_level)
+}
+
+fn generate_unconditional_reader_ast_node(
+    selection: &WithSpan<ValidatedSelection>,
+    schema: &ValidatedSchema,
+    parent_object_id: ObjectId,
+    indentation_level: u8,
+    nested_client_field_imports: &mut NestedClientFieldImports,
+    root_refetched_paths: &[RootRefetchedPath],
+    path: &mut Vec<NameAndArguments>,
 ) -> String {
     match &selection.item {
         Selection::ServerField(field) => match field {
@@ -1367,6 +1981,8 @@ fn generate_reader_ast_node(
 
                         let indent_1 = "  ".repeat(indentation_level as usize);
                         let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+                        let unwraps =
+                            get_serialized_unwraps(&scalar_field.unwraps, indentation_level + 1);
 
                         format!(
                             "{indent_1}{{\n\
@@ -1374,6 +1990,7 @@ fn generate_reader_ast_node(
                             {indent_2}fieldName: \"{field_name}\",\n\
                             {indent_2}alias: {alias},\n\
                             {indent_2}arguments: {arguments},\n\
+                            {unwraps}\
                             {indent_1}}},\n",
                         )
                     }
@@ -1466,6 +2083,7 @@ fn generate_reader_ast_node(
 
                 let inner_reader_ast = generate_reader_ast_with_path(
                     schema,
+                    linked_field.associated_data.parent_object_id,
                     &linked_field.selection_set,
                     indentation_level + 1,
                     nested_client_field_imports,
@@ -1479,17 +2097,52 @@ fn generate_reader_ast_node(
                     get_serialized_field_arguments(&linked_field.arguments, indentation_level + 1);
                 let indent_1 = "  ".repeat(indentation_level as usize);
                 let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+                let unwraps = get_serialized_unwraps(&linked_field.unwraps, indentation_level + 1);
                 format!(
                     "{indent_1}{{\n\
                     {indent_2}kind: \"Linked\",\n\
                     {indent_2}fieldName: \"{name}\",\n\
                     {indent_2}alias: {alias},\n\
                     {indent_2}arguments: {arguments},\n\
+                    {unwraps}\
                     {indent_2}selections: {inner_reader_ast},\n\
                     {indent_1}}},\n",
                 )
             }
         },
+        Selection::TypeRefinement(type_refinement) => {
+            let type_to_refine_to = type_refinement.type_to_refine_to.item;
+            let refined_object_id = type_refinement.associated_data.parent_object_id;
+            let concrete_types = serialize_concrete_types(
+                schema
+                    .schema_data
+                    .concrete_type_ids_of_type_refinement(parent_object_id, refined_object_id)
+                    .into_iter()
+                    .map(|object_id| schema.schema_data.object(object_id).name),
+            );
+
+            // Type refinements read from the same record, so the path is unchanged.
+            let inner_reader_ast = generate_reader_ast_with_path(
+                schema,
+                refined_object_id,
+                &type_refinement.selection_set,
+                indentation_level + 1,
+                nested_client_field_imports,
+                root_refetched_paths,
+                path,
+            );
+
+            let indent_1 = "  ".repeat(indentation_level as usize);
+            let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+            format!(
+                "{indent_1}{{\n\
+                {indent_2}kind: \"InlineFragment\",\n\
+                {indent_2}type: \"{type_to_refine_to}\",\n\
+                {indent_2}concreteTypes: {concrete_types},\n\
+                {indent_2}selections: {inner_reader_ast},\n\
+                {indent_1}}},\n",
+            )
+        }
     }
 }
 
@@ -1511,6 +2164,25 @@ fn generate_normalization_ast_node(
     item: &WithSpan<MergedServerFieldSelection>,
     schema: &ValidatedSchema,
     indentation_level: u8,
+) -> String {
+    let directives: &[WithSpan<FragmentDirectiveUsage>] = match &item.item {
+        MergedServerFieldSelection::ScalarField(scalar_field) => &scalar_field.directives,
+        MergedServerFieldSelection::LinkedField(linked_field) => &linked_field.directives,
+        MergedServerFieldSelection::InlineFragment(_) => &[],
+    };
+    if directives.is_empty() {
+        return generate_unconditional_normalization_ast_node(item, schema, indentation_level);
+    }
+
+    let inner_normalization_ast_node =
+        generate_unconditional_normalization_ast_node(item, schema, indentation_level + 2);
+    generate_conditional_ast_node(&inner_normalization_ast_node, directives, indentation_level)
+}
+
+fn generate_unconditional_normalization_ast_node(
+    item: &WithSpan<MergedServerFieldSelection>,
+    schema: &ValidatedSchema,
+    indentation_level: u8,
 ) -> String {
     match &item.item {
         MergedServerFieldSelection::ScalarField(scalar_field) => {
@@ -1559,9 +2231,116 @@ fn generate_normalization_ast_node(
                 {indent}}},\n"
             )
         }
+        MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+            let MergedInlineFragmentSelection {
+                type_to_refine_to,
+                concrete_types,
+                selection_set,
+            } = inline_fragment;
+            let concrete_types = serialize_concrete_types(concrete_types.iter().copied());
+            let indent = "  ".repeat(indentation_level as usize);
+            let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+
+            let selections =
+                generate_normalization_ast(schema, selection_set, indentation_level + 1);
+
+            format!(
+                "{indent}{{\n\
+                {indent_2}kind: \"InlineFragment\",\n\
+                {indent_2}type: \"{type_to_refine_to}\",\n\
+                {indent_2}concreteTypes: {concrete_types},\n\
+                {indent_2}selections: {selections},\n\
+                {indent}}},\n"
+            )
+        }
     }
 }
 
+/// The concrete types to which a type refinement applies are serialized as an array
+/// of __typename's, against which the runtime checks each object.
+fn serialize_concrete_types(
+    concrete_types: impl Iterator<Item = IsographObjectTypeName>,
+) -> String {
+    let concrete_types: Vec<_> = concrete_types
+        .map(|concrete_type| format!("\"{}\"", concrete_type))
+        .collect();
+    format!("[{}]", concrete_types.join(", "))
+}
+
+/// Selections with @skip or @include are wrapped in a Conditional node, so that
+/// the runtime does not treat them as missing data when they are excluded.
+fn generate_conditional_ast_node(
+    inner_ast_node: &str,
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+    indentation_level: u8,
+) -> String {
+    let indent_1 = "  ".repeat(indentation_level as usize);
+    let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+    let indent_3 = "  ".repeat((indentation_level + 2) as usize);
+
+    let mut conditions = String::new();
+    for directive in directives {
+        let kind = match directive.item.name.item.lookup() {
+            "skip" => "Skip",
+            "include" => "Include",
+            _ => panic!(
+                "Unexpected directive on selection. This is indicative of a bug in Isograph."
+            ),
+        };
+        let value = match &directive
+            .item
+            .arguments
+            .first()
+            .expect("Expected directive to have an if argument. This is indicative of a bug in Isograph.")
+            .item
+            .value
+            .item
+        {
+            NonConstantValue::Variable(variable_name) => {
+                format!("{{ kind: \"Variable\", name: \"{variable_name}\" }}")
+            }
+            value => format!(
+                "{{ kind: \"Literal\", value: {} }}",
+                serialize_constant_value_for_js(
+                    &value
+                        .to_constant_value()
+                        .expect("Expected value to be constant")
+                )
+            ),
+        };
+        conditions.push_str(&format!(
+            "{indent_3}{{ kind: \"{kind}\", value: {value} }},\n"
+        ));
+    }
+
+    format!(
+        "{indent_1}{{\n\
+        {indent_2}kind: \"Conditional\",\n\
+        {indent_2}conditions: [\n\
+        {conditions}\
+        {indent_2}],\n\
+        {indent_2}selections: [\n\
+        {inner_ast_node}\
+        {indent_2}],\n\
+        {indent_1}}},\n"
+    )
+}
+
+fn get_serialized_directives_for_query_text(
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+) -> String {
+    directives
+        .iter()
+        .map(|directive| {
+            format!(
+                " @{}{}",
+                directive.item.name.item,
+                get_serialized_arguments_for_query_text(&directive.item.arguments)
+            )
+        })
+        .collect()
+}
+
 fn get_serialized_arguments_for_query_text(
     arguments: &[WithLocation<SelectionFieldArgument>],
 ) -> String {
@@ -1573,13 +2352,17 @@ fn get_serialized_arguments_for_query_text(
         let mut s = format!(
             "({}: {}",
             first.item.name.item,
-            serialize_non_constant_value_for_graphql(&first.item.value.item)
+            escape_for_query_text(&serialize_non_constant_value_for_graphql(
+                &first.item.value.item,
+            ))
         );
         for argument in arguments {
             s.push_str(&format!(
                 ", {}: {}",
                 argument.item.name.item,
-                serialize_non_constant_value_for_graphql(&argument.item.value.item)
+                escape_for_query_text(&serialize_non_constant_value_for_graphql(
+                    &argument.item.value.item,
+                ))
             ));
         }
         s.push_str(")");
@@ -1611,12 +2394,17 @@ fn get_serialized_field_arguments(
                     {indent_1}],\n",
                 )
             }
-            NonConstantValue::Integer(int_value) => {
+            ref value => {
+                let literal_value = serialize_constant_value_for_js(
+                    &value
+                        .to_constant_value()
+                        .expect("Expected value to be constant"),
+                );
                 format!(
                     "\n\
                     {indent_1}[\n\
                     {indent_2}\"{argument_name}\",\n\
-                    {indent_2}{{ kind: \"Literal\", value: {int_value} }},\n\
+                    {indent_2}{{ kind: \"Literal\", value: {literal_value} }},\n\
                     {indent_1}],\n"
                 )
             }
@@ -1632,7 +2420,47 @@ fn get_serialized_field_arguments(
 fn serialize_non_constant_value_for_graphql(value: &NonConstantValue) -> String {
     match value {
         NonConstantValue::Variable(variable_name) => format!("${}", variable_name),
-        NonConstantValue::Integer(int_value) => int_value.to_string(),
+        _ => value
+            .to_constant_value()
+            .expect("Expected value to be constant")
+            .to_string(),
+    }
+}
+
+/// The query text is written into a single-quoted JavaScript string, so string
+/// literals within it must be escaped.
+fn escape_for_query_text(graphql_text: &str) -> String {
+    graphql_text.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Serialize a literal value as a JavaScript value. Enums are represented as strings.
+fn serialize_constant_value_for_js(value: &ConstantValue) -> String {
+    match value {
+        ConstantValue::Int(int_value) => int_value.to_string(),
+        ConstantValue::Float(float_value) => float_value.to_string(),
+        ConstantValue::String(string_value) => print_string_literal(string_value.lookup()),
+        ConstantValue::Boolean(bool_value) => bool_value.to_string(),
+        ConstantValue::Null => "null".to_string(),
+        ConstantValue::Enum(enum_value) => format!("\"{}\"", enum_value),
+        ConstantValue::List(list) => format!(
+            "[{}]",
+            list.iter()
+                .map(|item| serialize_constant_value_for_js(&item.item))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ConstantValue::Object(object) => format!(
+            "{{ {} }}",
+            object
+                .iter()
+                .map(|pair| format!(
+                    "\"{}\": {}",
+                    pair.name.item,
+                    serialize_constant_value_for_js(&pair.value.item)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
     }
 }
 
@@ -1725,3 +2553,169 @@ fn find_mutation_query_index(
 fn generate_path(object_name: IsographObjectTypeName, field_name: SelectableFieldName) -> PathBuf {
     PathBuf::from(object_name.lookup()).join(field_name.lookup())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use common_lang_types::TextSource;
+    use intern::string_key::Intern;
+    use isograph_config::ConfigOptions;
+    use isograph_lang_parser::{parse_iso_literal, IsoLiteralExtractionResult};
+    use isograph_schema::{UnvalidatedSchema, ValidatedSchema};
+
+    use super::{get_artifact_infos, ArtifactInfo};
+
+    const SCHEMA: &str = "
+        type Query {
+          me: User!
+        }
+
+        type Mutation {
+          setName(name: String!): User!
+        }
+
+        type Subscription {
+          userUpdated: User!
+        }
+
+        type User {
+          id: ID!
+          name: String
+        }
+    ";
+
+    fn text_source() -> TextSource {
+        TextSource {
+            path: "dummy".intern().into(),
+            span: None,
+        }
+    }
+
+    /// Validate SCHEMA after processing the given iso literals, each of which is
+    /// paired with the name of the const it is exported as.
+    fn validated_schema(iso_literals: &[(&str, Option<&str>)]) -> ValidatedSchema {
+        let options = ConfigOptions::default();
+        let mut warnings = vec![];
+
+        let type_system_document = graphql_schema_parser::parse_schema(SCHEMA, text_source())
+            .expect("Expected schema to parse");
+        let mut schema = UnvalidatedSchema::new();
+        schema
+            .process_graphql_type_system_document(type_system_document, &options, &mut warnings)
+            .expect("Expected schema to be processed");
+
+        for (iso_literal_text, const_export_name) in iso_literals {
+            match parse_iso_literal(
+                iso_literal_text,
+                "dummy.ts".intern().into(),
+                *const_export_name,
+                text_source(),
+            )
+            .expect("Expected iso literal to parse")
+            {
+                IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration) => {
+                    schema
+                        .process_client_field_declaration(client_field_declaration, text_source())
+                        .expect("Expected client field declaration to be processed")
+                }
+                IsoLiteralExtractionResult::EntrypointDeclaration(entrypoint_declaration) => schema
+                    .entrypoints
+                    .push((text_source(), entrypoint_declaration)),
+            }
+        }
+
+        ValidatedSchema::validate_and_construct(schema, &options, &mut warnings)
+            .expect("Expected schema to be valid")
+    }
+
+    fn artifact_infos(schema: &ValidatedSchema) -> Vec<ArtifactInfo<'_>> {
+        get_artifact_infos(
+            schema,
+            &PathBuf::from("/project"),
+            &PathBuf::from("/project/__isograph"),
+        )
+    }
+
+    /// The query text of each entrypoint artifact.
+    fn query_texts(artifact_infos: &[ArtifactInfo]) -> Vec<String> {
+        artifact_infos
+            .iter()
+            .filter_map(|artifact_info| match artifact_info {
+                ArtifactInfo::Entrypoint(entrypoint) => Some(entrypoint.query_text.0.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The type and field names of each reader artifact, sorted.
+    fn reader_names(artifact_infos: &[ArtifactInfo]) -> Vec<String> {
+        let mut reader_names: Vec<_> = artifact_infos
+            .iter()
+            .filter_map(|artifact_info| match artifact_info {
+                ArtifactInfo::Reader(reader) => Some(format!(
+                    "{}.{}",
+                    reader.parent_type.name, reader.client_field_name
+                )),
+                _ => None,
+            })
+            .collect();
+        reader_names.sort();
+        reader_names
+    }
+
+    #[test]
+    fn unreachable_client_field_on_non_root_type() {
+        let schema = validated_schema(&[(
+            "field User.Avatar {\n  name\n  __refetch\n}",
+            Some("Avatar"),
+        )]);
+        let artifact_infos = artifact_infos(&schema);
+
+        assert!(query_texts(&artifact_infos).is_empty());
+        assert_eq!(
+            reader_names(&artifact_infos),
+            ["User.Avatar", "User.__refetch"]
+        );
+    }
+
+    #[test]
+    fn subscription_entrypoint() {
+        let schema = validated_schema(&[
+            (
+                "field Subscription.OnUserUpdated {\n  userUpdated {\n    name\n  }\n}",
+                Some("OnUserUpdated"),
+            ),
+            ("entrypoint Subscription.OnUserUpdated", None),
+        ]);
+        let artifact_infos = artifact_infos(&schema);
+
+        let query_texts = query_texts(&artifact_infos);
+        assert_eq!(query_texts.len(), 1);
+        assert!(query_texts[0].starts_with("subscription OnUserUpdated "));
+        assert!(query_texts[0].contains("userUpdated {"));
+        assert_eq!(
+            reader_names(&artifact_infos),
+            ["Subscription.OnUserUpdated"]
+        );
+    }
+
+    #[test]
+    fn mutation_entrypoint() {
+        let schema = validated_schema(&[
+            (
+                "field Mutation.SetName($name: String!) {\n  \
+                 setName(name: $name) {\n    name\n  }\n}",
+                Some("SetName"),
+            ),
+            ("entrypoint Mutation.SetName", None),
+        ]);
+        let artifact_infos = artifact_infos(&schema);
+
+        let query_texts = query_texts(&artifact_infos);
+        assert_eq!(query_texts.len(), 1);
+        assert!(query_texts[0].starts_with("mutation SetName ($name: String!)"));
+        assert!(query_texts[0].contains("setName(name: $name) {"));
+        assert_eq!(reader_names(&artifact_infos), ["Mutation.SetName"]);
+    }
+}
@@ -0,0 +1,705 @@
+use std::collections::HashMap;
+
+use common_lang_types::Span;
+
+pub(crate) struct IsoLiteralExtraction<'a> {
+    pub(crate) const_export_name: Option<&'a str>,
+    pub(crate) iso_literal_text: &'a str,
+    pub(crate) iso_literal_start_index: usize,
+    /// The span from `iso` through the closing backtick, or through the closing
+    /// parenthesis if there is one, relative to the start of the file.
+    pub(crate) iso_call_span: Span,
+    pub(crate) has_associated_js_function: bool,
+    pub(crate) has_paren: bool,
+}
+
+/// Find the iso literals in a JavaScript or TypeScript file, i.e. template
+/// literals tagged or called with `iso`, such as
+///
+/// ```js
+/// export const Foo = iso(`field Query.Foo { bar }`)(function Foo() {});
+/// ```
+///
+/// The file is tokenized, so that `iso` within comments, strings and other
+/// template literals is ignored.
+pub(crate) fn extract_iso_literal_from_file_content(
+    content: &str,
+) -> impl Iterator<Item = IsoLiteralExtraction<'_>> + '_ {
+    let tokens = tokenize(content);
+    let export_names = iso_export_names(content, &tokens);
+
+    let mut extractions = vec![];
+    for (iso_index, token) in tokens.iter().enumerate() {
+        if !token.is_identifier(content, "iso")
+            || (iso_index > 0 && tokens[iso_index - 1].is_punctuator(content, "."))
+        {
+            continue;
+        }
+
+        let mut next_index = iso_index + 1;
+        let has_paren = tokens
+            .get(next_index)
+            .map(|token| token.is_punctuator(content, "("))
+            .unwrap_or(false);
+        if has_paren {
+            next_index += 1;
+        }
+
+        let template = match tokens.get(next_index) {
+            // An unterminated template literal does not end with a backtick. An empty
+            // template literal is extracted, so that we can report it.
+            Some(template)
+                if template.kind == JsTokenKind::Template
+                    && template.end - template.start >= 2
+                    && content.as_bytes()[template.end - 1] == b'`' =>
+            {
+                template
+            }
+            _ => continue,
+        };
+        next_index += 1;
+        let mut iso_call_end = template.end;
+
+        if has_paren {
+            if let Some(close_paren) = tokens.get(next_index) {
+                if close_paren.is_punctuator(content, ")") {
+                    iso_call_end = close_paren.end;
+                    next_index += 1;
+                }
+            }
+        }
+
+        let has_associated_js_function = tokens
+            .get(next_index)
+            .map(|token| token.is_punctuator(content, "("))
+            .unwrap_or(false);
+
+        extractions.push(IsoLiteralExtraction {
+            const_export_name: export_names.get(&iso_index).copied(),
+            // Exclude the backticks
+            iso_literal_text: &content[template.start + 1..template.end - 1],
+            iso_literal_start_index: template.start + 1,
+            iso_call_span: Span::new(token.start as u32, iso_call_end as u32),
+            has_associated_js_function,
+            has_paren,
+        });
+    }
+
+    extractions.into_iter()
+}
+
+/// Map the index of each `iso` token that is exported to its export name. We
+/// recognize `export const Foo = iso...`, `export const Foo: SomeType = iso...`
+/// and `export default iso...`, whose export name is `default`.
+fn iso_export_names<'a>(content: &'a str, tokens: &[JsToken]) -> HashMap<usize, &'a str> {
+    let mut export_names = HashMap::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        if !token.is_identifier(content, "export") {
+            continue;
+        }
+        let next = |offset: usize| tokens.get(index + offset);
+
+        if let Some(default_token) = next(1) {
+            if default_token.is_identifier(content, "default") {
+                if next(2).map(|token| token.is_identifier(content, "iso")) == Some(true) {
+                    export_names.insert(index + 2, "default");
+                }
+                continue;
+            }
+        }
+
+        let is_const = next(1)
+            .map(|token| token.is_identifier(content, "const"))
+            .unwrap_or(false);
+        let name = match next(2) {
+            Some(name) if is_const && name.kind == JsTokenKind::Identifier => name.text(content),
+            _ => continue,
+        };
+
+        let equals_index = match next(3) {
+            Some(token) if token.is_punctuator(content, "=") => index + 3,
+            Some(token) if token.is_punctuator(content, ":") => {
+                match type_annotation_end(content, tokens, index + 4) {
+                    Some(equals_index) => equals_index,
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+
+        if let Some(token) = tokens.get(equals_index + 1) {
+            if token.is_identifier(content, "iso") {
+                export_names.insert(equals_index + 1, name);
+            }
+        }
+    }
+
+    export_names
+}
+
+/// Find the index of the `=` that follows a type annotation, skipping over any
+/// brackets (including the angle brackets of generics) within the type.
+fn type_annotation_end(content: &str, tokens: &[JsToken], start_index: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate().skip(start_index) {
+        if token.kind != JsTokenKind::Punctuator {
+            continue;
+        }
+        match token.text(content) {
+            "(" | "[" | "{" | "<" => depth += 1,
+            ")" | "]" | "}" | ">" => depth -= 1,
+            "=" if depth == 0 => return Some(index),
+            ";" if depth == 0 => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsTokenKind {
+    /// An identifier or keyword
+    Identifier,
+    Punctuator,
+    /// A complete template literal without substitutions, including its backticks
+    Template,
+    /// A part of a template literal with substitutions, which cannot be an iso literal
+    TemplateWithSubstitutions,
+    /// Strings, numbers and regular expressions
+    Other,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct JsToken {
+    kind: JsTokenKind,
+    start: usize,
+    end: usize,
+}
+
+impl JsToken {
+    fn text<'a>(&self, content: &'a str) -> &'a str {
+        &content[self.start..self.end]
+    }
+
+    fn is_identifier(&self, content: &str, identifier: &str) -> bool {
+        self.kind == JsTokenKind::Identifier && self.text(content) == identifier
+    }
+
+    fn is_punctuator(&self, content: &str, punctuator: &str) -> bool {
+        self.kind == JsTokenKind::Punctuator && self.text(content) == punctuator
+    }
+}
+
+/// Keywords after which a `/` starts a regular expression, rather than a division.
+const KEYWORDS_PRECEDING_EXPRESSIONS: &[&str] = &[
+    "return",
+    "typeof",
+    "instanceof",
+    "in",
+    "of",
+    "new",
+    "delete",
+    "void",
+    "throw",
+    "case",
+    "do",
+    "else",
+    "yield",
+    "await",
+];
+
+/// What an unclosed `{`, JSX tag or JSX element belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Nesting {
+    /// A block, object literal, etc.
+    Brace,
+    /// A `${` substitution within a template literal
+    TemplateSubstitution,
+    /// A `{` within a JSX tag or within the children of a JSX element
+    JsxExpression,
+    /// The name and attributes of a JSX opening tag, i.e. between `<` and `>`
+    JsxTag,
+    /// The type arguments of a JSX opening tag, e.g. `<Option>` in
+    /// `<Select<Option> />`
+    JsxTypeArguments,
+    /// The children of a JSX element, i.e. JSX text and nested elements
+    JsxChildren,
+}
+
+/// A tokenizer that understands just enough JavaScript and TypeScript to find
+/// template literals reliably. It skips comments, strings and regular expressions,
+/// and tracks template literal substitutions (which can themselves contain
+/// template literals.) Whether a `/` starts a regular expression is decided based
+/// on the previous token, which is correct for all but pathological code.
+///
+/// JSX text (in which quotes and backticks are not special) is skipped. Since
+/// the file extension is not known, angle bracket type assertions such as
+/// `<Foo>bar` (which are not allowed in .tsx files) are mistaken for JSX elements
+/// that are never closed. In that case, we tokenize the file again without
+/// recognizing JSX.
+fn tokenize(content: &str) -> Vec<JsToken> {
+    let (tokens, has_unclosed_jsx_element) = tokenize_with_jsx(content, true);
+    if has_unclosed_jsx_element {
+        tokenize_with_jsx(content, false).0
+    } else {
+        tokens
+    }
+}
+
+/// Returns the tokens, and whether a JSX element was not closed by the end of
+/// the file.
+fn tokenize_with_jsx(content: &str, recognize_jsx: bool) -> (Vec<JsToken>, bool) {
+    let bytes = content.as_bytes();
+    let mut tokens: Vec<JsToken> = vec![];
+    let mut nesting_stack: Vec<Nesting> = vec![];
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if nesting_stack.last() == Some(&Nesting::JsxChildren) {
+            index = skip_jsx_text(bytes, index);
+            if index == bytes.len() {
+                break;
+            }
+            let start = index;
+            let kind = if bytes[index] == b'{' {
+                nesting_stack.push(Nesting::JsxExpression);
+                index += 1;
+                JsTokenKind::Punctuator
+            } else if bytes.get(skip_whitespace(bytes, index + 1)) == Some(&b'/') {
+                // A closing tag, which ends the element
+                index = skip_until(bytes, index, b">");
+                nesting_stack.pop();
+                JsTokenKind::Other
+            } else {
+                // A nested element
+                nesting_stack.push(Nesting::JsxTag);
+                index += 1;
+                JsTokenKind::Punctuator
+            };
+            tokens.push(JsToken {
+                kind,
+                start,
+                end: index,
+            });
+            continue;
+        }
+
+        let start = index;
+        let byte = bytes[index];
+        let next_byte = bytes.get(index + 1).copied();
+        let is_in_jsx_tag = nesting_stack.last() == Some(&Nesting::JsxTag);
+        let is_in_jsx_type_arguments = nesting_stack.last() == Some(&Nesting::JsxTypeArguments);
+
+        let kind = match byte {
+            b' ' | b'\t' | b'\n' | b'\r' | 0x0c => {
+                index += 1;
+                continue;
+            }
+            b'/' if next_byte == Some(b'/') => {
+                index = skip_until(bytes, index, b"\n");
+                continue;
+            }
+            b'/' if next_byte == Some(b'*') => {
+                index = skip_until(bytes, index + 2, b"*/");
+                continue;
+            }
+            b'/' if is_in_jsx_tag && next_byte == Some(b'>') => {
+                // The end of a self-closing element
+                nesting_stack.pop();
+                index += 2;
+                JsTokenKind::Other
+            }
+            b'>' if is_in_jsx_tag => {
+                nesting_stack.pop();
+                nesting_stack.push(Nesting::JsxChildren);
+                index += 1;
+                JsTokenKind::Punctuator
+            }
+            b'<' if is_in_jsx_tag || is_in_jsx_type_arguments => {
+                nesting_stack.push(Nesting::JsxTypeArguments);
+                index += 1;
+                JsTokenKind::Punctuator
+            }
+            b'>' if is_in_jsx_type_arguments => {
+                nesting_stack.pop();
+                index += 1;
+                JsTokenKind::Punctuator
+            }
+            // Attribute values cannot contain escapes, but can span lines.
+            b'\'' | b'"' if is_in_jsx_tag => {
+                index = skip_until(bytes, index + 1, &[byte]);
+                JsTokenKind::Other
+            }
+            b'<' if recognize_jsx
+                && regular_expression_allowed(content, tokens.last())
+                && starts_jsx_element(bytes, index + 1) =>
+            {
+                nesting_stack.push(Nesting::JsxTag);
+                index += 1;
+                JsTokenKind::Punctuator
+            }
+            b'\'' | b'"' => {
+                index = skip_string(bytes, index);
+                JsTokenKind::Other
+            }
+            b'`' => {
+                let (end, has_substitution) = skip_template(bytes, index + 1);
+                index = end;
+                if has_substitution {
+                    nesting_stack.push(Nesting::TemplateSubstitution);
+                    JsTokenKind::TemplateWithSubstitutions
+                } else {
+                    JsTokenKind::Template
+                }
+            }
+            b'{' => {
+                nesting_stack.push(if is_in_jsx_tag {
+                    Nesting::JsxExpression
+                } else {
+                    Nesting::Brace
+                });
+                index += 1;
+                JsTokenKind::Punctuator
+            }
+            b'}' => {
+                let closes_brace = matches!(
+                    nesting_stack.last(),
+                    Some(Nesting::Brace | Nesting::TemplateSubstitution | Nesting::JsxExpression)
+                );
+                if closes_brace && nesting_stack.pop() == Some(Nesting::TemplateSubstitution) {
+                    // The end of a substitution, so the template literal continues
+                    let (end, has_substitution) = skip_template(bytes, index + 1);
+                    index = end;
+                    if has_substitution {
+                        nesting_stack.push(Nesting::TemplateSubstitution);
+                    }
+                    JsTokenKind::TemplateWithSubstitutions
+                } else {
+                    index += 1;
+                    JsTokenKind::Punctuator
+                }
+            }
+            b'/' if !is_in_jsx_tag && regular_expression_allowed(content, tokens.last()) => {
+                index = skip_regular_expression(bytes, index);
+                JsTokenKind::Other
+            }
+            b'0'..=b'9' => {
+                index = skip_number(bytes, index);
+                JsTokenKind::Other
+            }
+            // Non-ASCII bytes are treated as identifier characters, so that tokens
+            // always start and end on a character boundary.
+            byte if is_identifier_byte(byte) => {
+                index = skip_identifier_characters(bytes, index);
+                JsTokenKind::Identifier
+            }
+            b'=' if next_byte == Some(b'>') => {
+                index += 2;
+                JsTokenKind::Punctuator
+            }
+            _ => {
+                index += 1;
+                JsTokenKind::Punctuator
+            }
+        };
+
+        tokens.push(JsToken {
+            kind,
+            start,
+            end: index,
+        });
+    }
+
+    let has_unclosed_jsx_element = nesting_stack.iter().any(|nesting| {
+        matches!(
+            nesting,
+            Nesting::JsxTag | Nesting::JsxTypeArguments | Nesting::JsxChildren
+        )
+    });
+    (tokens, has_unclosed_jsx_element)
+}
+
+/// Whether a `<` in a position where an expression is allowed starts a JSX
+/// element, i.e. whether it is followed by a tag name, or by `>` (a fragment).
+/// The type parameters of generic arrow functions, such as `<T,>` and
+/// `<T extends U>`, do not start JSX elements.
+fn starts_jsx_element(bytes: &[u8], index: usize) -> bool {
+    match bytes.get(index) {
+        Some(b'>') => true,
+        Some(&byte) if is_identifier_byte(byte) && !byte.is_ascii_digit() => {
+            let after_name = skip_whitespace(bytes, skip_identifier_characters(bytes, index));
+            let is_type_parameter = bytes.get(after_name) == Some(&b',')
+                || (bytes[after_name..].starts_with(b"extends")
+                    && bytes
+                        .get(after_name + "extends".len())
+                        .map(|byte| !is_identifier_byte(*byte))
+                        .unwrap_or(true));
+            !is_type_parameter
+        }
+        _ => false,
+    }
+}
+
+/// Skip JSX text, up to the `{` or `<` that ends it.
+fn skip_jsx_text(bytes: &[u8], mut index: usize) -> usize {
+    while index < bytes.len() && bytes[index] != b'{' && bytes[index] != b'<' {
+        index += 1;
+    }
+    index
+}
+
+fn skip_whitespace(bytes: &[u8], mut index: usize) -> usize {
+    while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+        index += 1;
+    }
+    index
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || byte >= 0x80
+}
+
+fn skip_identifier_characters(bytes: &[u8], mut index: usize) -> usize {
+    while index < bytes.len() && is_identifier_byte(bytes[index]) {
+        index += 1;
+    }
+    index
+}
+
+/// Numbers may contain periods, e.g. 1.5, as well as letters, e.g. 0xff or 1e3.
+fn skip_number(bytes: &[u8], mut index: usize) -> usize {
+    while index < bytes.len() && (is_identifier_byte(bytes[index]) || bytes[index] == b'.') {
+        index += 1;
+    }
+    index
+}
+
+/// Returns the index after the terminator, or the end of the file.
+fn skip_until(bytes: &[u8], index: usize, terminator: &[u8]) -> usize {
+    bytes[index..]
+        .windows(terminator.len())
+        .position(|window| window == terminator)
+        .map(|position| index + position + terminator.len())
+        .unwrap_or(bytes.len())
+}
+
+/// Skip a single- or double-quoted string. Unterminated strings end at the end
+/// of the line.
+fn skip_string(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'\n' => return index,
+            byte if byte == quote => return index + 1,
+            _ => index += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Skip the rest of a template literal, starting after a backtick or after the
+/// closing brace of a substitution. Returns the index after the closing backtick
+/// or after the `${` that opens a substitution, and whether a substitution was
+/// opened.
+fn skip_template(bytes: &[u8], mut index: usize) -> (usize, bool) {
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'`' => return (index + 1, false),
+            b'$' if bytes.get(index + 1) == Some(&b'{') => return (index + 2, true),
+            _ => index += 1,
+        }
+    }
+    (bytes.len(), false)
+}
+
+fn skip_regular_expression(bytes: &[u8], start: usize) -> usize {
+    let mut index = start + 1;
+    let mut in_character_class = false;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'[' => {
+                in_character_class = true;
+                index += 1;
+            }
+            b']' => {
+                in_character_class = false;
+                index += 1;
+            }
+            b'/' if !in_character_class => {
+                // Skip the flags
+                return skip_identifier_characters(bytes, index + 1);
+            }
+            b'\n' => return index,
+            _ => index += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn regular_expression_allowed(content: &str, previous_token: Option<&JsToken>) -> bool {
+    match previous_token {
+        None => true,
+        Some(token) => match token.kind {
+            JsTokenKind::Identifier => {
+                KEYWORDS_PRECEDING_EXPRESSIONS.contains(&token.text(content))
+            }
+            // A `/` after a `<` is the start of a JSX closing tag, not of a regular
+            // expression.
+            JsTokenKind::Punctuator => !matches!(token.text(content), ")" | "]" | "}" | "<"),
+            JsTokenKind::Template | JsTokenKind::TemplateWithSubstitutions => false,
+            JsTokenKind::Other => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_iso_literal_from_file_content;
+
+    /// The text and export name of each extracted iso literal.
+    fn extract(content: &str) -> Vec<(&str, Option<&str>)> {
+        extract_iso_literal_from_file_content(content)
+            .map(|extraction| (extraction.iso_literal_text, extraction.const_export_name))
+            .collect()
+    }
+
+    #[test]
+    fn ignores_iso_in_comments() {
+        let content = r#"
+            // export const Foo = iso(`field Query.Foo {}`)(foo);
+            /* export const Bar = iso(`field Query.Bar {}`)(bar); */
+            /** iso(` */
+            export const Baz = iso(`field Query.Baz {}`)(baz);
+        "#;
+        assert_eq!(extract(content), vec![("field Query.Baz {}", Some("Baz"))]);
+    }
+
+    #[test]
+    fn ignores_iso_in_strings() {
+        let content = r#"
+            const a = "iso(`field Query.Foo {}`)";
+            const b = 'it`s';
+            export const Baz = iso(`field Query.Baz {}`)(baz);
+        "#;
+        assert_eq!(extract(content), vec![("field Query.Baz {}", Some("Baz"))]);
+    }
+
+    #[test]
+    fn distinguishes_regular_expressions_from_division() {
+        // If the backtick within the regular expression started a template literal,
+        // the iso literal would be swallowed.
+        let content = r#"
+            const regex = /`[/`]/g;
+            export const Foo = iso(`field Query.Foo {}`)(foo);
+        "#;
+        assert_eq!(extract(content), vec![("field Query.Foo {}", Some("Foo"))]);
+
+        // If the first slash started a regular expression, it would end at the second
+        // slash and swallow the iso literal.
+        let content = r#"
+            const ratio = (width) / 2; export const Foo = iso(`field Query.Foo {}`)(foo); half / 2;
+        "#;
+        assert_eq!(extract(content), vec![("field Query.Foo {}", Some("Foo"))]);
+    }
+
+    #[test]
+    fn handles_nested_template_substitutions() {
+        let content = r#"
+            const message = `${`inner ${value} iso(`} iso(\`field Query.Bar {}\`)`;
+            export const Foo = iso(`field Query.Foo {}`)(foo);
+            const nested = `${ { key: `${iso(`field Query.Baz {}`)}` }.key }`;
+        "#;
+        assert_eq!(
+            extract(content),
+            vec![
+                ("field Query.Foo {}", Some("Foo")),
+                ("field Query.Baz {}", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_escaped_backticks() {
+        let content = r#"
+            const text = `not \` iso(\`field Query.Bar {}\`) \\`;
+            export const Foo = iso(`field Query.Foo {}`)(foo);
+        "#;
+        assert_eq!(extract(content), vec![("field Query.Foo {}", Some("Foo"))]);
+    }
+
+    #[test]
+    fn finds_export_names() {
+        let content = r#"
+            export const Foo = iso(`field Query.Foo {}`)(foo);
+            export const Bar: IsographEntrypoint<A, { b: C[] }> = iso(`entrypoint Query.Bar`);
+            export default iso(`field Query.Baz {}`)(baz);
+            const Qux = iso(`field Query.Qux {}`)(qux);
+            export const Quux = foo.iso(`field Query.Quux {}`);
+        "#;
+        assert_eq!(
+            extract(content),
+            vec![
+                ("field Query.Foo {}", Some("Foo")),
+                ("entrypoint Query.Bar", Some("Bar")),
+                ("field Query.Baz {}", Some("default")),
+                ("field Query.Qux {}", None),
+            ]
+        );
+    }
+
+    /// The export name of each extracted iso literal.
+    fn export_names(content: &str) -> Vec<Option<&str>> {
+        extract(content)
+            .into_iter()
+            .map(|(_, const_export_name)| const_export_name)
+            .collect()
+    }
+
+    #[test]
+    fn handles_jsx_closing_tags() {
+        // If the slash of a closing tag started a regular expression, it would
+        // swallow the rest of the line.
+        assert_eq!(
+            export_names(include_str!(
+                "../fixtures/iso_literal_extraction/jsx_closing_tags.tsx"
+            )),
+            vec![Some("Title"), Some("Header"), Some("Footer")]
+        );
+    }
+
+    #[test]
+    fn skips_jsx_text() {
+        // If the backtick in JSX text started a template literal, it would swallow
+        // the start of the next iso literal.
+        assert_eq!(
+            export_names(include_str!(
+                "../fixtures/iso_literal_extraction/jsx_text.tsx"
+            )),
+            vec![Some("Shortcuts"), Some("Console")]
+        );
+    }
+
+    #[test]
+    fn handles_type_assertions_that_look_like_jsx() {
+        assert_eq!(
+            export_names(include_str!(
+                "../fixtures/iso_literal_extraction/type_assertion.ts"
+            )),
+            vec![Some("Foo")]
+        );
+    }
+
+    #[test]
+    fn extracts_empty_iso_literals() {
+        let content = "export const Foo = iso(``)(foo);";
+        assert_eq!(extract(content), vec![("", Some("Foo"))]);
+
+        // An unterminated template literal is not extracted.
+        assert_eq!(extract("export const Foo = iso(`"), vec![]);
+    }
+}
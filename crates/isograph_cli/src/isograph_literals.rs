@@ -1,16 +1,13 @@
-use std::{
-    fs::{self, DirEntry},
-    io,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use ignore::{gitignore::Gitignore, WalkBuilder};
+use isograph_config::ProjectFiles;
 
 use crate::batch_compile::BatchCompileError;
 
 pub(crate) fn read_files_in_folder(
     canonicalized_root_path: &PathBuf,
+    project_files: &ProjectFiles,
 ) -> Result<Vec<(PathBuf, String)>, BatchCompileError> {
     if !canonicalized_root_path.is_dir() {
         return Err(BatchCompileError::ProjectRootNotADirectory {
@@ -19,22 +16,116 @@ pub(crate) fn read_files_in_folder(
         });
     }
 
-    read_dir_recursive(&canonicalized_root_path)?
+    read_dir_recursive(canonicalized_root_path, project_files)?
         .into_iter()
-        .filter(has_valid_extension)
         .map(|path| read_file(path, canonicalized_root_path))
         .collect()
 }
 
-fn has_valid_extension(path: &PathBuf) -> bool {
-    let extension = path.extension().and_then(|x| x.to_str());
-    match extension {
-        Some("ts") | Some("tsx") | Some("js") | Some("jsx") => true,
-        _ => false,
+/// Whether a file that the watcher reported as changed would have been
+/// included when walking the project root, ignoring .gitignore and .ignore files.
+pub(crate) fn is_valid_project_file(
+    path: &PathBuf,
+    canonicalized_root_path: &PathBuf,
+    project_files: &ProjectFiles,
+) -> bool {
+    path.strip_prefix(canonicalized_root_path)
+        .map(|relative_path| project_files.is_included_file(relative_path))
+        .unwrap_or(false)
+}
+
+/// Whether a change to this file can change which files are ignored.
+pub(crate) fn is_ignore_file(path: &Path) -> bool {
+    path.file_name()
+        .map(|file_name| file_name == ".gitignore" || file_name == ".ignore")
+        .unwrap_or(false)
+}
+
+/// Whether a path that the watcher reported as changed can affect which project
+/// files exist or what they contain, i.e. whether it is within the project root,
+/// outside of excluded and artifact directories, and not ignored by a .gitignore
+/// or .ignore file. This lets us skip changes to e.g. node_modules without walking
+/// the project root.
+///
+/// Only the .gitignore and .ignore files within the project root are consulted, so
+/// a path that is ignored by other means (e.g. a global gitignore) is considered
+/// relevant. That is harmless, since walking the project root skips it.
+pub(crate) fn is_relevant_changed_path(
+    path: &Path,
+    canonicalized_root_path: &Path,
+    project_files: &ProjectFiles,
+) -> bool {
+    let relative_path = match path.strip_prefix(canonicalized_root_path) {
+        Ok(relative_path) => relative_path,
+        Err(_) => return false,
+    };
+    if relative_path.as_os_str().is_empty() {
+        return true;
+    }
+
+    let is_within_skipped_directory = relative_path
+        .ancestors()
+        .skip(1)
+        .filter(|ancestor| !ancestor.as_os_str().is_empty())
+        .any(|ancestor| {
+            ancestor
+                .file_name()
+                .map(|name| name == ".git")
+                .unwrap_or(false)
+                || !project_files.is_included_directory(ancestor)
+        });
+    if is_within_skipped_directory {
+        return false;
+    }
+
+    if path.is_dir() {
+        relative_path
+            .file_name()
+            .map(|name| name != ".git")
+            .unwrap_or(true)
+            && project_files.is_included_directory(relative_path)
+            && !is_ignored_by_ignore_files(path, true, canonicalized_root_path)
+    } else if path.is_file() {
+        is_ignore_file(path)
+            || (project_files.is_included_file(relative_path)
+                && !is_ignored_by_ignore_files(path, false, canonicalized_root_path))
+    } else {
+        // The path was deleted, so we cannot tell whether it was ignored. Forgetting
+        // the source files within it is cheap, though.
+        true
     }
 }
 
-fn read_file(
+/// Whether the .ignore and .gitignore files in the project root and in the
+/// directories between it and the path ignore the path. As when walking the project
+/// root, deeper files take precedence, and .ignore takes precedence over .gitignore.
+fn is_ignored_by_ignore_files(path: &Path, is_dir: bool, canonicalized_root_path: &Path) -> bool {
+    for directory in path
+        .ancestors()
+        .skip(1)
+        .take_while(|directory| directory.starts_with(canonicalized_root_path))
+    {
+        for ignore_file_name in [".ignore", ".gitignore"] {
+            let ignore_file_path = directory.join(ignore_file_name);
+            if !ignore_file_path.is_file() {
+                continue;
+            }
+            // If some lines are invalid, the remaining lines are still used, as when
+            // walking.
+            let (ignore_file, _) = Gitignore::new(ignore_file_path);
+            let matched = ignore_file.matched_path_or_any_parents(path, is_dir);
+            if matched.is_ignore() {
+                return true;
+            }
+            if matched.is_whitelist() {
+                return false;
+            }
+        }
+    }
+    false
+}
+
+pub(crate) fn read_file(
     path: PathBuf,
     canonicalized_root_path: &PathBuf,
 ) -> Result<(PathBuf, String), BatchCompileError> {
@@ -60,61 +151,55 @@ fn read_file(
     ))
 }
 
-fn read_dir_recursive(root_js_path: &PathBuf) -> Result<Vec<PathBuf>, BatchCompileError> {
-    let mut paths = vec![];
-
-    visit_dirs_skipping_isograph(&root_js_path, &mut |dir_entry| {
-        paths.push(dir_entry.path());
-    })
-    .map_err(BatchCompileError::from)?;
-
-    Ok(paths)
-}
-
-// Thanks https://doc.rust-lang.org/stable/std/fs/fn.read_dir.html
-fn visit_dirs_skipping_isograph(dir: &Path, cb: &mut dyn FnMut(&DirEntry)) -> io::Result<()> {
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            if !dir.ends_with(ISOGRAPH_FOLDER) {
-                visit_dirs_skipping_isograph(&path, cb)?;
+/// Walk the project root, honoring .gitignore and .ignore files, and skipping
+/// artifact directories and excluded directories.
+fn read_dir_recursive(
+    canonicalized_root_path: &PathBuf,
+    project_files: &ProjectFiles,
+) -> Result<Vec<PathBuf>, BatchCompileError> {
+    let filter_root_path = canonicalized_root_path.clone();
+    let filter_project_files = project_files.clone();
+
+    let walker = WalkBuilder::new(canonicalized_root_path)
+        // Hidden files are not skipped unless they are ignored
+        .hidden(false)
+        // .gitignore files are honored even outside of a git repository
+        .require_git(false)
+        .filter_entry(move |dir_entry| {
+            let is_dir = dir_entry
+                .file_type()
+                .map(|file_type| file_type.is_dir())
+                .unwrap_or(false);
+            if !is_dir || dir_entry.file_name() == ".git" {
+                return !is_dir;
             }
-        } else {
-            cb(&entry);
+            match dir_entry.path().strip_prefix(&filter_root_path) {
+                Ok(relative_path) => {
+                    relative_path.as_os_str().is_empty()
+                        || filter_project_files.is_included_directory(relative_path)
+                }
+                Err(_) => true,
+            }
+        })
+        .build();
+
+    let mut paths = vec![];
+    for dir_entry in walker {
+        let dir_entry = dir_entry?;
+        let is_file = dir_entry
+            .file_type()
+            .map(|file_type| file_type.is_file())
+            .unwrap_or(false);
+        if is_file
+            && is_valid_project_file(
+                &dir_entry.path().to_path_buf(),
+                canonicalized_root_path,
+                project_files,
+            )
+        {
+            paths.push(dir_entry.into_path());
         }
     }
-    Ok(())
-}
-
-pub(crate) static ISOGRAPH_FOLDER: &'static str = "__isograph";
-lazy_static! {
-    static ref EXTRACT_ISO_LITERAL: Regex =
-        Regex::new(r"(export const ([^ ]+) =\s+)?iso(\()?`([^`]+)`(\))?(\()?").unwrap();
-}
-
-pub(crate) struct IsoLiteralExtraction<'a> {
-    pub(crate) const_export_name: Option<&'a str>,
-    pub(crate) iso_literal_text: &'a str,
-    pub(crate) iso_literal_start_index: usize,
-    pub(crate) has_associated_js_function: bool,
-    pub(crate) has_paren: bool,
-}
 
-pub(crate) fn extract_iso_literal_from_file_content<'a>(
-    content: &'a str,
-) -> impl Iterator<Item = IsoLiteralExtraction<'a>> + 'a {
-    EXTRACT_ISO_LITERAL
-        .captures_iter(content)
-        .into_iter()
-        .map(|captures| {
-            let iso_literal_match = captures.get(4).unwrap();
-            IsoLiteralExtraction {
-                const_export_name: captures.get(1).map(|_| captures.get(2).unwrap().as_str()),
-                iso_literal_text: iso_literal_match.as_str(),
-                iso_literal_start_index: iso_literal_match.start(),
-                has_associated_js_function: captures.get(6).is_some(),
-                has_paren: captures.get(3).is_some(),
-            }
-        })
+    Ok(paths)
 }
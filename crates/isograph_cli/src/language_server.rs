@@ -0,0 +1,485 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use common_lang_types::{EmbeddedLocation, LineAndColumn, Location, Severity, WithLocation};
+use isograph_config::CompilerConfig;
+use isograph_schema::ValidatedSchema;
+use lsp_server::{Connection, ErrorCode, Message, Notification, ProtocolError, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+        DidSaveTextDocument, LogMessage, Notification as _, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, Request as _},
+    CompletionOptions, DiagnosticRelatedInformation, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidChangeWatchedFilesParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, HoverProviderCapability,
+    LogMessageParams, MessageType, NumberOrString, OneOf, Position, PublishDiagnosticsParams,
+    Range, ServerCapabilities, TextDocumentPositionParams, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use thiserror::Error;
+
+use crate::{
+    batch_compile::create_validated_schema,
+    compiler_state::CompilerState,
+    language_server_features::{completion, go_to_definition, hover},
+};
+
+/// How long to wait for further changes before recompiling, so that we compile once
+/// per burst of keystrokes rather than once per keystroke.
+const RECOMPILE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Run a language server that communicates with the editor over stdio, until the
+/// editor asks it to shut down.
+pub(crate) fn run_language_server(config: CompilerConfig) -> Result<(), LanguageServerError> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let server_capabilities = serde_json::to_value(ServerCapabilities {
+        // We re-parse the entire document on every change, so we might as well
+        // receive the entire document.
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["{".to_string()]),
+            ..Default::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    })?;
+    connection.initialize(server_capabilities)?;
+
+    let mut state = LanguageServerState::new(config);
+    state.compile_and_publish_diagnostics(&connection)?;
+    main_loop(&connection, &mut state)?;
+
+    // The io threads exit once the connection is dropped.
+    drop(connection);
+    io_threads
+        .join()
+        .map_err(LanguageServerError::UnableToJoinIoThreads)?;
+
+    Ok(())
+}
+
+pub(crate) struct LanguageServerState {
+    config: CompilerConfig,
+    pub(crate) compiler_state: CompilerState,
+    /// The schema from the most recent compilation that succeeded. Completion, hover
+    /// and go-to-definition use this schema, so that they keep working while the
+    /// document being edited has errors.
+    pub(crate) validated_schema: Option<ValidatedSchema>,
+    /// Files for which we have published diagnostics, so that we can clear their
+    /// diagnostics once they are fixed.
+    files_with_diagnostics: HashSet<PathBuf>,
+}
+
+impl LanguageServerState {
+    fn new(config: CompilerConfig) -> Self {
+        LanguageServerState {
+            config,
+            compiler_state: CompilerState::default(),
+            validated_schema: None,
+            files_with_diagnostics: HashSet::new(),
+        }
+    }
+
+    fn compile_and_publish_diagnostics(
+        &mut self,
+        connection: &Connection,
+    ) -> Result<(), LanguageServerError> {
+        let mut diagnostics = vec![];
+        let result =
+            create_validated_schema(&self.config, &mut self.compiler_state, &mut diagnostics);
+        match result {
+            Ok((validated_schema, _)) => self.validated_schema = Some(validated_schema),
+            Err(err) => diagnostics.extend(err.to_diagnostics()),
+        }
+
+        let mut diagnostics_by_file: HashMap<PathBuf, Vec<lsp_types::Diagnostic>> = HashMap::new();
+        for diagnostic in diagnostics {
+            match diagnostic.location {
+                Location::Embedded(location) => {
+                    diagnostics_by_file
+                        .entry(PathBuf::from(location.file_path()))
+                        .or_default()
+                        .push(lsp_types::Diagnostic {
+                            range: self.lsp_range(&location).unwrap_or_default(),
+                            severity: Some(match diagnostic.severity {
+                                Severity::Error => DiagnosticSeverity::ERROR,
+                                Severity::Warning => DiagnosticSeverity::WARNING,
+                            }),
+                            code: Some(NumberOrString::String(diagnostic.code.to_string())),
+                            source: Some("isograph".to_string()),
+                            message: diagnostic.message,
+                            related_information: self
+                                .lsp_related_information(&diagnostic.related_locations),
+                            ..Default::default()
+                        });
+                }
+                Location::Generated => {
+                    // There is no document to which we can attach this diagnostic.
+                    send_notification::<LogMessage>(
+                        connection,
+                        LogMessageParams {
+                            typ: match diagnostic.severity {
+                                Severity::Error => MessageType::ERROR,
+                                Severity::Warning => MessageType::WARNING,
+                            },
+                            message: diagnostic.message,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        for path in self.files_with_diagnostics.drain() {
+            diagnostics_by_file.entry(path).or_default();
+        }
+        for (path, diagnostics) in diagnostics_by_file {
+            let uri = match Url::from_file_path(&path) {
+                Ok(uri) => uri,
+                Err(_) => continue,
+            };
+            if !diagnostics.is_empty() {
+                self.files_with_diagnostics.insert(path);
+            }
+            send_notification::<PublishDiagnostics>(
+                connection,
+                PublishDiagnosticsParams {
+                    uri,
+                    diagnostics,
+                    version: None,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// The content of the file, preferring the unsaved content of an open document
+    /// over the content on disk.
+    fn file_contents(&self, path: &Path) -> Option<Cow<'_, str>> {
+        match self.compiler_state.open_documents.get(path) {
+            Some(content) => Some(Cow::Borrowed(content)),
+            None => std::fs::read_to_string(path).ok().map(Cow::Owned),
+        }
+    }
+
+    /// The content of the document and the byte offset of the position within it.
+    pub(crate) fn document_and_offset(
+        &self,
+        text_document_position: &TextDocumentPositionParams,
+    ) -> Option<(Cow<'_, str>, usize)> {
+        let path = uri_to_path(&text_document_position.text_document.uri)?;
+        let document = self.file_contents(&path)?;
+        let offset = position_to_offset(&document, text_document_position.position);
+        Some((document, offset))
+    }
+
+    pub(crate) fn lsp_location(&self, location: &EmbeddedLocation) -> Option<lsp_types::Location> {
+        Some(lsp_types::Location::new(
+            Url::from_file_path(location.file_path()).ok()?,
+            self.lsp_range(location)?,
+        ))
+    }
+
+    /// Related locations that are generated, or that are in files that cannot be
+    /// read, are omitted.
+    fn lsp_related_information(
+        &self,
+        related_locations: &[WithLocation<String>],
+    ) -> Option<Vec<DiagnosticRelatedInformation>> {
+        let related_information: Vec<_> = related_locations
+            .iter()
+            .filter_map(|related_location| match related_location.location {
+                Location::Embedded(location) => Some(DiagnosticRelatedInformation {
+                    location: self.lsp_location(&location)?,
+                    message: related_location.item.clone(),
+                }),
+                Location::Generated => None,
+            })
+            .collect();
+        if related_information.is_empty() {
+            None
+        } else {
+            Some(related_information)
+        }
+    }
+
+    fn lsp_range(&self, location: &EmbeddedLocation) -> Option<Range> {
+        // Locations are relative to the text that was compiled, which may differ
+        // from the current content of the document.
+        let path = Path::new(location.file_path());
+        let file_contents = match self.compiler_state.file_contents(&self.config, path) {
+            Some(file_contents) => Cow::Borrowed(file_contents),
+            None => self.file_contents(path)?,
+        };
+        let (start, end) = location.line_and_column_range(&file_contents);
+        Some(Range::new(
+            line_and_column_to_position(start),
+            line_and_column_to_position(end),
+        ))
+    }
+}
+
+fn main_loop(
+    connection: &Connection,
+    state: &mut LanguageServerState,
+) -> Result<(), LanguageServerError> {
+    let mut must_recompile = false;
+    loop {
+        let message = if must_recompile {
+            match connection.receiver.recv_timeout(RECOMPILE_DEBOUNCE) {
+                Ok(message) => message,
+                Err(err) if err.is_timeout() => {
+                    state.compile_and_publish_diagnostics(connection)?;
+                    must_recompile = false;
+                    continue;
+                }
+                Err(_) => return Ok(()),
+            }
+        } else {
+            match connection.receiver.recv() {
+                Ok(message) => message,
+                Err(_) => return Ok(()),
+            }
+        };
+
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                let method = request.method.clone();
+                let response = match method.as_str() {
+                    Completion::METHOD => handle_request::<Completion>(state, request, completion),
+                    HoverRequest::METHOD => handle_request::<HoverRequest>(state, request, hover),
+                    GotoDefinition::METHOD => {
+                        handle_request::<GotoDefinition>(state, request, go_to_definition)
+                    }
+                    _ => Response::new_err(
+                        request.id,
+                        ErrorCode::MethodNotFound as i32,
+                        format!("Unsupported request {}", method),
+                    ),
+                };
+                send(connection, Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                let method = notification.method.clone();
+                match handle_notification(&mut state.compiler_state, notification) {
+                    Ok(notification_requires_recompile) => {
+                        must_recompile |= notification_requires_recompile
+                    }
+                    Err(err) => {
+                        // Notifications cannot be responded to, so we log the malformed
+                        // notification and keep going, as we do for malformed requests.
+                        send_notification::<LogMessage>(
+                            connection,
+                            LogMessageParams {
+                                typ: MessageType::ERROR,
+                                message: format!(
+                                    "Unable to handle notification {}.\nReason: {}",
+                                    method, err
+                                ),
+                            },
+                        )?;
+                    }
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+}
+
+fn handle_request<R: lsp_types::request::Request>(
+    state: &LanguageServerState,
+    request: Request,
+    handler: impl FnOnce(&LanguageServerState, R::Params) -> R::Result,
+) -> Response {
+    match serde_json::from_value::<R::Params>(request.params) {
+        Ok(params) => Response::new_ok(request.id, handler(state, params)),
+        Err(err) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, err.to_string()),
+    }
+}
+
+/// Update the open documents and changed paths. Returns whether we must recompile,
+/// or an error if the notification's params are malformed.
+fn handle_notification(
+    compiler_state: &mut CompilerState,
+    notification: Notification,
+) -> Result<bool, serde_json::Error> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+            // The document may have been created since we last walked the project root,
+            // in which case marking it as changed makes us check whether it is a project
+            // file.
+            if let Some(path) = uri_to_path(&params.text_document.uri) {
+                compiler_state
+                    .open_documents
+                    .insert(path.clone(), params.text_document.text);
+                compiler_state.mark_paths_as_changed(std::iter::once(path));
+            }
+        }
+        DidChangeTextDocument::METHOD => {
+            let mut params: DidChangeTextDocumentParams =
+                serde_json::from_value(notification.params)?;
+            // We requested full document sync, so the last change contains the entire
+            // document.
+            if let (Some(path), Some(change)) = (
+                uri_to_path(&params.text_document.uri),
+                params.content_changes.pop(),
+            ) {
+                compiler_state.open_documents.insert(path, change.text);
+            }
+        }
+        DidSaveTextDocument::METHOD => {
+            // The schema and schema extensions are always read from disk, so saving
+            // them must trigger a recompilation.
+            let params: DidSaveTextDocumentParams = serde_json::from_value(notification.params)?;
+            compiler_state
+                .mark_paths_as_changed(uri_to_path(&params.text_document.uri).into_iter());
+        }
+        DidCloseTextDocument::METHOD => {
+            // Once closed, the content on disk is used again.
+            let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+            if let Some(path) = uri_to_path(&params.text_document.uri) {
+                compiler_state.open_documents.remove(&path);
+                compiler_state.mark_paths_as_changed(std::iter::once(path));
+            }
+        }
+        DidChangeWatchedFiles::METHOD => {
+            let params: DidChangeWatchedFilesParams = serde_json::from_value(notification.params)?;
+            compiler_state.mark_paths_as_changed(
+                params
+                    .changes
+                    .iter()
+                    .filter_map(|file_event| uri_to_path(&file_event.uri)),
+            );
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn send_notification<N: lsp_types::notification::Notification>(
+    connection: &Connection,
+    params: N::Params,
+) -> Result<(), LanguageServerError> {
+    send(
+        connection,
+        Message::Notification(Notification::new(N::METHOD.to_string(), params)),
+    )
+}
+
+fn send(connection: &Connection, message: Message) -> Result<(), LanguageServerError> {
+    connection
+        .sender
+        .send(message)
+        .map_err(|_| LanguageServerError::ConnectionClosed)
+}
+
+/// Paths are canonicalized, so that they match the paths of source files, which
+/// are relative to the canonicalized project root. Deleted files cannot be
+/// canonicalized, so their paths are used as-is.
+fn uri_to_path(uri: &Url) -> Option<PathBuf> {
+    let path = uri.to_file_path().ok()?;
+    Some(path.canonicalize().unwrap_or(path))
+}
+
+/// LSP positions are zero-indexed, and (by default) measure characters in UTF-16
+/// code units, like our one-indexed columns.
+fn line_and_column_to_position(line_and_column: LineAndColumn) -> Position {
+    Position::new(
+        (line_and_column.line - 1) as u32,
+        (line_and_column.column - 1) as u32,
+    )
+}
+
+fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut line = 0;
+    let mut character = 0;
+    for (index, char) in text.char_indices() {
+        if line == position.line && (character >= position.character || char == '\n') {
+            return index;
+        }
+        if char == '\n' {
+            line += 1;
+            character = 0;
+        } else {
+            character += char.len_utf16() as u32;
+        }
+    }
+    text.len()
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum LanguageServerError {
+    #[error("Error in the language server protocol.\nReason: {0}")]
+    ProtocolError(#[from] ProtocolError),
+
+    #[error("Unable to serialize or deserialize a message.\nReason: {0}")]
+    UnableToSerializeOrDeserialize(#[from] serde_json::Error),
+
+    #[error("The connection to the editor was closed.")]
+    ConnectionClosed,
+
+    #[error("Unable to join the threads that read from stdin and write to stdout.\nReason: {0}")]
+    UnableToJoinIoThreads(std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use lsp_server::Notification;
+    use lsp_types::notification::{DidOpenTextDocument, Exit, Notification as _};
+    use serde_json::json;
+
+    use super::handle_notification;
+    use crate::compiler_state::CompilerState;
+
+    fn did_open(text_document: serde_json::Value) -> Notification {
+        Notification::new(
+            DidOpenTextDocument::METHOD.to_string(),
+            json!({ "textDocument": text_document }),
+        )
+    }
+
+    #[test]
+    fn malformed_notification_is_an_error() {
+        let mut compiler_state = CompilerState::default();
+
+        assert!(handle_notification(&mut compiler_state, did_open(json!(1))).is_err());
+        assert!(compiler_state.open_documents.is_empty());
+
+        // Subsequent notifications are still handled.
+        let notification = did_open(json!({
+            "uri": "file:///project/Foo.tsx",
+            "languageId": "typescriptreact",
+            "version": 1,
+            "text": "const foo = 1;",
+        }));
+        assert!(handle_notification(&mut compiler_state, notification).unwrap());
+        assert_eq!(
+            compiler_state
+                .open_documents
+                .get(Path::new("/project/Foo.tsx"))
+                .map(String::as_str),
+            Some("const foo = 1;")
+        );
+    }
+
+    #[test]
+    fn unhandled_notification_does_not_require_recompiling() {
+        let mut compiler_state = CompilerState::default();
+        let notification = Notification::new(Exit::METHOD.to_string(), json!(null));
+        assert!(!handle_notification(&mut compiler_state, notification).unwrap());
+    }
+}
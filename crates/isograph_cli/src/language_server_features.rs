@@ -0,0 +1,476 @@
+use common_lang_types::{
+    DescriptionValue, EmbeddedLocation, HasName, Location, SelectableFieldName, Span,
+    UnvalidatedTypeName, WithSpan,
+};
+use intern::{string_key::Intern, Lookup};
+use isograph_lang_parser::{lex_iso_literal, IsoLiteralExtractionResult, IsographLangTokenKind};
+use isograph_lang_types::{ObjectId, SelectableFieldId};
+use isograph_schema::{
+    FieldDefinitionLocation, ValidatedClientField, ValidatedFieldDefinitionLocation,
+    ValidatedSchema, ValidatedSchemaObject,
+};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionParams, CompletionResponse, Documentation,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams, MarkupContent,
+    MarkupKind, TextDocumentPositionParams,
+};
+
+use crate::{
+    iso_literal_extraction::extract_iso_literal_from_file_content,
+    language_server::LanguageServerState,
+};
+
+/// Complete the names of the fields that can be selected at the cursor.
+pub(crate) fn completion(
+    state: &LanguageServerState,
+    params: CompletionParams,
+) -> Option<CompletionResponse> {
+    let schema = state.validated_schema.as_ref()?;
+    let (document, offset) = state.document_and_offset(&params.text_document_position)?;
+    let cursor_context = iso_literal_cursor_context(&document, offset)?;
+    let object_id = object_id_at_path(
+        schema,
+        cursor_context.parent_type,
+        cursor_context.enclosing_selection_sets.as_ref()?,
+    )?;
+
+    let mut items: Vec<_> = schema
+        .schema_data
+        .object(object_id)
+        .encountered_fields
+        .iter()
+        .map(|(field_name, field)| {
+            let (description, type_description) = field_description_and_type(schema, field);
+            CompletionItem {
+                label: field_name.to_string(),
+                kind: Some(match field {
+                    FieldDefinitionLocation::Server(_) => CompletionItemKind::FIELD,
+                    FieldDefinitionLocation::Client(_) => CompletionItemKind::FUNCTION,
+                }),
+                detail: Some(type_description),
+                documentation: description
+                    .map(|description| Documentation::String(description.to_string())),
+                ..Default::default()
+            }
+        })
+        .collect();
+    items.sort_by(|item_1, item_2| item_1.label.cmp(&item_2.label));
+
+    Some(CompletionResponse::Array(items))
+}
+
+/// Show the type and description of the field under the cursor.
+pub(crate) fn hover(state: &LanguageServerState, params: HoverParams) -> Option<Hover> {
+    let field_under_cursor = field_under_cursor(state, &params.text_document_position_params)?;
+    let (description, type_description) =
+        field_description_and_type(field_under_cursor.schema, field_under_cursor.field);
+
+    let mut value = format!(
+        "```graphql\n{}.{}: {}\n```",
+        field_under_cursor.parent_object.name, field_under_cursor.field_name, type_description
+    );
+    if let Some(description) = description {
+        value.push_str("\n\n");
+        value.push_str(description.lookup());
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: None,
+    })
+}
+
+/// Go from a server field to its definition in the schema, or from a client field
+/// to the iso literal that declares it.
+pub(crate) fn go_to_definition(
+    state: &LanguageServerState,
+    params: GotoDefinitionParams,
+) -> Option<GotoDefinitionResponse> {
+    let field_under_cursor = field_under_cursor(state, &params.text_document_position_params)?;
+    let schema = field_under_cursor.schema;
+
+    let location = match field_under_cursor.field {
+        FieldDefinitionLocation::Server(server_field_id) => {
+            match schema.field(*server_field_id).name.location {
+                Location::Embedded(location) => location,
+                Location::Generated => return None,
+            }
+        }
+        FieldDefinitionLocation::Client(client_field_id) => {
+            client_field_declaration_location(state, schema.client_field(*client_field_id))?
+        }
+    };
+
+    Some(GotoDefinitionResponse::Scalar(
+        state.lsp_location(&location)?,
+    ))
+}
+
+struct FieldUnderCursor<'a> {
+    schema: &'a ValidatedSchema,
+    parent_object: &'a ValidatedSchemaObject,
+    field_name: SelectableFieldName,
+    field: &'a ValidatedFieldDefinitionLocation,
+}
+
+fn field_under_cursor<'a>(
+    state: &'a LanguageServerState,
+    text_document_position: &TextDocumentPositionParams,
+) -> Option<FieldUnderCursor<'a>> {
+    let schema = state.validated_schema.as_ref()?;
+    let (document, offset) = state.document_and_offset(text_document_position)?;
+    let cursor_context = iso_literal_cursor_context(&document, offset)?;
+    let object_id = object_id_at_path(
+        schema,
+        cursor_context.parent_type,
+        cursor_context.enclosing_selection_sets.as_ref()?,
+    )?;
+
+    let parent_object = schema.schema_data.object(object_id);
+    let field_name: SelectableFieldName = cursor_context.field_name_under_cursor?.intern().into();
+    let field = parent_object.encountered_fields.get(&field_name)?;
+
+    Some(FieldUnderCursor {
+        schema,
+        parent_object,
+        field_name,
+        field,
+    })
+}
+
+/// Client fields do not record where they were declared, so we look for the iso
+/// literal that declares the client field among the parsed source files. Client
+/// fields that are not declared in an iso literal (e.g. refetch fields) have no
+/// location.
+fn client_field_declaration_location(
+    state: &LanguageServerState,
+    client_field: &ValidatedClientField,
+) -> Option<EmbeddedLocation> {
+    state
+        .compiler_state
+        .source_files
+        .as_ref()?
+        .values()
+        .flat_map(|source_file| source_file.iso_literals.iter().flatten())
+        .find_map(|(extraction_result, text_source)| match extraction_result {
+            IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration) => {
+                let client_field_declaration = &client_field_declaration.item;
+                if client_field_declaration.parent_type.item.lookup()
+                    == client_field.type_and_field.type_name.lookup()
+                    && client_field_declaration.client_field_name.item.lookup()
+                        == client_field.name.lookup()
+                {
+                    Some(EmbeddedLocation::new(
+                        *text_source,
+                        client_field_declaration.client_field_name.span,
+                    ))
+                } else {
+                    None
+                }
+            }
+            IsoLiteralExtractionResult::EntrypointDeclaration(_) => None,
+        })
+}
+
+fn field_description_and_type(
+    schema: &ValidatedSchema,
+    field: &ValidatedFieldDefinitionLocation,
+) -> (Option<DescriptionValue>, String) {
+    match field {
+        FieldDefinitionLocation::Server(server_field_id) => {
+            let server_field = schema.field(*server_field_id);
+            let type_annotation = server_field
+                .associated_data
+                .clone()
+                .map(|type_id| schema.schema_data.lookup_unvalidated_type(type_id).name());
+            (server_field.description, type_annotation.to_string())
+        }
+        FieldDefinitionLocation::Client(client_field_id) => (
+            schema.client_field(*client_field_id).description,
+            "client field".to_string(),
+        ),
+    }
+}
+
+/// Follow the linked fields and type refinements, starting at the parent type, and
+/// return the type of the innermost selection set.
+fn object_id_at_path(
+    schema: &ValidatedSchema,
+    parent_type: &str,
+    enclosing_selection_sets: &[EnclosingSelectionSet],
+) -> Option<ObjectId> {
+    let mut object_id = object_id_of_type_name(schema, parent_type)?;
+
+    for enclosing_selection_set in enclosing_selection_sets {
+        object_id = match enclosing_selection_set {
+            EnclosingSelectionSet::LinkedField(linked_field_name) => {
+                let linked_field_name: SelectableFieldName = linked_field_name.intern().into();
+                match schema
+                    .schema_data
+                    .object(object_id)
+                    .encountered_fields
+                    .get(&linked_field_name)?
+                {
+                    FieldDefinitionLocation::Server(server_field_id) => {
+                        match schema.field(*server_field_id).associated_data.inner() {
+                            SelectableFieldId::Object(object_id) => *object_id,
+                            SelectableFieldId::Scalar(_)
+                            | SelectableFieldId::Enum(_)
+                            | SelectableFieldId::InputObject(_) => return None,
+                        }
+                    }
+                    FieldDefinitionLocation::Client(_) => return None,
+                }
+            }
+            EnclosingSelectionSet::TypeRefinement(type_to_refine_to) => {
+                object_id_of_type_name(schema, type_to_refine_to)?
+            }
+        };
+    }
+
+    Some(object_id)
+}
+
+fn object_id_of_type_name(schema: &ValidatedSchema, type_name: &str) -> Option<ObjectId> {
+    let type_name: UnvalidatedTypeName = type_name.intern().into();
+    match schema.schema_data.defined_types.get(&type_name)? {
+        SelectableFieldId::Object(object_id) => Some(*object_id),
+        SelectableFieldId::Scalar(_)
+        | SelectableFieldId::Enum(_)
+        | SelectableFieldId::InputObject(_) => None,
+    }
+}
+
+/// A selection set that contains the cursor, nested within the selection set of the
+/// client field or entrypoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EnclosingSelectionSet<'a> {
+    /// The selection set of the linked field with this name.
+    LinkedField(&'a str),
+    /// The selection set of a type refinement (`... on Type`) to this type.
+    TypeRefinement(&'a str),
+}
+
+/// Where the cursor is within an iso literal.
+#[derive(Debug, PartialEq, Eq)]
+struct IsoLiteralCursorContext<'a> {
+    /// The type on which the client field or entrypoint is defined.
+    parent_type: &'a str,
+    /// The selection sets that contain the cursor, outermost first, excluding the
+    /// selection set of the client field itself. None if the cursor is not within a
+    /// selection set.
+    enclosing_selection_sets: Option<Vec<EnclosingSelectionSet<'a>>>,
+    /// The name of the selected field under the cursor, if any. The name of the
+    /// client field being declared also counts as a selected field on the parent
+    /// type.
+    field_name_under_cursor: Option<&'a str>,
+}
+
+/// Determine the cursor context by lexing the iso literal that contains the cursor,
+/// rather than by parsing it, because the iso literal is usually incomplete while
+/// it is being typed.
+fn iso_literal_cursor_context(
+    document: &str,
+    offset: usize,
+) -> Option<IsoLiteralCursorContext<'_>> {
+    let iso_literal_extraction =
+        extract_iso_literal_from_file_content(document).find(|iso_literal_extraction| {
+            let start = iso_literal_extraction.iso_literal_start_index;
+            start <= offset && offset <= start + iso_literal_extraction.iso_literal_text.len()
+        })?;
+    let iso_literal_text = iso_literal_extraction.iso_literal_text;
+    let offset = (offset - iso_literal_extraction.iso_literal_start_index) as u32;
+
+    let tokens: Vec<_> = lex_iso_literal(iso_literal_text).collect();
+    let source = move |span: Span| &iso_literal_text[span.as_usize_range()];
+    let kind_at = |token_index: usize| tokens.get(token_index).map(|token| token.item);
+
+    // The iso literal starts with `field` or `entrypoint`, followed by an optional
+    // description and `Type.field_name`.
+    let mut index = 1;
+    if matches!(
+        kind_at(index)?,
+        IsographLangTokenKind::StringLiteral | IsographLangTokenKind::BlockStringLiteral
+    ) {
+        index += 1;
+    }
+    let parent_type_token = tokens.get(index)?;
+    if parent_type_token.item != IsographLangTokenKind::Identifier {
+        return None;
+    }
+    let parent_type = source(parent_type_token.span);
+
+    let mut field_name_under_cursor = None;
+    if let Some(client_field_name_token) = tokens.get(index + 2) {
+        let span = client_field_name_token.span;
+        if client_field_name_token.item == IsographLangTokenKind::Identifier
+            && span.start <= offset
+            && offset <= span.end
+        {
+            return Some(IsoLiteralCursorContext {
+                parent_type,
+                enclosing_selection_sets: Some(vec![]),
+                field_name_under_cursor: Some(source(span)),
+            });
+        }
+    }
+
+    // Each entry is the selection set that was opened, or None if we do not know what
+    // the selection set belongs to. The outermost entry is the selection set of the
+    // client field itself.
+    let mut selection_set_stack: Vec<Option<EnclosingSelectionSet>> = vec![];
+    let mut last_selection = None;
+    let mut paren_depth = 0;
+    for (token_index, token) in tokens.iter().enumerate().skip(index + 3) {
+        let WithSpan { item: kind, span } = *token;
+        let is_identifier = kind == IsographLangTokenKind::Identifier;
+        // An identifier that ends at the cursor is under the cursor, but punctuation
+        // that starts at the cursor is after it.
+        if (is_identifier && span.start > offset) || (!is_identifier && span.end > offset) {
+            break;
+        }
+
+        match kind {
+            IsographLangTokenKind::OpenBrace => {
+                selection_set_stack.push(last_selection.take());
+            }
+            IsographLangTokenKind::CloseBrace => {
+                selection_set_stack.pop();
+                last_selection = None;
+            }
+            IsographLangTokenKind::OpenParen => paren_depth += 1,
+            IsographLangTokenKind::CloseParen => paren_depth -= 1,
+            IsographLangTokenKind::Identifier => {
+                let previous_kind = kind_at(token_index - 1);
+                let is_directive = previous_kind == Some(IsographLangTokenKind::At);
+                let is_alias = kind_at(token_index + 1) == Some(IsographLangTokenKind::Colon);
+                // In `... on Type`, `on` follows the spread and `Type` follows `on`.
+                let is_on_keyword = previous_kind == Some(IsographLangTokenKind::Spread);
+                let is_type_to_refine_to = kind_at(token_index - 2)
+                    == Some(IsographLangTokenKind::Spread)
+                    && previous_kind == Some(IsographLangTokenKind::Identifier);
+                if is_directive
+                    || is_alias
+                    || is_on_keyword
+                    || paren_depth != 0
+                    || selection_set_stack.is_empty()
+                {
+                    continue;
+                }
+
+                if is_type_to_refine_to {
+                    last_selection = Some(EnclosingSelectionSet::TypeRefinement(source(span)));
+                } else {
+                    let field_name = source(span);
+                    last_selection = Some(EnclosingSelectionSet::LinkedField(field_name));
+                    if offset <= span.end {
+                        field_name_under_cursor = Some(field_name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // If we do not know what one of the enclosing selection sets belongs to, we cannot
+    // tell which type the cursor is on.
+    let enclosing_selection_sets = if selection_set_stack.is_empty() {
+        None
+    } else {
+        selection_set_stack.into_iter().skip(1).collect()
+    };
+
+    Some(IsoLiteralCursorContext {
+        parent_type,
+        enclosing_selection_sets,
+        field_name_under_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{iso_literal_cursor_context, EnclosingSelectionSet, IsoLiteralCursorContext};
+
+    /// Return the cursor context at the position of the `|` in the document.
+    fn cursor_context_at_bar(document: &str) -> Option<IsoLiteralCursorContext<'_>> {
+        let offset = document
+            .find('|')
+            .expect("Expected document to contain a cursor");
+        iso_literal_cursor_context(document, offset)
+    }
+
+    #[test]
+    fn cursor_in_client_field_selection_set() {
+        let document = "export const Foo = iso(`\n  field Query.Foo {\n    na|me\n  }\n`);";
+        assert_eq!(
+            cursor_context_at_bar(document),
+            Some(IsoLiteralCursorContext {
+                parent_type: "Query",
+                enclosing_selection_sets: Some(vec![]),
+                field_name_under_cursor: Some("na"),
+            })
+        );
+    }
+
+    #[test]
+    fn cursor_in_linked_field() {
+        let document =
+            "export const Foo = iso(`\n  field Query.Foo {\n    me {\n      |\n    }\n  }\n`);";
+        let cursor_context = cursor_context_at_bar(document).unwrap();
+        assert_eq!(
+            cursor_context.enclosing_selection_sets,
+            Some(vec![EnclosingSelectionSet::LinkedField("me")])
+        );
+        assert_eq!(cursor_context.field_name_under_cursor, None);
+    }
+
+    #[test]
+    fn cursor_in_type_refinement() {
+        let document = "export const Foo = iso(`\n  field Query.Foo {\n    node(id: 1) {\n      \
+             ... on User {\n        |\n      }\n    }\n  }\n`);";
+        assert_eq!(
+            cursor_context_at_bar(document)
+                .unwrap()
+                .enclosing_selection_sets,
+            Some(vec![
+                EnclosingSelectionSet::LinkedField("node"),
+                EnclosingSelectionSet::TypeRefinement("User"),
+            ])
+        );
+    }
+
+    #[test]
+    fn cursor_after_closed_selection_sets() {
+        let document =
+            "export const Foo = iso(`\n  field Query.Foo {\n    me @loadable { id }\n    \
+             ... on Query { name }\n    |\n  }\n`);";
+        assert_eq!(
+            cursor_context_at_bar(document)
+                .unwrap()
+                .enclosing_selection_sets,
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn cursor_on_client_field_name() {
+        let document = "export const Foo = iso(`\n  field Query.Foo| {\n    name\n  }\n`);";
+        assert_eq!(
+            cursor_context_at_bar(document),
+            Some(IsoLiteralCursorContext {
+                parent_type: "Query",
+                enclosing_selection_sets: Some(vec![]),
+                field_name_under_cursor: Some("Foo"),
+            })
+        );
+    }
+
+    #[test]
+    fn cursor_outside_iso_literal() {
+        let document = "export const F|oo = iso(`\n  field Query.Foo {\n    name\n  }\n`);";
+        assert_eq!(cursor_context_at_bar(document), None);
+    }
+}
@@ -1,26 +1,53 @@
 mod artifact_file_contents;
 mod batch_compile;
+mod check_artifacts;
+mod compiler_state;
+mod diagnostics;
 mod generate_artifacts;
+mod iso_literal_extraction;
 mod isograph_literals;
+mod language_server;
+mod language_server_features;
 mod opt;
 mod schema;
 mod watch;
 mod write_artifacts;
 
-use batch_compile::compile_and_print;
+use batch_compile::{compile_and_print, CompileMode};
 use colored::Colorize;
-use isograph_config::create_config;
-use opt::CliOptions;
+use common_lang_types::{Diagnostic, ErrorCode, Location, Severity};
+use compiler_state::CompilerState;
+use diagnostics::{print_diagnostics, DiagnosticsFormat};
+use isograph_config::{create_config, ConfigError};
+use language_server::run_language_server;
+use opt::{CliOptions, Command};
 use structopt::StructOpt;
 use watch::handle_watch_command;
 
 #[tokio::main]
 async fn main() {
     let opt = CliOptions::from_args();
-    let config = create_config(opt.config.unwrap_or("./isograph.config.json".into()));
+    let config = match create_config(opt.config).and_then(|mut config| {
+        if let Some(project_name) = &opt.project {
+            config.select_project(project_name)?;
+        }
+        Ok(config)
+    }) {
+        Ok(config) => config,
+        Err(err) => exit_with_config_error(err, opt.diagnostics_format),
+    };
 
-    if opt.watch {
-        match handle_watch_command(config).await {
+    if let Some(Command::Lsp) = opt.command {
+        let config = match config.into_single_project() {
+            Ok(config) => config,
+            Err(err) => exit_with_config_error(err, opt.diagnostics_format),
+        };
+        if let Err(err) = run_language_server(config) {
+            eprintln!("{}\n{}", "Error in language server.\n".bright_red(), err);
+            std::process::exit(1);
+        }
+    } else if opt.watch {
+        match handle_watch_command(config, opt.diagnostics_format).await {
             Ok(res) => match res {
                 Ok(_) => {
                     eprintln!("{}", "Successfully watched. Exiting.\n".bright_green())
@@ -44,8 +71,40 @@ async fn main() {
             }
         };
     } else {
-        if let Err(_) = compile_and_print(&config) {
+        let compile_mode = if opt.check {
+            CompileMode::CheckArtifacts
+        } else if opt.validate_only {
+            CompileMode::ValidateOnly
+        } else {
+            CompileMode::WriteArtifacts
+        };
+        let mut compiler_states: Vec<_> = config
+            .projects
+            .iter()
+            .map(|_| CompilerState::default())
+            .collect();
+        if !compile_and_print(
+            config.projects.iter().zip(compiler_states.iter_mut()),
+            compile_mode,
+            opt.diagnostics_format,
+        ) {
             std::process::exit(1);
         }
     }
 }
+
+fn exit_with_config_error(err: ConfigError, diagnostics_format: Option<DiagnosticsFormat>) -> ! {
+    match diagnostics_format {
+        Some(diagnostics_format) => print_diagnostics(
+            diagnostics_format,
+            &[Diagnostic::new(
+                err.error_code(),
+                Severity::Error,
+                err.to_string(),
+                Location::generated(),
+            )],
+        ),
+        None => eprintln!("{}\n{}", "Error when loading config.\n".bright_red(), err),
+    }
+    std::process::exit(1);
+}
@@ -2,14 +2,46 @@ use std::path::PathBuf;
 
 use structopt::StructOpt;
 
+use crate::diagnostics::DiagnosticsFormat;
+
 /// Options if we're doing a batch compilation
 #[derive(Debug, StructOpt)]
 pub(crate) struct CliOptions {
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+
     #[structopt(long)]
     pub watch: bool,
 
-    /// Compile using this config file. If not provided, searches for a config in
-    /// package.json under the `isograph` key.
+    /// Do not write artifacts. Instead, fail if any generated artifact is missing,
+    /// has changed or should be deleted.
+    #[structopt(long, conflicts_with_all = &["watch", "validate-only"])]
+    pub check: bool,
+
+    /// Do not generate artifacts. Instead, stop after validating the schema, client
+    /// fields and entrypoints.
+    #[structopt(long, conflicts_with = "watch")]
+    pub validate_only: bool,
+
+    /// Print errors and warnings to stdout in a machine-readable format,
+    /// either json or sarif.
+    #[structopt(long, possible_values = &["json", "sarif"])]
+    pub diagnostics_format: Option<DiagnosticsFormat>,
+
+    /// Compile using this config file, which can be an isograph.config.json file or
+    /// a package.json file with an `isograph` key. If not provided, searches the
+    /// current directory and its parents for either.
     #[structopt(long)]
     pub config: Option<PathBuf>,
+
+    /// Only compile the project with this name, from the config's `projects` map.
+    /// Required by the language server if the config contains multiple projects.
+    #[structopt(long)]
+    pub project: Option<String>,
+}
+
+#[derive(Debug, StructOpt)]
+pub(crate) enum Command {
+    /// Run a language server, which communicates with the editor over stdio
+    Lsp,
 }
@@ -1,47 +1,98 @@
 use colored::Colorize;
-use isograph_config::CompilerConfig;
+use isograph_config::{CompilerConfig, IsographConfig};
 use notify::{Error, RecommendedWatcher, RecursiveMode, Watcher};
 use notify_debouncer_full::{
     new_debouncer, DebounceEventResult, DebouncedEvent, Debouncer, FileIdMap,
 };
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use tokio::{runtime::Handle, sync::mpsc::Receiver, task::JoinError};
 
-use crate::batch_compile::compile_and_print;
+use crate::{
+    batch_compile::{compile_and_print, CompileMode},
+    compiler_state::CompilerState,
+    diagnostics::DiagnosticsFormat,
+    isograph_literals::is_relevant_changed_path,
+};
 
 pub(crate) async fn handle_watch_command(
-    config: CompilerConfig,
+    config: IsographConfig,
+    diagnostics_format: Option<DiagnosticsFormat>,
 ) -> Result<Result<(), Vec<Error>>, JoinError> {
-    let _ = compile_and_print(&config);
+    // The compiler state of each project is kept alive between compilations, so
+    // that we only re-parse files that changed.
+    let mut compiler_states: Vec<_> = config
+        .projects
+        .iter()
+        .map(|_| CompilerState::default())
+        .collect();
+    compile_and_print(
+        config.projects.iter().zip(compiler_states.iter_mut()),
+        CompileMode::WriteArtifacts,
+        diagnostics_format,
+    );
 
     let (mut rx, mut watcher) = create_debounced_file_watcher();
 
-    // We need to watch a few things: the schema, extensions, and project root
-    watcher
-        .watcher()
-        .watch(&config.project_root, RecursiveMode::Recursive)
-        .expect("Failure when watching project root");
-    watcher
-        .watcher()
-        .watch(&config.schema, RecursiveMode::Recursive)
-        .expect("Failing when watching schema");
-    for extension in &config.schema_extensions {
+    // We need to watch a few things for each project: the schema, extensions, and
+    // project root
+    for project in &config.projects {
+        watcher
+            .watcher()
+            .watch(&project.project_root, RecursiveMode::Recursive)
+            .expect("Failure when watching project root");
         watcher
             .watcher()
-            .watch(&extension, RecursiveMode::Recursive)
-            .expect("Failing when watching schema extension");
+            .watch(&project.schema, RecursiveMode::Recursive)
+            .expect("Failing when watching schema");
+        for extension in &project.schema_extensions {
+            watcher
+                .watcher()
+                .watch(&extension, RecursiveMode::Recursive)
+                .expect("Failing when watching schema extension");
+        }
     }
 
     tokio::spawn(async move {
         while let Some(res) = rx.recv().await {
             match res {
                 Ok(events) => {
-                    if any_modified_path_is_outside_artifact_directory(
-                        &events,
-                        &config.artifact_directory,
-                    ) {
+                    let changed_paths =
+                        modified_paths_outside_artifact_directories(&events, &config.projects);
+                    // Only the projects whose inputs changed are recompiled.
+                    let mut changed_projects = vec![false; config.projects.len()];
+                    for (index, (project, compiler_state)) in config
+                        .projects
+                        .iter()
+                        .zip(compiler_states.iter_mut())
+                        .enumerate()
+                    {
+                        let project_changed_paths: Vec<_> = changed_paths
+                            .iter()
+                            .filter(|changed_path| is_project_input(project, changed_path))
+                            .cloned()
+                            .collect();
+                        if !project_changed_paths.is_empty() {
+                            compiler_state.mark_paths_as_changed(project_changed_paths.into_iter());
+                            changed_projects[index] = true;
+                        }
+                    }
+
+                    if changed_projects.contains(&true) {
                         eprintln!("{}", "File changes detected.".cyan());
-                        let _ = compile_and_print(&config);
+                        compile_and_print(
+                            config
+                                .projects
+                                .iter()
+                                .zip(compiler_states.iter_mut())
+                                .zip(changed_projects.iter())
+                                .filter(|(_, changed)| **changed)
+                                .map(|(project, _)| project),
+                            CompileMode::WriteArtifacts,
+                            diagnostics_format,
+                        );
                     }
                 }
                 Err(errors) => return Err(errors),
@@ -52,20 +103,35 @@ pub(crate) async fn handle_watch_command(
     .await
 }
 
-fn any_modified_path_is_outside_artifact_directory(
+fn modified_paths_outside_artifact_directories(
     events: &[DebouncedEvent],
-    artifact_directory: &PathBuf,
-) -> bool {
+    projects: &[CompilerConfig],
+) -> Vec<PathBuf> {
+    let mut modified_paths = vec![];
     for event in events.iter() {
         for watched_path in event.paths.iter() {
             // These paths are canonicalized, so it is sufficient
             // to check starts_with
-            if !watched_path.starts_with(artifact_directory) {
-                return true;
+            if !projects
+                .iter()
+                .any(|project| watched_path.starts_with(&project.artifact_directory))
+            {
+                modified_paths.push(watched_path.clone());
             }
         }
     }
-    false
+    modified_paths
+}
+
+/// Whether a change to this path can affect the compilation of the project.
+/// Changes to excluded or ignored paths within the project root do not.
+fn is_project_input(project: &CompilerConfig, path: &Path) -> bool {
+    is_relevant_changed_path(path, &project.project_root, &project.project_files)
+        || path == project.schema
+        || project
+            .schema_extensions
+            .iter()
+            .any(|schema_extension| path == schema_extension)
 }
 
 fn create_debounced_file_watcher() -> (
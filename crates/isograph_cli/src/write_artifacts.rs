@@ -1,23 +1,41 @@
 use std::{
-    fs::{self, File},
-    io::Write,
-    path::PathBuf,
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs::{self, DirEntry, File},
+    hash::{Hash, Hasher},
+    io::{self, Write},
+    path::{Path, PathBuf},
 };
 
 use crate::generate_artifacts::{GenerateArtifactsError, PathAndContent};
 
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ArtifactWriteCounts {
+    pub(crate) written: usize,
+    pub(crate) unchanged: usize,
+    pub(crate) deleted: usize,
+}
+
+/// The hash of the content of each artifact that was written (or found to be up to
+/// date) by the previous compilation, keyed by absolute path.
+pub(crate) type ArtifactHashes = HashMap<PathBuf, u64>;
+
+/// Write the artifacts to disk.
+///
+/// Artifacts whose content matches the file on disk are not touched. Changed and
+/// new artifacts are written to a temporary file, which is then renamed, so that
+/// dev servers never observe a half-written artifact. Files in the artifact
+/// directory that are no longer generated are deleted.
+///
+/// If previous_artifact_hashes is Some, i.e. this is not the first compilation,
+/// artifacts are compared against the previous compilation's artifacts instead of
+/// against the files on disk, and only previously generated artifacts can be stale.
+/// This way, a recompilation only touches the artifacts that changed, instead of
+/// reading every artifact and walking the artifact directory.
 pub(crate) fn write_to_disk<'schema>(
     paths_and_contents: impl Iterator<Item = PathAndContent>,
     artifact_directory: &PathBuf,
-) -> Result<usize, GenerateArtifactsError> {
-    if artifact_directory.exists() {
-        fs::remove_dir_all(&artifact_directory).map_err(|e| {
-            GenerateArtifactsError::UnableToDeleteDirectory {
-                path: artifact_directory.clone(),
-                message: e,
-            }
-        })?;
-    }
+    previous_artifact_hashes: &mut Option<ArtifactHashes>,
+) -> Result<ArtifactWriteCounts, GenerateArtifactsError> {
     fs::create_dir_all(&artifact_directory).map_err(|e| {
         GenerateArtifactsError::UnableToCreateDirectory {
             path: artifact_directory.clone(),
@@ -25,33 +43,339 @@ pub(crate) fn write_to_disk<'schema>(
         }
     })?;
 
-    let mut count = 0;
-    for path_and_content in paths_and_contents {
-        // Is this better than materializing paths_and_contents sooner?
-        count += 1;
+    // If we fail partway, the next compilation compares against the disk again.
+    let previous_artifact_hashes_taken = previous_artifact_hashes.take();
 
+    let mut counts = ArtifactWriteCounts::default();
+    let mut artifact_hashes = ArtifactHashes::new();
+    for path_and_content in paths_and_contents {
         let absolute_directory = artifact_directory.join(path_and_content.relative_directory);
-        fs::create_dir_all(&absolute_directory).map_err(|e| {
-            GenerateArtifactsError::UnableToCreateDirectory {
-                path: absolute_directory.clone(),
-                message: e,
-            }
-        })?;
-
         let absolute_file_path =
             absolute_directory.join(&format!("{}.ts", path_and_content.file_name_prefix));
-        let mut file = File::create(&absolute_file_path).map_err(|e| {
-            GenerateArtifactsError::UnableToWriteToArtifactFile {
-                path: absolute_file_path.clone(),
+        let artifact_hash = content_hash(&path_and_content.file_content);
+
+        let is_unchanged = match &previous_artifact_hashes_taken {
+            // The file may have been deleted since the previous compilation.
+            Some(previous_artifact_hashes) => {
+                previous_artifact_hashes.get(&absolute_file_path) == Some(&artifact_hash)
+                    && absolute_file_path.is_file()
+            }
+            None => artifact_content_matches(&absolute_file_path, &path_and_content.file_content)?,
+        };
+        if is_unchanged {
+            counts.unchanged += 1;
+        } else {
+            fs::create_dir_all(&absolute_directory).map_err(|e| {
+                GenerateArtifactsError::UnableToCreateDirectory {
+                    path: absolute_directory.clone(),
+                    message: e,
+                }
+            })?;
+            write_atomically(&absolute_file_path, &path_and_content.file_content)?;
+            counts.written += 1;
+        }
+
+        artifact_hashes.insert(absolute_file_path, artifact_hash);
+    }
+
+    let mut stale_paths = vec![];
+    match &previous_artifact_hashes_taken {
+        Some(previous_artifact_hashes) => {
+            for path in previous_artifact_hashes.keys() {
+                if !artifact_hashes.contains_key(path) && path.is_file() {
+                    stale_paths.push(path.clone());
+                }
+            }
+        }
+        None => visit_artifact_files(artifact_directory, &mut |dir_entry| {
+            let path = dir_entry.path();
+            if !artifact_hashes.contains_key(&path) {
+                stale_paths.push(path);
+            }
+        })
+        .map_err(|e| GenerateArtifactsError::UnableToReadArtifactFile {
+            path: artifact_directory.clone(),
+            message: e,
+        })?,
+    }
+
+    for stale_path in stale_paths {
+        fs::remove_file(&stale_path).map_err(|e| {
+            GenerateArtifactsError::UnableToDeleteArtifactFile {
+                path: stale_path.clone(),
                 message: e,
             }
         })?;
+        counts.deleted += 1;
+    }
+    if previous_artifact_hashes_taken.is_none() || counts.deleted > 0 {
+        remove_empty_directories(artifact_directory)?;
+    }
+
+    *previous_artifact_hashes = Some(artifact_hashes);
+    Ok(counts)
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Whether a file exists at this path and has exactly this content.
+pub(crate) fn artifact_content_matches(
+    absolute_file_path: &PathBuf,
+    file_content: &str,
+) -> Result<bool, GenerateArtifactsError> {
+    if !absolute_file_path.is_file() {
+        return Ok(false);
+    }
+    let existing_content = fs::read(&absolute_file_path).map_err(|e| {
+        GenerateArtifactsError::UnableToReadArtifactFile {
+            path: absolute_file_path.clone(),
+            message: e,
+        }
+    })?;
+    Ok(existing_content == file_content.as_bytes())
+}
+
+fn write_atomically(
+    absolute_file_path: &PathBuf,
+    file_content: &str,
+) -> Result<(), GenerateArtifactsError> {
+    let mut temporary_file_name = absolute_file_path
+        .file_name()
+        .expect("Expected artifact path to have a file name")
+        .to_os_string();
+    temporary_file_name.push(".tmp");
+    let temporary_file_path = absolute_file_path.with_file_name(temporary_file_name);
 
-        file.write(path_and_content.file_content.as_bytes())
-            .map_err(|e| GenerateArtifactsError::UnableToWriteToArtifactFile {
-                path: absolute_file_path.clone(),
+    let result = File::create(&temporary_file_path)
+        .and_then(|mut file| file.write_all(file_content.as_bytes()))
+        .map_err(|e| GenerateArtifactsError::UnableToWriteToArtifactFile {
+            path: temporary_file_path.clone(),
+            message: e,
+        })
+        .and_then(|()| {
+            fs::rename(&temporary_file_path, absolute_file_path).map_err(|e| {
+                GenerateArtifactsError::UnableToWriteToArtifactFile {
+                    path: absolute_file_path.clone(),
+                    message: e,
+                }
+            })
+        });
+    if result.is_err() {
+        // Once we compare against the previous compilation's artifacts, the temporary
+        // file would not be deleted as a stale artifact, so we remove it here. The
+        // original error is more useful than an error from removing the file.
+        let _ = fs::remove_file(&temporary_file_path);
+    }
+    result
+}
+
+fn remove_empty_directories(dir: &Path) -> Result<(), GenerateArtifactsError> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| GenerateArtifactsError::UnableToReadArtifactFile {
+            path: dir.to_path_buf(),
+            message: e,
+        })?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| GenerateArtifactsError::UnableToReadArtifactFile {
+                path: dir.to_path_buf(),
                 message: e,
-            })?;
+            })?
+            .path();
+        if path.is_dir() {
+            remove_empty_directories(&path)?;
+            let is_empty = fs::read_dir(&path)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false);
+            if is_empty {
+                fs::remove_dir(&path).map_err(|e| {
+                    GenerateArtifactsError::UnableToDeleteDirectory {
+                        path: path.clone(),
+                        message: e,
+                    }
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn visit_artifact_files(dir: &Path, cb: &mut dyn FnMut(&DirEntry)) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.path().is_dir() {
+            visit_artifact_files(&entry.path(), cb)?;
+        } else {
+            cb(&entry);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use intern::string_key::Intern;
+    use tempfile::TempDir;
+
+    use super::{write_to_disk, ArtifactHashes, ArtifactWriteCounts};
+    use crate::generate_artifacts::PathAndContent;
+
+    /// The artifacts, as pairs of a path relative to the artifact directory (without
+    /// the .ts extension) and content.
+    fn write(
+        artifact_directory: &PathBuf,
+        artifacts: &[(&str, &str)],
+        previous_artifact_hashes: &mut Option<ArtifactHashes>,
+    ) -> ArtifactWriteCounts {
+        let paths_and_contents = artifacts.iter().map(|(path, content)| {
+            let path = PathBuf::from(path);
+            PathAndContent {
+                relative_directory: path
+                    .parent()
+                    .expect("Expected artifact path to have a parent")
+                    .to_path_buf(),
+                file_name_prefix: path
+                    .file_name()
+                    .expect("Expected artifact path to have a file name")
+                    .to_str()
+                    .expect("Expected artifact path to be valid UTF-8")
+                    .intern()
+                    .into(),
+                file_content: content.to_string(),
+            }
+        });
+        write_to_disk(
+            paths_and_contents,
+            artifact_directory,
+            previous_artifact_hashes,
+        )
+        .expect("Expected artifacts to be written")
+    }
+
+    fn artifact_directory() -> (TempDir, PathBuf) {
+        let temp_dir = tempfile::tempdir().expect("Expected temp dir to be created");
+        let artifact_directory = temp_dir.path().join("__isograph");
+        (temp_dir, artifact_directory)
+    }
+
+    fn counts(counts: ArtifactWriteCounts) -> (usize, usize, usize) {
+        (counts.written, counts.unchanged, counts.deleted)
+    }
+
+    fn read(artifact_directory: &PathBuf, path: &str) -> Option<String> {
+        fs::read_to_string(artifact_directory.join(path)).ok()
+    }
+
+    #[test]
+    fn writes_changed_artifacts_and_deletes_stale_artifacts() {
+        let (_temp_dir, artifact_directory) = artifact_directory();
+        let mut artifact_hashes = None;
+
+        assert_eq!(
+            counts(write(
+                &artifact_directory,
+                &[("Query/Foo/reader", "foo"), ("Query/Bar/reader", "bar")],
+                &mut artifact_hashes,
+            )),
+            (2, 0, 0)
+        );
+        assert!(artifact_hashes.is_some());
+
+        assert_eq!(
+            counts(write(
+                &artifact_directory,
+                &[("Query/Foo/reader", "foo 2")],
+                &mut artifact_hashes,
+            )),
+            (1, 0, 1)
+        );
+        assert_eq!(
+            read(&artifact_directory, "Query/Foo/reader.ts").as_deref(),
+            Some("foo 2")
+        );
+        // The directory of the stale artifact is removed, too.
+        assert!(!artifact_directory.join("Query/Bar").exists());
+
+        assert_eq!(
+            counts(write(
+                &artifact_directory,
+                &[("Query/Foo/reader", "foo 2")],
+                &mut artifact_hashes,
+            )),
+            (0, 1, 0)
+        );
+    }
+
+    #[test]
+    fn first_compilation_compares_against_the_files_on_disk() {
+        let (_temp_dir, artifact_directory) = artifact_directory();
+        fs::create_dir_all(artifact_directory.join("Query/Foo")).unwrap();
+        fs::create_dir_all(artifact_directory.join("Query/Stale")).unwrap();
+        fs::write(artifact_directory.join("Query/Foo/reader.ts"), "foo").unwrap();
+        fs::write(artifact_directory.join("Query/Stale/reader.ts"), "stale").unwrap();
+
+        assert_eq!(
+            counts(write(
+                &artifact_directory,
+                &[("Query/Foo/reader", "foo"), ("Query/Bar/reader", "bar")],
+                &mut None,
+            )),
+            (1, 1, 1)
+        );
+        assert!(!artifact_directory.join("Query/Stale").exists());
+    }
+
+    #[test]
+    fn artifacts_deleted_on_disk_are_rewritten() {
+        let (_temp_dir, artifact_directory) = artifact_directory();
+        let mut artifact_hashes = None;
+        write(
+            &artifact_directory,
+            &[("Query/Foo/reader", "foo")],
+            &mut artifact_hashes,
+        );
+
+        fs::remove_file(artifact_directory.join("Query/Foo/reader.ts")).unwrap();
+        assert_eq!(
+            counts(write(
+                &artifact_directory,
+                &[("Query/Foo/reader", "foo")],
+                &mut artifact_hashes,
+            )),
+            (1, 0, 0)
+        );
+        assert_eq!(
+            read(&artifact_directory, "Query/Foo/reader.ts").as_deref(),
+            Some("foo")
+        );
+    }
+
+    #[test]
+    fn temporary_file_is_removed_if_writing_fails() {
+        let (_temp_dir, artifact_directory) = artifact_directory();
+        // A file cannot be renamed to the path of a non-empty directory.
+        fs::create_dir_all(artifact_directory.join("Query/Foo/reader.ts/nested")).unwrap();
+
+        let mut artifact_hashes = Some(ArtifactHashes::new());
+        let paths_and_contents = std::iter::once(PathAndContent {
+            relative_directory: PathBuf::from("Query/Foo"),
+            file_name_prefix: "reader".intern().into(),
+            file_content: "foo".to_string(),
+        });
+        assert!(write_to_disk(
+            paths_and_contents,
+            &artifact_directory,
+            &mut artifact_hashes
+        )
+        .is_err());
+        assert!(!artifact_directory.join("Query/Foo/reader.ts.tmp").exists());
+        // The next compilation compares against the files on disk.
+        assert!(artifact_hashes.is_none());
     }
-    Ok(count)
 }
@@ -1,15 +1,76 @@
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+};
 
+use common_lang_types::{Diagnostic, ErrorCode, Severity, WithLocation};
 use serde::Deserialize;
 
+use crate::{
+    project_files::{default_exclude, default_file_extensions},
+    ConfigError, ProjectFiles,
+};
+
 pub static ISOGRAPH_FOLDER: &'static str = "__isograph";
+pub static CONFIG_FILE_NAME: &'static str = "isograph.config.json";
+static PACKAGE_JSON_FILE_NAME: &'static str = "package.json";
+static PACKAGE_JSON_KEY: &'static str = "isograph";
+static PROJECTS_KEY: &'static str = "projects";
+
+#[derive(Debug)]
+pub struct IsographConfig {
+    /// The isograph.config.json or package.json file from which the config was loaded
+    pub config_location: PathBuf,
+    /// The projects that should be compiled, ordered by name. A config without a
+    /// `projects` map contains a single, unnamed project.
+    pub projects: Vec<CompilerConfig>,
+}
+
+impl IsographConfig {
+    /// Keep only the project named project_name.
+    pub fn select_project(&mut self, project_name: &str) -> Result<(), ConfigError> {
+        if !self
+            .projects
+            .iter()
+            .any(|project| project.project_name.as_deref() == Some(project_name))
+        {
+            return Err(ConfigError::UnknownProject {
+                path: self.config_location.clone(),
+                project_name: project_name.to_string(),
+                available_projects: self.project_names(),
+            });
+        }
+        self.projects
+            .retain(|project| project.project_name.as_deref() == Some(project_name));
+        Ok(())
+    }
 
-use std::error::Error;
+    /// For consumers that can only handle a single project, e.g. the language server.
+    pub fn into_single_project(mut self) -> Result<CompilerConfig, ConfigError> {
+        if self.projects.len() == 1 {
+            Ok(self.projects.remove(0))
+        } else {
+            Err(ConfigError::ProjectNotSpecified {
+                available_projects: self.project_names(),
+                path: self.config_location,
+            })
+        }
+    }
 
-use colorize::AnsiColor;
+    fn project_names(&self) -> Vec<String> {
+        self.projects
+            .iter()
+            .filter_map(|project| project.project_name.clone())
+            .collect()
+    }
+}
 
 #[derive(Debug)]
 pub struct CompilerConfig {
+    /// The key of this project in the `projects` map, or None if the config file
+    /// does not have a `projects` map
+    pub project_name: Option<String>,
     /// The folder where the compiler should look for Isograph literals
     pub project_root: PathBuf,
     /// The folder where the compiler should create artifacts
@@ -18,14 +79,43 @@ pub struct CompilerConfig {
     pub schema: PathBuf,
     /// The absolute path to the schema extensions
     pub schema_extensions: Vec<PathBuf>,
+    /// Which files within the project root are searched for iso literals
+    pub project_files: ProjectFiles,
 
     /// Various options that are of lesser importance
     pub options: ConfigOptions,
 }
 
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone)]
 pub struct ConfigOptions {
     pub on_invalid_id_type: OptionalValidationLevel,
+    /// Keyed by the name of the GraphQL scalar
+    pub custom_scalar_types: HashMap<String, CustomScalarType>,
+    /// What to do if a scalar defined in the schema has no custom scalar type. Such
+    /// scalars are typed as string.
+    pub on_unknown_scalar_type: OptionalValidationLevel,
+    /// What to do if an iso literal selects a field that is marked @deprecated.
+    pub on_deprecated_field_selected: OptionalValidationLevel,
+    /// What to do if an iso literal unwraps a field with `!`, but the field is
+    /// already non-null at that level.
+    pub on_unnecessary_unwrap: OptionalValidationLevel,
+}
+
+/// The TypeScript type of a scalar defined in the schema.
+#[derive(Debug, Clone)]
+pub struct CustomScalarType {
+    /// A TypeScript type expression, e.g. `number` or `Decimal`. If there is an
+    /// import path, this is the name of the type that is imported.
+    pub javascript_type: String,
+    pub import_path: Option<CustomScalarTypeImportPath>,
+}
+
+#[derive(Debug, Clone)]
+pub enum CustomScalarTypeImportPath {
+    /// A module that is imported as is, e.g. `decimal.js`
+    Module(String),
+    /// The absolute path to a file, which is imported relative to the artifact
+    File(PathBuf),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -39,17 +129,20 @@ pub enum OptionalValidationLevel {
 }
 
 impl OptionalValidationLevel {
-    pub fn on_failure<E>(self, on_error: impl FnOnce() -> E) -> Result<(), E>
+    /// If this is Warn, the error is added to warnings, which the caller returns
+    /// alongside its result, and which the CLI decides how to print.
+    pub fn on_failure<E>(
+        self,
+        warnings: &mut Vec<Diagnostic>,
+        on_error: impl FnOnce() -> WithLocation<E>,
+    ) -> Result<(), WithLocation<E>>
     where
-        E: Error,
+        E: ErrorCode + fmt::Display,
     {
         match self {
             OptionalValidationLevel::Ignore => Ok(()),
             OptionalValidationLevel::Warn => {
-                let warning = on_error();
-                // TODO pass to some sort of warning gatherer, this is weird!
-                // The fact that we know about colorize here is weird!
-                eprintln!("{}\n{}\n", "Warning:".yellow(), warning);
+                warnings.push(on_error().to_diagnostic(Severity::Warning));
                 Ok(())
             }
             OptionalValidationLevel::Error => Err(on_error()),
@@ -63,9 +156,23 @@ impl Default for OptionalValidationLevel {
     }
 }
 
+enum ConfigFile {
+    SingleProject(ProjectConfigFile),
+    MultiProject(MultiProjectConfigFile),
+}
+
 #[derive(Deserialize)]
 #[serde(deny_unknown_fields)]
-struct ConfigFile {
+struct MultiProjectConfigFile {
+    /// The projects, keyed by name. Each project is configured like a config file
+    /// without a `projects` map, and its relative paths are resolved against the
+    /// directory containing the config file.
+    pub projects: BTreeMap<String, ProjectConfigFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProjectConfigFile {
     /// The relative path to the folder where the compiler should look for Isograph literals
     pub project_root: PathBuf,
     /// The relative path to the folder where the compiler should create artifacts
@@ -76,31 +183,94 @@ struct ConfigFile {
     /// The relative path to schema extensions
     #[serde(default)]
     pub schema_extensions: Vec<PathBuf>,
+    /// Globs, relative to the project root, of the files that should be searched
+    /// for iso literals. Defaults to all files.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Globs, relative to the project root, of the files and directories that
+    /// should not be searched for iso literals. Defaults to node_modules folders.
+    #[serde(default = "default_exclude")]
+    pub exclude: Vec<String>,
+    /// The extensions of the files that should be searched for iso literals.
+    /// Defaults to ts, tsx, js and jsx.
+    #[serde(default = "default_file_extensions")]
+    pub file_extensions: Vec<String>,
 
     /// Various that are of lesser importance
     #[serde(default = "Default::default")]
     pub options: ConfigFileOptions,
 }
 
-pub fn create_config(mut config_location: PathBuf) -> CompilerConfig {
-    let config_contents = match std::fs::read_to_string(&config_location) {
-        Ok(contents) => contents,
-        Err(_) => match config_location.to_str() {
-            Some(loc) => {
-                panic!("Expected config to be found at {}", loc)
-            }
-            None => {
-                panic!("Expected config to be found.")
+/// Load the config at config_location, which can be an isograph.config.json file,
+/// or a package.json file with an `isograph` key. If no location is provided, walk
+/// up from the current directory until such a file is found.
+pub fn create_config(config_location: Option<PathBuf>) -> Result<IsographConfig, ConfigError> {
+    let (config_location, config_parsed) = match config_location {
+        Some(config_location) => {
+            let config_parsed = if is_package_json(&config_location) {
+                parse_package_json(&config_location)?.ok_or_else(|| {
+                    ConfigError::PackageJsonHasNoIsographKey {
+                        path: config_location.clone(),
+                    }
+                })?
+            } else {
+                parse_config_file(&config_location)?
+            };
+            (config_location, config_parsed)
+        }
+        None => find_config()?,
+    };
+
+    let config_dir = config_location
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+
+    let projects = match config_parsed {
+        ConfigFile::SingleProject(project_config_parsed) => {
+            vec![create_project_config(
+                &config_dir,
+                None,
+                project_config_parsed,
+            )?]
+        }
+        ConfigFile::MultiProject(multi_project_config_parsed) => {
+            if multi_project_config_parsed.projects.is_empty() {
+                return Err(ConfigError::NoProjects {
+                    path: config_location,
+                });
             }
-        },
+            multi_project_config_parsed
+                .projects
+                .into_iter()
+                .map(|(project_name, project_config_parsed)| {
+                    create_project_config(
+                        &config_dir,
+                        Some(project_name.clone()),
+                        project_config_parsed,
+                    )
+                    .map_err(|error| ConfigError::InvalidProject {
+                        project_name,
+                        error: Box::new(error),
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
     };
 
-    let config_parsed: ConfigFile = serde_json::from_str(&config_contents)
-        .unwrap_or_else(|e| panic!("Error parsing config. Error: {}", e));
+    validate_artifact_directories_are_distinct(&projects)?;
 
-    config_location.pop();
-    let config_dir = config_location;
+    Ok(IsographConfig {
+        config_location,
+        projects,
+    })
+}
 
+fn create_project_config(
+    config_dir: &Path,
+    project_name: Option<String>,
+    config_parsed: ProjectConfigFile,
+) -> Result<CompilerConfig, ConfigError> {
     let artifact_dir = config_dir
         .join(
             config_parsed
@@ -109,48 +279,219 @@ pub fn create_config(mut config_location: PathBuf) -> CompilerConfig {
                 .unwrap_or(&config_parsed.project_root),
         )
         .join(&*ISOGRAPH_FOLDER);
-    std::fs::create_dir_all(&artifact_dir).expect("Unable to create artifact directory");
+    create_dir_all("artifact_directory", &artifact_dir)?;
 
     let project_root_dir = config_dir.join(&config_parsed.project_root);
-    std::fs::create_dir_all(&project_root_dir).expect("Unable to create project root directory");
-
-    CompilerConfig {
-        project_root: project_root_dir.canonicalize().expect(&format!(
-            "Unable to canonicalize project root at {:?}.",
-            config_parsed.project_root
-        )),
-        artifact_directory: artifact_dir.canonicalize().expect(&format!(
-            "Unable to canonicalize artifact directory at {:?}.",
-            config_parsed.artifact_directory
-        )),
-        schema: config_dir
-            .join(&config_parsed.schema)
-            .canonicalize()
-            .expect(&format!(
-                "Unable to canonicalize schema path. Does {:?} exist?",
-                config_parsed.schema
-            )),
+    create_dir_all("project_root", &project_root_dir)?;
+
+    Ok(CompilerConfig {
+        project_name,
+        project_root: canonicalize("project_root", &project_root_dir)?,
+        artifact_directory: canonicalize("artifact_directory", &artifact_dir)?,
+        schema: canonicalize("schema", &config_dir.join(&config_parsed.schema))?,
         schema_extensions: config_parsed
             .schema_extensions
-            .into_iter()
+            .iter()
             .map(|schema_extension| {
-                config_dir
-                    .join(&schema_extension)
-                    .canonicalize()
-                    .expect(&format!(
-                        "Unable to canonicalize schema extension path. Does {:?} exist?",
-                        schema_extension
-                    ))
+                canonicalize("schema_extensions", &config_dir.join(schema_extension))
             })
-            .collect(),
-        options: create_options(config_parsed.options),
+            .collect::<Result<_, _>>()?,
+        project_files: ProjectFiles::new(
+            &config_parsed.include,
+            &config_parsed.exclude,
+            &config_parsed.file_extensions,
+        )?,
+        options: create_options(config_dir, config_parsed.options),
+    })
+}
+
+/// Each project deletes the stale artifacts in its artifact directory, so projects
+/// that shared an artifact directory would delete each other's artifacts.
+fn validate_artifact_directories_are_distinct(
+    projects: &[CompilerConfig],
+) -> Result<(), ConfigError> {
+    for (index, project) in projects.iter().enumerate() {
+        if let Some(other_project) = projects[index + 1..]
+            .iter()
+            .find(|other_project| other_project.artifact_directory == project.artifact_directory)
+        {
+            return Err(ConfigError::SharedArtifactDirectory {
+                path: project.artifact_directory.clone(),
+                first_project_name: project.project_name.clone().unwrap_or_default(),
+                second_project_name: other_project.project_name.clone().unwrap_or_default(),
+            });
+        }
     }
+    Ok(())
 }
 
-#[derive(Deserialize, Default)]
+/// Walk up from the current directory, and return the first isograph.config.json
+/// file, or package.json file with an `isograph` key. In a given directory,
+/// isograph.config.json takes precedence.
+fn find_config() -> Result<(PathBuf, ConfigFile), ConfigError> {
+    let current_dir = std::env::current_dir()
+        .map_err(|message| ConfigError::UnableToReadCurrentDirectory { message })?;
+
+    for directory in current_dir.ancestors() {
+        let config_file_path = directory.join(CONFIG_FILE_NAME);
+        if config_file_path.is_file() {
+            let config_parsed = parse_config_file(&config_file_path)?;
+            return Ok((config_file_path, config_parsed));
+        }
+
+        let package_json_path = directory.join(PACKAGE_JSON_FILE_NAME);
+        if package_json_path.is_file() {
+            if let Some(config_parsed) = parse_package_json(&package_json_path)? {
+                return Ok((package_json_path, config_parsed));
+            }
+        }
+    }
+
+    Err(ConfigError::ConfigNotFound {
+        searched_from: current_dir,
+    })
+}
+
+fn is_package_json(path: &Path) -> bool {
+    path.file_name()
+        .map(|file_name| file_name == PACKAGE_JSON_FILE_NAME)
+        .unwrap_or(false)
+}
+
+fn parse_config_file(path: &Path) -> Result<ConfigFile, ConfigError> {
+    let contents = read_config(path)?;
+    let config: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|message| ConfigError::InvalidJson {
+            path: path.to_path_buf(),
+            message,
+        })?;
+    deserialize_config(path, None, config)
+}
+
+/// Returns None if the package.json file has no `isograph` key.
+fn parse_package_json(path: &Path) -> Result<Option<ConfigFile>, ConfigError> {
+    let contents = read_config(path)?;
+    let package_json: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|message| ConfigError::InvalidJson {
+            path: path.to_path_buf(),
+            message,
+        })?;
+
+    match package_json.get(PACKAGE_JSON_KEY) {
+        Some(config) => deserialize_config(path, Some(PACKAGE_JSON_KEY), config.clone()).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// A config with a `projects` key is a multi-project config. Otherwise, the config
+/// itself describes a single project.
+fn deserialize_config(
+    path: &Path,
+    key_prefix: Option<&str>,
+    config: serde_json::Value,
+) -> Result<ConfigFile, ConfigError> {
+    if config.get(PROJECTS_KEY).is_some() {
+        serde_path_to_error::deserialize(config)
+            .map(ConfigFile::MultiProject)
+            .map_err(|error| invalid_config_error(path, key_prefix, error))
+    } else {
+        serde_path_to_error::deserialize(config)
+            .map(ConfigFile::SingleProject)
+            .map_err(|error| invalid_config_error(path, key_prefix, error))
+    }
+}
+
+fn read_config(path: &Path) -> Result<String, ConfigError> {
+    std::fs::read_to_string(path).map_err(|message| ConfigError::UnableToReadConfig {
+        path: path.to_path_buf(),
+        message,
+    })
+}
+
+fn invalid_config_error(
+    path: &Path,
+    key_prefix: Option<&str>,
+    error: serde_path_to_error::Error<serde_json::Error>,
+) -> ConfigError {
+    let key = error
+        .path()
+        .iter()
+        .map(|segment| segment.to_string())
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let key = key_prefix
+        .into_iter()
+        .map(str::to_string)
+        .chain(key)
+        .collect::<Vec<_>>()
+        .join(".");
+    let path = path.to_path_buf();
+    let message = error.into_inner();
+
+    if key.is_empty() {
+        ConfigError::InvalidConfig { path, message }
+    } else {
+        ConfigError::InvalidConfigKey { path, key, message }
+    }
+}
+
+fn create_dir_all(key: &'static str, path: &Path) -> Result<(), ConfigError> {
+    std::fs::create_dir_all(path).map_err(|message| ConfigError::UnableToCreateDirectory {
+        key,
+        path: path.to_path_buf(),
+        message,
+    })
+}
+
+fn canonicalize(key: &'static str, path: &Path) -> Result<PathBuf, ConfigError> {
+    path.canonicalize()
+        .map_err(|message| ConfigError::UnableToCanonicalizePath {
+            key,
+            path: path.to_path_buf(),
+            message,
+        })
+}
+
+#[derive(Deserialize)]
 #[serde(default, deny_unknown_fields)]
 struct ConfigFileOptions {
     on_invalid_id_type: ConfigFileOptionalValidationLevel,
+    custom_scalar_types: HashMap<String, ConfigFileCustomScalarType>,
+    on_unknown_scalar_type: ConfigFileOptionalValidationLevel,
+    on_deprecated_field_selected: ConfigFileOptionalValidationLevel,
+    on_unnecessary_unwrap: ConfigFileOptionalValidationLevel,
+}
+
+impl Default for ConfigFileOptions {
+    fn default() -> Self {
+        Self {
+            on_invalid_id_type: ConfigFileOptionalValidationLevel::Error,
+            custom_scalar_types: HashMap::new(),
+            on_unknown_scalar_type: ConfigFileOptionalValidationLevel::Warn,
+            on_deprecated_field_selected: ConfigFileOptionalValidationLevel::Warn,
+            on_unnecessary_unwrap: ConfigFileOptionalValidationLevel::Warn,
+        }
+    }
+}
+
+/// Either a TypeScript type expression, e.g. `"number"`, or an object with a type
+/// and the path from which it is imported, e.g.
+/// `{ "type": "Decimal", "import_path": "decimal.js" }`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ConfigFileCustomScalarType {
+    JavascriptType(String),
+    WithImportPath(ConfigFileCustomScalarTypeWithImportPath),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFileCustomScalarTypeWithImportPath {
+    #[serde(rename = "type")]
+    javascript_type: String,
+    /// A module, e.g. `decimal.js`, or a path relative to the config file, which
+    /// must start with a period, e.g. `./src/scalars`
+    import_path: Option<String>,
 }
 
 #[derive(Deserialize, Debug, Clone, Copy)]
@@ -170,9 +511,46 @@ impl Default for ConfigFileOptionalValidationLevel {
     }
 }
 
-fn create_options(options: ConfigFileOptions) -> ConfigOptions {
+fn create_options(config_dir: &Path, options: ConfigFileOptions) -> ConfigOptions {
     ConfigOptions {
         on_invalid_id_type: create_optional_validation_level(options.on_invalid_id_type),
+        custom_scalar_types: options
+            .custom_scalar_types
+            .into_iter()
+            .map(|(scalar_name, custom_scalar_type)| {
+                (
+                    scalar_name,
+                    create_custom_scalar_type(config_dir, custom_scalar_type),
+                )
+            })
+            .collect(),
+        on_unknown_scalar_type: create_optional_validation_level(options.on_unknown_scalar_type),
+        on_deprecated_field_selected: create_optional_validation_level(
+            options.on_deprecated_field_selected,
+        ),
+        on_unnecessary_unwrap: create_optional_validation_level(options.on_unnecessary_unwrap),
+    }
+}
+
+fn create_custom_scalar_type(
+    config_dir: &Path,
+    custom_scalar_type: ConfigFileCustomScalarType,
+) -> CustomScalarType {
+    match custom_scalar_type {
+        ConfigFileCustomScalarType::JavascriptType(javascript_type) => CustomScalarType {
+            javascript_type,
+            import_path: None,
+        },
+        ConfigFileCustomScalarType::WithImportPath(custom_scalar_type) => CustomScalarType {
+            javascript_type: custom_scalar_type.javascript_type,
+            import_path: custom_scalar_type.import_path.map(|import_path| {
+                if import_path.starts_with('.') {
+                    CustomScalarTypeImportPath::File(config_dir.join(import_path))
+                } else {
+                    CustomScalarTypeImportPath::Module(import_path)
+                }
+            }),
+        },
     }
 }
 
@@ -0,0 +1,135 @@
+use std::{io, path::PathBuf};
+
+use common_lang_types::ErrorCode;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error(
+        "Unable to find a config. Searched {searched_from:?} and its parent directories \
+        for an isograph.config.json file, or a package.json file with an `isograph` key."
+    )]
+    ConfigNotFound { searched_from: PathBuf },
+
+    #[error("Unable to determine the current directory.\nReason: {message}")]
+    UnableToReadCurrentDirectory { message: io::Error },
+
+    #[error("Unable to read config at path {path:?}.\nReason: {message}")]
+    UnableToReadConfig { path: PathBuf, message: io::Error },
+
+    #[error("Unable to parse {path:?} as JSON.\nReason: {message}")]
+    InvalidJson {
+        path: PathBuf,
+        message: serde_json::Error,
+    },
+
+    #[error("{path:?} does not contain an `isograph` key.")]
+    PackageJsonHasNoIsographKey { path: PathBuf },
+
+    #[error("Invalid config at {path:?}.\nReason: {message}")]
+    InvalidConfig {
+        path: PathBuf,
+        message: serde_json::Error,
+    },
+
+    #[error("Invalid value for the key `{key}` in the config at {path:?}.\nReason: {message}")]
+    InvalidConfigKey {
+        path: PathBuf,
+        /// The path to the offending key, e.g. `options.on_invalid_id_type`
+        key: String,
+        message: serde_json::Error,
+    },
+
+    #[error("Invalid glob {glob:?} for the key `{key}` in the config.\nReason: {message}")]
+    InvalidGlob {
+        key: &'static str,
+        glob: String,
+        message: globset::Error,
+    },
+
+    #[error("The config at {path:?} has an empty `projects` map.")]
+    NoProjects { path: PathBuf },
+
+    #[error("Invalid config for the project `{project_name}`.\n{error}")]
+    InvalidProject {
+        project_name: String,
+        error: Box<ConfigError>,
+    },
+
+    #[error(
+        "The projects `{first_project_name}` and `{second_project_name}` have the same \
+        artifact directory, {path:?}. Each project must have its own artifact directory."
+    )]
+    SharedArtifactDirectory {
+        path: PathBuf,
+        first_project_name: String,
+        second_project_name: String,
+    },
+
+    #[error(
+        "The config at {path:?} does not contain a project named `{project_name}`.{}",
+        available_projects_message(.available_projects)
+    )]
+    UnknownProject {
+        path: PathBuf,
+        project_name: String,
+        available_projects: Vec<String>,
+    },
+
+    #[error(
+        "The config at {path:?} contains multiple projects. Select one with --project.{}",
+        available_projects_message(.available_projects)
+    )]
+    ProjectNotSpecified {
+        path: PathBuf,
+        available_projects: Vec<String>,
+    },
+
+    #[error("Unable to create the directory {path:?} for the key `{key}` in the config.\nReason: {message}")]
+    UnableToCreateDirectory {
+        key: &'static str,
+        path: PathBuf,
+        message: io::Error,
+    },
+
+    #[error("Unable to find {path:?}, for the key `{key}` in the config.\nReason: {message}")]
+    UnableToCanonicalizePath {
+        key: &'static str,
+        path: PathBuf,
+        message: io::Error,
+    },
+}
+
+impl ErrorCode for ConfigError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            ConfigError::ConfigNotFound { .. } => "config/config_not_found",
+            ConfigError::UnableToReadCurrentDirectory { .. } => {
+                "config/unable_to_read_current_directory"
+            }
+            ConfigError::UnableToReadConfig { .. } => "config/unable_to_read_config",
+            ConfigError::InvalidJson { .. } => "config/invalid_json",
+            ConfigError::PackageJsonHasNoIsographKey { .. } => {
+                "config/package_json_has_no_isograph_key"
+            }
+            ConfigError::InvalidConfig { .. } => "config/invalid_config",
+            ConfigError::InvalidConfigKey { .. } => "config/invalid_config_key",
+            ConfigError::InvalidGlob { .. } => "config/invalid_glob",
+            ConfigError::NoProjects { .. } => "config/no_projects",
+            ConfigError::InvalidProject { error, .. } => error.error_code(),
+            ConfigError::SharedArtifactDirectory { .. } => "config/shared_artifact_directory",
+            ConfigError::UnknownProject { .. } => "config/unknown_project",
+            ConfigError::ProjectNotSpecified { .. } => "config/project_not_specified",
+            ConfigError::UnableToCreateDirectory { .. } => "config/unable_to_create_directory",
+            ConfigError::UnableToCanonicalizePath { .. } => "config/unable_to_canonicalize_path",
+        }
+    }
+}
+
+fn available_projects_message(available_projects: &[String]) -> String {
+    if available_projects.is_empty() {
+        " The config does not have a `projects` map.".to_string()
+    } else {
+        format!(" Available projects: {}.", available_projects.join(", "))
+    }
+}
@@ -1,3 +1,7 @@
 mod compilation_options;
+mod config_error;
+mod project_files;
 
 pub use compilation_options::*;
+pub use config_error::*;
+pub use project_files::*;
@@ -0,0 +1,189 @@
+use std::path::Path;
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+use crate::{ConfigError, ISOGRAPH_FOLDER};
+
+/// Which files within the project root are searched for iso literals. Paths
+/// passed to these methods are relative to the project root.
+///
+/// In addition, the compiler skips files that are ignored by .gitignore and
+/// .ignore files.
+#[derive(Debug, Clone)]
+pub struct ProjectFiles {
+    /// If None, all files are included.
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    /// Without the leading period, e.g. "ts"
+    file_extensions: Vec<String>,
+}
+
+impl ProjectFiles {
+    pub(crate) fn new(
+        include: &[String],
+        exclude: &[String],
+        file_extensions: &[String],
+    ) -> Result<Self, ConfigError> {
+        Ok(ProjectFiles {
+            include: if include.is_empty() {
+                None
+            } else {
+                Some(build_glob_set("include", include)?)
+            },
+            exclude: build_glob_set("exclude", exclude)?,
+            file_extensions: file_extensions
+                .iter()
+                .map(|file_extension| file_extension.trim_start_matches('.').to_string())
+                .collect(),
+        })
+    }
+
+    /// Whether the compiler should look for project files within this directory.
+    pub fn is_included_directory(&self, relative_path: &Path) -> bool {
+        !is_within_artifact_directory(relative_path) && !self.exclude.is_match(relative_path)
+    }
+
+    /// Whether this file should be searched for iso literals. Files within excluded
+    /// directories are themselves excluded.
+    pub fn is_included_file(&self, relative_path: &Path) -> bool {
+        self.has_valid_extension(relative_path)
+            && !is_within_artifact_directory(relative_path)
+            && self
+                .include
+                .as_ref()
+                .map(|include| include.is_match(relative_path))
+                .unwrap_or(true)
+            && !relative_path
+                .ancestors()
+                .filter(|ancestor| !ancestor.as_os_str().is_empty())
+                .any(|ancestor| self.exclude.is_match(ancestor))
+    }
+
+    fn has_valid_extension(&self, relative_path: &Path) -> bool {
+        match relative_path.extension().and_then(|x| x.to_str()) {
+            Some(extension) => self
+                .file_extensions
+                .iter()
+                .any(|file_extension| file_extension == extension),
+            None => false,
+        }
+    }
+}
+
+impl Default for ProjectFiles {
+    /// The project files of a config that does not specify include, exclude or
+    /// fileExtensions.
+    fn default() -> Self {
+        ProjectFiles::new(&[], &default_exclude(), &default_file_extensions())
+            .expect("Expected default globs to be valid")
+    }
+}
+
+pub(crate) fn default_exclude() -> Vec<String> {
+    vec!["**/node_modules".to_string()]
+}
+
+pub(crate) fn default_file_extensions() -> Vec<String> {
+    ["ts", "tsx", "js", "jsx"]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_within_artifact_directory(relative_path: &Path) -> bool {
+    relative_path
+        .components()
+        .any(|component| component.as_os_str() == ISOGRAPH_FOLDER)
+}
+
+fn build_glob_set(key: &'static str, globs: &[String]) -> Result<GlobSet, ConfigError> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        // A * does not match a path separator, whereas ** does.
+        let compiled_glob = GlobBuilder::new(glob)
+            .literal_separator(true)
+            .build()
+            .map_err(|message| ConfigError::InvalidGlob {
+                key,
+                glob: glob.clone(),
+                message,
+            })?;
+        builder.add(compiled_glob);
+    }
+    builder.build().map_err(|message| ConfigError::InvalidGlob {
+        key,
+        glob: globs.join(", "),
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::ProjectFiles;
+
+    fn project_files(include: &[&str], exclude: &[&str], file_extensions: &[&str]) -> ProjectFiles {
+        let to_strings = |items: &[&str]| {
+            items
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<_>>()
+        };
+        ProjectFiles::new(
+            &to_strings(include),
+            &to_strings(exclude),
+            &to_strings(file_extensions),
+        )
+        .expect("Expected globs to be valid")
+    }
+
+    #[test]
+    fn node_modules_is_excluded_by_default() {
+        let project_files = ProjectFiles::default();
+        assert!(!project_files.is_included_directory(Path::new("node_modules")));
+        assert!(!project_files.is_included_directory(Path::new("packages/app/node_modules")));
+        assert!(!project_files.is_included_file(Path::new("node_modules/library/index.ts")));
+        assert!(!project_files
+            .is_included_file(Path::new("packages/app/node_modules/library/index.tsx")));
+
+        assert!(project_files.is_included_directory(Path::new("src")));
+        assert!(project_files.is_included_file(Path::new("src/index.ts")));
+    }
+
+    #[test]
+    fn files_within_excluded_directories_are_excluded() {
+        let project_files = project_files(&[], &["**/__generated__"], &["ts"]);
+        assert!(!project_files.is_included_directory(Path::new("src/__generated__")));
+        assert!(!project_files.is_included_file(Path::new("src/__generated__/a.ts")));
+        assert!(!project_files.is_included_file(Path::new("src/__generated__/nested/a.ts")));
+        assert!(project_files.is_included_file(Path::new("src/a.ts")));
+        // Overriding exclude means that node_modules is no longer excluded.
+        assert!(project_files.is_included_file(Path::new("node_modules/library/a.ts")));
+    }
+
+    #[test]
+    fn artifact_directory_is_always_excluded() {
+        let project_files = project_files(&[], &[], &["ts"]);
+        assert!(!project_files.is_included_directory(Path::new("src/__isograph")));
+        assert!(!project_files.is_included_file(Path::new("src/__isograph/Query/a.ts")));
+    }
+
+    #[test]
+    fn files_must_match_include_and_have_a_valid_extension() {
+        let project_files = project_files(&["src/**"], &["**/node_modules"], &["ts", ".mts"]);
+        assert!(project_files.is_included_file(Path::new("src/a.ts")));
+        // The leading period of a file extension is optional.
+        assert!(project_files.is_included_file(Path::new("src/a.mts")));
+        assert!(!project_files.is_included_file(Path::new("src/a.tsx")));
+        assert!(!project_files.is_included_file(Path::new("src/a")));
+        assert!(!project_files.is_included_file(Path::new("scripts/a.ts")));
+    }
+
+    #[test]
+    fn mts_files_are_excluded_by_default() {
+        let project_files = ProjectFiles::default();
+        assert!(project_files.is_included_file(Path::new("src/a.tsx")));
+        assert!(!project_files.is_included_file(Path::new("src/a.mts")));
+    }
+}
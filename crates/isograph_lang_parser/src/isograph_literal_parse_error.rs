@@ -1,4 +1,4 @@
-use common_lang_types::{FieldNameOrAlias, ScalarFieldName, WithLocation, WithSpan};
+use common_lang_types::{ErrorCode, FieldNameOrAlias, ScalarFieldName, WithLocation, WithSpan};
 use thiserror::Error;
 
 use super::peekable_lexer::LowLevelParseError;
@@ -24,6 +24,9 @@ pub enum IsographLiteralParseError {
     #[error("Isograph literals must start with field or entrypoint")]
     ExpectedFieldOrEntrypoint,
 
+    #[error("Isograph literals must not be empty. They must start with field or entrypoint")]
+    EmptyIsoLiteral,
+
     #[error(
         "This isograph field literal must be exported as a named export, for example \
         as `export const {suggested_const_export_name}`"
@@ -35,8 +38,16 @@ pub enum IsographLiteralParseError {
     #[error("Expected a valid value, like $foo or 42")]
     ExpectedNonConstantValue,
 
-    #[error("Descriptions are currently disallowed")]
-    DescriptionsAreDisallowed,
+    #[error(
+        "Expected a constant value, like 42 or \"foo\". Variables are not yet \
+        supported within lists and objects"
+    )]
+    ExpectedConstantValue,
+
+    #[error(
+        "The integer `{integer}` is out of range. Integers must fit in a signed 64-bit integer"
+    )]
+    IntegerOutOfRange { integer: String },
 
     #[error("Expected a comma or linebreak")]
     ExpectedCommaOrLineBreak,
@@ -60,6 +71,51 @@ pub enum IsographLiteralParseError {
     DuplicateNameOrAlias { name_or_alias: FieldNameOrAlias },
 }
 
+impl ErrorCode for IsographLiteralParseError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            IsographLiteralParseError::ParseError { .. } => "isograph_literal_parse/parse_error",
+            IsographLiteralParseError::ExpectedTypeAnnotation => {
+                "isograph_literal_parse/expected_type_annotation"
+            }
+            IsographLiteralParseError::LeftoverTokens => "isograph_literal_parse/leftover_tokens",
+            IsographLiteralParseError::ExpectedAssociatedJsFunction => {
+                "isograph_literal_parse/expected_associated_js_function"
+            }
+            IsographLiteralParseError::ExpectedFieldOrEntrypoint => {
+                "isograph_literal_parse/expected_field_or_entrypoint"
+            }
+            IsographLiteralParseError::EmptyIsoLiteral => {
+                "isograph_literal_parse/empty_iso_literal"
+            }
+            IsographLiteralParseError::ExpectedLiteralToBeExported { .. } => {
+                "isograph_literal_parse/expected_literal_to_be_exported"
+            }
+            IsographLiteralParseError::ExpectedNonConstantValue => {
+                "isograph_literal_parse/expected_non_constant_value"
+            }
+            IsographLiteralParseError::ExpectedConstantValue => {
+                "isograph_literal_parse/expected_constant_value"
+            }
+            IsographLiteralParseError::IntegerOutOfRange { .. } => {
+                "isograph_literal_parse/integer_out_of_range"
+            }
+            IsographLiteralParseError::ExpectedCommaOrLineBreak => {
+                "isograph_literal_parse/expected_comma_or_line_break"
+            }
+            IsographLiteralParseError::ExpectedSelectionSet => {
+                "isograph_literal_parse/expected_selection_set"
+            }
+            IsographLiteralParseError::ExpectedParenthesesAroundIsoLiteral => {
+                "isograph_literal_parse/expected_parentheses_around_iso_literal"
+            }
+            IsographLiteralParseError::DuplicateNameOrAlias { .. } => {
+                "isograph_literal_parse/duplicate_name_or_alias"
+            }
+        }
+    }
+}
+
 impl From<LowLevelParseError> for IsographLiteralParseError {
     fn from(error: LowLevelParseError) -> Self {
         IsographLiteralParseError::ParseError { error }
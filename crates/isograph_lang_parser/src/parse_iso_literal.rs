@@ -5,13 +5,15 @@ use common_lang_types::{
     UnvalidatedTypeName, WithLocation, WithSpan,
 };
 use graphql_lang_types::{
-    ListTypeAnnotation, NamedTypeAnnotation, NonNullTypeAnnotation, TypeAnnotation,
+    unescape_string_literal, ConstantValue, ListTypeAnnotation, NameValuePair, NamedTypeAnnotation,
+    NonNullTypeAnnotation, TypeAnnotation,
 };
 use intern::string_key::{Intern, StringKey};
 use isograph_lang_types::{
     ClientFieldDeclaration, EntrypointTypeAndField, FragmentDirectiveUsage, LinkedFieldSelection,
     NonConstantValue, ScalarFieldSelection, Selection, SelectionFieldArgument,
-    ServerFieldSelection, UnvalidatedSelection, Unwrap, VariableDefinition,
+    ServerFieldSelection, TypeRefinementSelection, UnvalidatedSelection, Unwrap,
+    VariableDefinition,
 };
 
 use crate::{
@@ -19,6 +21,7 @@ use crate::{
     ParseResultWithLocation, ParseResultWithSpan, PeekableLexer,
 };
 
+#[derive(Debug, Clone)]
 pub enum IsoLiteralExtractionResult {
     ClientFieldDeclaration(WithSpan<ClientFieldDeclaration>),
     EntrypointDeclaration(WithSpan<EntrypointTypeAndField>),
@@ -120,7 +123,7 @@ fn parse_client_field_declaration_inner<'a>(
 ) -> ParseResultWithSpan<WithSpan<ClientFieldDeclaration>> {
     let client_field_declaration = tokens
         .with_span(|tokens| {
-            let _description = parse_optional_description(tokens)?;
+            let description = parse_optional_description(tokens);
             let parent_type = tokens
                 .parse_string_key_type(IsographLangTokenKind::Identifier)
                 .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
@@ -135,7 +138,7 @@ fn parse_client_field_declaration_inner<'a>(
 
             let variable_definitions = parse_variable_definitions(tokens, text_source)?;
 
-            let directives = parse_directives(tokens)?;
+            let directives = parse_directives(tokens, text_source)?;
 
             let selection_set_and_unwraps = parse_selection_set_and_unwraps(tokens, text_source)?;
 
@@ -155,6 +158,7 @@ fn parse_client_field_declaration_inner<'a>(
             // --------------------
 
             Ok(ClientFieldDeclaration {
+                description,
                 parent_type,
                 client_field_name,
                 selection_set_and_unwraps,
@@ -221,6 +225,7 @@ fn parse_optional_selection_set<'a>(
                     ));
                 }
             }
+            Selection::TypeRefinement(_) => {}
         }
         selections.push(selection);
     }
@@ -273,6 +278,13 @@ fn parse_selection<'a>(
 ) -> ParseResultWithSpan<WithSpan<UnvalidatedSelection>> {
     tokens
         .with_span(|tokens| {
+            if tokens
+                .parse_token_of_kind(IsographLangTokenKind::Spread)
+                .is_ok()
+            {
+                return parse_type_refinement(tokens, text_source);
+            }
+
             let (field_name, alias) = parse_optional_alias_and_field_name(tokens)?;
             let field_name = field_name.to_with_location(text_source);
             let alias = alias.map(|alias| alias.to_with_location(text_source));
@@ -280,6 +292,8 @@ fn parse_selection<'a>(
             // TODO distinguish field groups
             let arguments = parse_optional_arguments(tokens, text_source)?;
 
+            let directives = parse_directives(tokens, text_source)?;
+
             // If we encounter a selection set, we are parsing a linked field. Otherwise, a scalar field.
             let selection_set = parse_optional_selection_set(tokens, text_source)?;
 
@@ -303,6 +317,7 @@ fn parse_selection<'a>(
                                 &arguments,
                             ),
                         arguments,
+                        directives,
                     },
                 )),
                 None => Selection::ServerField(ServerFieldSelection::ScalarField(
@@ -318,6 +333,7 @@ fn parse_selection<'a>(
                                 &arguments,
                             ),
                         arguments,
+                        directives,
                     },
                 )),
             };
@@ -326,6 +342,37 @@ fn parse_selection<'a>(
         .transpose()
 }
 
+/// Parse the remainder of a type refinement, e.g. `on User { name }`, after
+/// the spread has been consumed.
+fn parse_type_refinement(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResultWithSpan<UnvalidatedSelection> {
+    tokens
+        .parse_matching_identifier("on")
+        .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+    let type_to_refine_to = tokens
+        .parse_string_key_type::<UnvalidatedTypeName>(IsographLangTokenKind::Identifier)
+        .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?
+        .to_with_location(text_source);
+
+    let selection_set = parse_optional_selection_set(tokens, text_source)?.ok_or_else(|| {
+        WithSpan::new(
+            IsographLiteralParseError::ExpectedSelectionSet,
+            tokens.peek().span,
+        )
+    })?;
+
+    // commas are required
+    parse_comma_or_line_break(tokens)?;
+
+    Ok(Selection::TypeRefinement(TypeRefinementSelection {
+        type_to_refine_to,
+        associated_data: (),
+        selection_set,
+    }))
+}
+
 fn parse_optional_alias_and_field_name(
     tokens: &mut PeekableLexer,
 ) -> ParseResultWithSpan<(WithSpan<StringKey>, Option<WithSpan<StringKey>>)> {
@@ -357,15 +404,17 @@ fn parse_unwraps(tokens: &mut PeekableLexer) -> Vec<WithSpan<Unwrap>> {
 
 fn parse_directives(
     tokens: &mut PeekableLexer,
+    text_source: TextSource,
 ) -> ParseResultWithSpan<Vec<WithSpan<FragmentDirectiveUsage>>> {
     let mut directives = vec![];
     while let Ok(token) = tokens.parse_token_of_kind(IsographLangTokenKind::At) {
         let name = tokens
             .parse_string_key_type(IsographLangTokenKind::Identifier)
             .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
-        let directive_span = Span::join(token.span, name.span);
+        let arguments = parse_optional_arguments(tokens, text_source)?;
+        let directive_span = Span::new(token.span.start, tokens.white_space_span().start);
         directives.push(WithSpan::new(
-            FragmentDirectiveUsage { name },
+            FragmentDirectiveUsage { name, arguments },
             directive_span,
         ));
     }
@@ -406,7 +455,7 @@ fn parse_argument(
             tokens
                 .parse_token_of_kind(IsographLangTokenKind::Colon)
                 .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
-            let value = parse_non_constant_value(tokens)?;
+            let value = parse_non_constant_value(tokens, text_source)?;
             Ok::<_, WithSpan<IsographLiteralParseError>>(SelectionFieldArgument { name, value })
         })
         .transpose()?;
@@ -415,7 +464,15 @@ fn parse_argument(
 
 fn parse_non_constant_value(
     tokens: &mut PeekableLexer,
+    text_source: TextSource,
 ) -> ParseResultWithSpan<WithSpan<NonConstantValue>> {
+    match tokens.peek().item {
+        IsographLangTokenKind::OpenBracket => return parse_list_value(tokens, text_source),
+        IsographLangTokenKind::OpenBrace => return parse_object_value(tokens, text_source),
+        IsographLangTokenKind::IntegerLiteral => return parse_integer_value(tokens),
+        _ => {}
+    }
+
     from_control_flow(|| {
         to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
             let _dollar_sign = tokens
@@ -429,13 +486,55 @@ fn parse_non_constant_value(
 
         to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
             let number = tokens
-                .parse_source_of_kind(IsographLangTokenKind::IntegerLiteral)
+                .parse_source_of_kind(IsographLangTokenKind::FloatLiteral)
                 .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
             Ok(number.map(|number| {
-                NonConstantValue::Integer(number.parse().expect("Expected valid integer"))
+                NonConstantValue::Float(number.parse::<f64>().expect("Expected valid float").into())
+            }))
+        })?;
+
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let string = tokens
+                .parse_source_of_kind(IsographLangTokenKind::StringLiteral)
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            Ok(string.map(|source_with_quotes| {
+                NonConstantValue::String(
+                    unescape_string_literal(&source_with_quotes[1..source_with_quotes.len() - 1])
+                        .intern()
+                        .into(),
+                )
             }))
         })?;
 
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let true_value = tokens
+                .parse_matching_identifier("true")
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            Ok(true_value.map(|_| NonConstantValue::Boolean(true)))
+        })?;
+
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let false_value = tokens
+                .parse_matching_identifier("false")
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            Ok(false_value.map(|_| NonConstantValue::Boolean(false)))
+        })?;
+
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let null_value = tokens
+                .parse_matching_identifier("null")
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            Ok(null_value.map(|_| NonConstantValue::Null))
+        })?;
+
+        // All remaining identifiers are treated as enums.
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let enum_value = tokens
+                .parse_string_key_type(IsographLangTokenKind::Identifier)
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            Ok(enum_value.map(NonConstantValue::Enum))
+        })?;
+
         ControlFlow::Continue(WithSpan::new(
             IsographLiteralParseError::ExpectedNonConstantValue,
             Span::todo_generated(),
@@ -443,6 +542,97 @@ fn parse_non_constant_value(
     })
 }
 
+fn parse_integer_value(
+    tokens: &mut PeekableLexer,
+) -> ParseResultWithSpan<WithSpan<NonConstantValue>> {
+    let number = tokens
+        .parse_source_of_kind(IsographLangTokenKind::IntegerLiteral)
+        .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+    match number.item.parse() {
+        Ok(int_value) => Ok(WithSpan::new(
+            NonConstantValue::Integer(int_value),
+            number.span,
+        )),
+        Err(_) => Err(WithSpan::new(
+            IsographLiteralParseError::IntegerOutOfRange {
+                integer: number.item.to_string(),
+            },
+            number.span,
+        )),
+    }
+}
+
+fn parse_list_value(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResultWithSpan<WithSpan<NonConstantValue>> {
+    tokens
+        .with_span(|tokens| {
+            tokens
+                .parse_token_of_kind(IsographLangTokenKind::OpenBracket)
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            let mut values = vec![];
+            while tokens
+                .parse_token_of_kind(IsographLangTokenKind::CloseBracket)
+                .is_err()
+            {
+                values.push(parse_constant_value(tokens, text_source)?);
+                // commas are optional
+                let _comma = tokens.parse_token_of_kind(IsographLangTokenKind::Comma);
+            }
+            Ok::<_, WithSpan<IsographLiteralParseError>>(NonConstantValue::List(values))
+        })
+        .transpose()
+}
+
+fn parse_object_value(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResultWithSpan<WithSpan<NonConstantValue>> {
+    tokens
+        .with_span(|tokens| {
+            tokens
+                .parse_token_of_kind(IsographLangTokenKind::OpenBrace)
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            let mut values = vec![];
+            while tokens
+                .parse_token_of_kind(IsographLangTokenKind::CloseBrace)
+                .is_err()
+            {
+                let name = tokens
+                    .parse_string_key_type(IsographLangTokenKind::Identifier)
+                    .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?
+                    .to_with_location(text_source);
+                tokens
+                    .parse_token_of_kind(IsographLangTokenKind::Colon)
+                    .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+                let value = parse_constant_value(tokens, text_source)?;
+                values.push(NameValuePair { name, value });
+                // commas are optional
+                let _comma = tokens.parse_token_of_kind(IsographLangTokenKind::Comma);
+            }
+            Ok::<_, WithSpan<IsographLiteralParseError>>(NonConstantValue::Object(values))
+        })
+        .transpose()
+}
+
+/// Parse a value within a list or object, which (for now) cannot contain variables.
+fn parse_constant_value(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResultWithSpan<WithLocation<ConstantValue>> {
+    let value = parse_non_constant_value(tokens, text_source)?;
+    match value.item.to_constant_value() {
+        Some(constant_value) => {
+            Ok(WithSpan::new(constant_value, value.span).to_with_location(text_source))
+        }
+        None => Err(WithSpan::new(
+            IsographLiteralParseError::ExpectedConstantValue,
+            value.span,
+        )),
+    }
+}
+
 fn parse_variable_definitions(
     tokens: &mut PeekableLexer,
     text_source: TextSource,
@@ -482,8 +672,20 @@ fn parse_variable_definition(
                 .parse_token_of_kind(IsographLangTokenKind::Colon)
                 .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
             let type_ = parse_type_annotation(tokens)?;
+            let default_value = if tokens
+                .parse_token_of_kind(IsographLangTokenKind::Equals)
+                .is_ok()
+            {
+                Some(parse_constant_value(tokens, text_source)?)
+            } else {
+                None
+            };
 
-            Ok::<_, WithSpan<IsographLiteralParseError>>(VariableDefinition { name, type_ })
+            Ok::<_, WithSpan<IsographLiteralParseError>>(VariableDefinition {
+                name,
+                type_,
+                default_value,
+            })
         })
         .transpose()?;
     Ok(variable_definition)
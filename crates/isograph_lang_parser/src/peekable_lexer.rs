@@ -39,25 +39,17 @@ impl<'source> PeekableLexer<'source> {
 
     /// Get the next token (and advance)
     pub fn parse_token(&mut self) -> WithSpan<IsographLangTokenKind> {
-        // Skip over (and record) any invalid tokens until either a valid token or an EOF is encountered
-        loop {
-            let kind = self
-                .lexer
-                .next()
-                .unwrap_or(IsographLangTokenKind::EndOfFile);
-            match kind {
-                IsographLangTokenKind::Error => {
-                    // TODO propagate? continue?
-                    panic!("Encountered an error. This probably means you have an invalid token.")
-                }
-                _ => {
-                    self.end_index_of_last_parsed_token = self.current.span.end;
-                    let span = self.lexer_span();
-                    // TODO why does self.current = ... not work here?
-                    return std::mem::replace(&mut self.current, WithSpan::new(kind, span));
-                }
-            }
-        }
+        // Invalid tokens are returned as IsographLangTokenKind::Error, so that the caller
+        // reports an unexpected token, instead of panicking. This matters for the language
+        // server, which parses iso literals while they are being typed.
+        let kind = self
+            .lexer
+            .next()
+            .unwrap_or(IsographLangTokenKind::EndOfFile);
+        self.end_index_of_last_parsed_token = self.current.span.end;
+        let span = self.lexer_span();
+        // TODO why does self.current = ... not work here?
+        std::mem::replace(&mut self.current, WithSpan::new(kind, span))
     }
 
     pub fn peek(&self) -> WithSpan<IsographLangTokenKind> {
@@ -131,7 +123,6 @@ impl<'source> PeekableLexer<'source> {
         Ok(WithSpan::new(source.into(), kind.span))
     }
 
-    #[allow(dead_code)]
     pub fn parse_matching_identifier(
         &mut self,
         identifier: &'static str,
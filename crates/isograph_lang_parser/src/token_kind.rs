@@ -1,5 +1,6 @@
 use std::fmt;
 
+use common_lang_types::{Span, WithSpan};
 use logos::{Lexer, Logos};
 
 #[derive(Logos, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -29,16 +30,16 @@ pub enum IsographLangTokenKind {
     Dollar,
     EndOfFile,
 
-    // #[token("=")]
-    // Equals,
+    #[token("=")]
+    Equals,
     #[token("!")]
     Exclamation,
 
     // IntegerPart:    -?(0|[1-9][0-9]*)
     // FractionalPart: \\.[0-9]+
     // ExponentPart:   [eE][+-]?[0-9]+
-    // #[regex("-?(0|[1-9][0-9]*)(\\.[0-9]+[eE][+-]?[0-9]+|\\.[0-9]+|[eE][+-]?[0-9]+)")]
-    // FloatLiteral,
+    #[regex("-?(0|[1-9][0-9]*)(\\.[0-9]+[eE][+-]?[0-9]+|\\.[0-9]+|[eE][+-]?[0-9]+)")]
+    FloatLiteral,
     #[regex("[a-zA-Z_][a-zA-Z0-9_]*")]
     Identifier,
 
@@ -67,9 +68,8 @@ pub enum IsographLangTokenKind {
 
     // #[token("|")]
     // Pipe,
-
-    // #[token("...")]
-    // Spread,
+    #[token("...")]
+    Spread,
 
     // Comments
     // #[regex("#[^\n\r]*")]
@@ -159,9 +159,9 @@ impl fmt::Display for IsographLangTokenKind {
             // IsographLangTokenKind::Colon => "colon (':')",
             IsographLangTokenKind::Dollar => "dollar ('$')",
             IsographLangTokenKind::EndOfFile => "end of file",
-            // IsographLangTokenKind::Equals => "equals ('=')",
+            IsographLangTokenKind::Equals => "equals ('=')",
             IsographLangTokenKind::Exclamation => "exclamation mark ('!')",
-            // IsographLangTokenKind::FloatLiteral => "floating point value (e.g. '3.14')",
+            IsographLangTokenKind::FloatLiteral => "floating point value (e.g. '3.14')",
             IsographLangTokenKind::Identifier => "non-variable identifier (e.g. 'x' or 'Foo')",
             IsographLangTokenKind::IntegerLiteral => "integer value (e.g. '0' or '42')",
             IsographLangTokenKind::OpenBrace => "open brace ('{')",
@@ -170,7 +170,7 @@ impl fmt::Display for IsographLangTokenKind {
             IsographLangTokenKind::Period => "period ('.')",
             // IsographLangTokenKind::PeriodPeriod => "double period ('..')",
             // IsographLangTokenKind::Pipe => "pipe ('|')",
-            // IsographLangTokenKind::Spread => "spread ('...')",
+            IsographLangTokenKind::Spread => "spread ('...')",
             IsographLangTokenKind::BlockStringLiteral => "block string (e.g. '\"\"\"hi\"\"\"')",
             IsographLangTokenKind::Error => "error",
             IsographLangTokenKind::ErrorFloatLiteralMissingZero => {
@@ -196,6 +196,16 @@ impl fmt::Display for IsographLangTokenKind {
     }
 }
 
+/// Lex the source of an iso literal without parsing it, skipping unrecognized
+/// characters. The language server uses this to make sense of iso literals that are
+/// incomplete because they are still being typed.
+pub fn lex_iso_literal(source: &str) -> impl Iterator<Item = WithSpan<IsographLangTokenKind>> + '_ {
+    IsographLangTokenKind::lexer(source)
+        .spanned()
+        .filter(|(kind, _)| *kind != IsographLangTokenKind::Error)
+        .map(|(kind, span)| WithSpan::new(kind, Span::new(span.start as u32, span.end as u32)))
+}
+
 fn lex_block_string(lexer: &mut Lexer<'_, IsographLangTokenKind>) -> bool {
     let remainder = lexer.remainder();
     let mut string_lexer = BlockStringToken::lexer(remainder);
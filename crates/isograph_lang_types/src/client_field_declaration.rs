@@ -1,9 +1,13 @@
 use common_lang_types::{
-    ConstExportName, FieldArgumentName, FieldNameOrAlias, FilePath, HasName, IsographDirectiveName,
-    LinkedFieldAlias, LinkedFieldName, ScalarFieldAlias, ScalarFieldName, SelectableFieldName,
-    UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
+    ConstExportName, DescriptionValue, EnumLiteralValue, FieldArgumentName, FieldNameOrAlias,
+    FilePath, HasName, IsographDirectiveName, LinkedFieldAlias, LinkedFieldName, ScalarFieldAlias,
+    ScalarFieldName, SelectableFieldName, StringLiteralValue, UnvalidatedTypeName, ValueKeyName,
+    VariableName, WithLocation, WithSpan,
 };
-use graphql_lang_types::TypeAnnotation;
+use graphql_lang_types::{
+    print_string_literal, ConstantValue, FloatValue, NameValuePair, TypeAnnotation,
+};
+use intern::Lookup;
 
 pub type UnvalidatedSelection = Selection<
     // <UnvalidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
@@ -15,9 +19,16 @@ pub type UnvalidatedScalarFieldSelection = ScalarFieldSelection<
     // <UnvalidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
     (),
 >;
+pub type UnvalidatedTypeRefinementSelection = TypeRefinementSelection<
+    // <UnvalidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
+    (),
+    // <UnvalidatedSchemaState as SchemaValidationState>::ClientFieldSelectionLinkedFieldAssociatedData,
+    (),
+>;
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct ClientFieldDeclaration {
+    pub description: Option<WithSpan<DescriptionValue>>,
     pub const_export_name: ConstExportName,
     pub parent_type: WithSpan<UnvalidatedTypeName>,
     pub client_field_name: WithSpan<ScalarFieldName>,
@@ -32,12 +43,13 @@ pub struct ClientFieldDeclaration {
 /// Ugly name, but at least it makes clear this isn't a schema directive.
 pub struct FragmentDirectiveUsage {
     pub name: WithSpan<IsographDirectiveName>,
-    // TODO arguments and such
+    pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub enum Selection<TScalarField, TLinkedField> {
     ServerField(ServerFieldSelection<TScalarField, TLinkedField>),
+    TypeRefinement(TypeRefinementSelection<TScalarField, TLinkedField>),
     // FieldGroup(FieldGroupSelection),
 }
 
@@ -47,9 +59,18 @@ impl<TScalarField, TLinkedField> Selection<TScalarField, TLinkedField> {
         map: &mut impl FnMut(
             ServerFieldSelection<TScalarField, TLinkedField>,
         ) -> ServerFieldSelection<TNewScalarField, TNewLinkedField>,
+        map_type_refinement: &mut impl FnMut(
+            TypeRefinementSelection<TScalarField, TLinkedField>,
+        ) -> TypeRefinementSelection<
+            TNewScalarField,
+            TNewLinkedField,
+        >,
     ) -> Selection<TNewScalarField, TNewLinkedField> {
         match self {
             Selection::ServerField(field_selection) => Selection::ServerField(map(field_selection)),
+            Selection::TypeRefinement(type_refinement) => {
+                Selection::TypeRefinement(map_type_refinement(type_refinement))
+            }
         }
     }
 
@@ -59,11 +80,20 @@ impl<TScalarField, TLinkedField> Selection<TScalarField, TLinkedField> {
             ServerFieldSelection<TScalarField, TLinkedField>,
         )
             -> Result<ServerFieldSelection<TNewScalarField, TNewLinkedField>, E>,
+        map_type_refinement: &mut impl FnMut(
+            TypeRefinementSelection<TScalarField, TLinkedField>,
+        ) -> Result<
+            TypeRefinementSelection<TNewScalarField, TNewLinkedField>,
+            E,
+        >,
     ) -> Result<Selection<TNewScalarField, TNewLinkedField>, E> {
         match self {
             Selection::ServerField(field_selection) => {
                 Ok(Selection::ServerField(map(field_selection)?))
             }
+            Selection::TypeRefinement(type_refinement) => Ok(Selection::TypeRefinement(
+                map_type_refinement(type_refinement)?,
+            )),
         }
     }
 }
@@ -143,6 +173,7 @@ pub struct ScalarFieldSelection<TScalarField> {
     pub associated_data: TScalarField,
     pub unwraps: Vec<WithSpan<Unwrap>>,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    pub directives: Vec<WithSpan<FragmentDirectiveUsage>>,
 }
 
 impl<TScalarField> ScalarFieldSelection<TScalarField> {
@@ -154,6 +185,7 @@ impl<TScalarField> ScalarFieldSelection<TScalarField> {
             unwraps: self.unwraps,
             arguments: self.arguments,
             normalization_alias: self.normalization_alias,
+            directives: self.directives,
         }
     }
 
@@ -168,6 +200,7 @@ impl<TScalarField> ScalarFieldSelection<TScalarField> {
             unwraps: self.unwraps,
             arguments: self.arguments,
             normalization_alias: self.normalization_alias,
+            directives: self.directives,
         })
     }
 
@@ -187,6 +220,7 @@ pub struct LinkedFieldSelection<TScalarField, TLinkedField> {
     pub selection_set: Vec<WithSpan<Selection<TScalarField, TLinkedField>>>,
     pub unwraps: Vec<WithSpan<Unwrap>>,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    pub directives: Vec<WithSpan<FragmentDirectiveUsage>>,
 }
 
 impl<TScalarField, TLinkedField> LinkedFieldSelection<TScalarField, TLinkedField> {
@@ -197,6 +231,16 @@ impl<TScalarField, TLinkedField> LinkedFieldSelection<TScalarField, TLinkedField
     }
 }
 
+/// A selection set that only applies if the parent object is of a given
+/// concrete type, e.g. `... on User { name }`. In a validated selection,
+/// associated_data contains the id of the refined type.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct TypeRefinementSelection<TScalarField, TLinkedField> {
+    pub type_to_refine_to: WithLocation<UnvalidatedTypeName>,
+    pub associated_data: TLinkedField,
+    pub selection_set: Vec<WithSpan<Selection<TScalarField, TLinkedField>>>,
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub enum Unwrap {
     ActualUnwrap,
@@ -204,6 +248,15 @@ pub enum Unwrap {
     // FakeUnwrap?
 }
 
+/// For each level of the selected field's type (the value, then its list items, and
+/// so on), whether `field!` asserts that it is non-null.
+pub fn unwrapped_levels(unwraps: &[WithSpan<Unwrap>]) -> Vec<bool> {
+    unwraps
+        .iter()
+        .map(|unwrap| unwrap.item == Unwrap::ActualUnwrap)
+        .collect()
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct SelectionFieldArgument {
     pub name: WithSpan<FieldArgumentName>,
@@ -225,31 +278,172 @@ impl SelectionFieldArgument {
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub enum NonConstantValue {
     Variable(VariableName),
-    Integer(u64),
+    Integer(i64),
+    Float(FloatValue),
+    String(StringLiteralValue),
+    Boolean(bool),
+    Null,
+    Enum(EnumLiteralValue),
+    // Variables are not (yet) supported within lists and objects.
+    List(Vec<WithLocation<ConstantValue>>),
+    Object(Vec<NameValuePair<ValueKeyName, ConstantValue>>),
 }
 
 impl NonConstantValue {
     pub fn reachable_variables(&self) -> Vec<VariableName> {
         match self {
             NonConstantValue::Variable(name) => vec![*name],
-            NonConstantValue::Integer(_) => vec![],
+            _ => vec![],
         }
     }
 
+    /// Returns the value as a ConstantValue, or None if it is a variable.
+    pub fn to_constant_value(&self) -> Option<ConstantValue> {
+        Some(match self {
+            NonConstantValue::Variable(_) => return None,
+            NonConstantValue::Integer(int_value) => ConstantValue::Int(*int_value),
+            NonConstantValue::Float(float_value) => ConstantValue::Float(*float_value),
+            NonConstantValue::String(string_value) => ConstantValue::String(*string_value),
+            NonConstantValue::Boolean(bool_value) => ConstantValue::Boolean(*bool_value),
+            NonConstantValue::Null => ConstantValue::Null,
+            NonConstantValue::Enum(enum_value) => ConstantValue::Enum(*enum_value),
+            NonConstantValue::List(list) => ConstantValue::List(list.clone()),
+            NonConstantValue::Object(object) => ConstantValue::Object(object.clone()),
+        })
+    }
+
     pub fn to_alias_str_chunk(&self) -> String {
         match self {
             NonConstantValue::Variable(name) => format!("v_{}", name),
             // l for literal, i.e. this is shared with others
-            NonConstantValue::Integer(int_value) => format!("l_{}", int_value),
+            _ => {
+                let constant_value = self
+                    .to_constant_value()
+                    .expect("Expected value to be constant");
+                format!(
+                    "l_{}",
+                    escape_alias_str_chunk(&constant_value_to_key_str(&constant_value))
+                )
+            }
         }
     }
 }
 
+/// The string that is used to identify a literal value in store keys. Strings and
+/// enums are used as-is, and all other values are serialized as JSON. This must be
+/// kept in sync with @isograph/react
+fn constant_value_to_key_str(value: &ConstantValue) -> String {
+    match value {
+        ConstantValue::String(string_value) => string_value.to_string(),
+        ConstantValue::Enum(enum_value) => enum_value.to_string(),
+        _ => constant_value_to_json(value),
+    }
+}
+
+fn constant_value_to_json(value: &ConstantValue) -> String {
+    match value {
+        ConstantValue::Int(int_value) => int_value.to_string(),
+        ConstantValue::Float(float_value) => float_to_json(float_value.as_float()),
+        ConstantValue::String(string_value) => print_string_literal(string_value.lookup()),
+        ConstantValue::Boolean(bool_value) => bool_value.to_string(),
+        ConstantValue::Null => "null".to_string(),
+        ConstantValue::Enum(enum_value) => format!("\"{}\"", enum_value),
+        ConstantValue::List(list) => format!(
+            "[{}]",
+            list.iter()
+                .map(|item| constant_value_to_json(&item.item))
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        ConstantValue::Object(object) => format!(
+            "{{{}}}",
+            object
+                .iter()
+                .map(|pair| format!(
+                    "\"{}\":{}",
+                    pair.name.item,
+                    constant_value_to_json(&pair.value.item)
+                ))
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+    }
+}
+
+/// Format a float the way JSON.stringify does, which differs from Rust's Display
+/// for very large and very small numbers, e.g. 1e21 is formatted as `1e+21`
+/// rather than `1000000000000000000000`, and 1e-7 as `1e-7` rather than
+/// `0.0000001`. See Number::toString in the ECMAScript spec.
+fn float_to_json(value: f64) -> String {
+    if !value.is_finite() {
+        return "null".to_string();
+    }
+    if value == 0.0 {
+        // Including -0
+        return "0".to_string();
+    }
+
+    // LowerExp formats the shortest digits that round trip, e.g. 1.5e-7, as does
+    // JavaScript.
+    let exponential = format!("{:e}", value.abs());
+    let (mantissa, exponent) = exponential
+        .split_once('e')
+        .expect("Expected float to be formatted with an exponent");
+    let digits = mantissa.replace('.', "");
+    let digit_count = digits.len() as i32;
+    // The position of the decimal point relative to the start of the digits
+    let point_position = exponent
+        .parse::<i32>()
+        .expect("Expected exponent to be an integer")
+        + 1;
+
+    let sign = if value < 0.0 { "-" } else { "" };
+    let unsigned = if digit_count <= point_position && point_position <= 21 {
+        format!(
+            "{digits}{}",
+            "0".repeat((point_position - digit_count) as usize)
+        )
+    } else if 0 < point_position && point_position <= 21 {
+        let (integer_part, fractional_part) = digits.split_at(point_position as usize);
+        format!("{integer_part}.{fractional_part}")
+    } else if -6 < point_position && point_position <= 0 {
+        format!("0.{}{digits}", "0".repeat(-point_position as usize))
+    } else {
+        let exponent = point_position - 1;
+        let exponent_sign = if exponent < 0 { "-" } else { "+" };
+        let (first_digit, remaining_digits) = digits.split_at(1);
+        let fraction = if remaining_digits.is_empty() {
+            String::new()
+        } else {
+            format!(".{remaining_digits}")
+        };
+        format!("{first_digit}{fraction}e{exponent_sign}{}", exponent.abs())
+    };
+    format!("{sign}{unsigned}")
+}
+
+/// Aliases must be valid GraphQL names, so every character that cannot appear
+/// in a name is replaced with its hex-encoded code point, e.g. `-` becomes `_2d_`.
+/// Underscores are also escaped (as `_5f_`), so that distinct values always result
+/// in distinct aliases. This must be kept in sync with @isograph/react
+fn escape_alias_str_chunk(key_str: &str) -> String {
+    key_str
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_string()
+            } else {
+                format!("_{:x}_", c as u32)
+            }
+        })
+        .collect()
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct VariableDefinition<TValue> {
     pub name: WithLocation<VariableName>,
     pub type_: TypeAnnotation<TValue>,
-    // pub default_value: Option<WithLocation<ConstantValue>>,
+    pub default_value: Option<WithLocation<ConstantValue>>,
 }
 
 impl<TValue> VariableDefinition<TValue> {
@@ -260,6 +454,7 @@ impl<TValue> VariableDefinition<TValue> {
         VariableDefinition {
             name: self.name,
             type_: self.type_.map(map),
+            default_value: self.default_value,
         }
     }
 
@@ -270,6 +465,7 @@ impl<TValue> VariableDefinition<TValue> {
         Ok(VariableDefinition {
             name: self.name,
             type_: self.type_.and_then(map)?,
+            default_value: self.default_value,
         })
     }
 }
@@ -13,10 +13,16 @@ u32_newtype!(ObjectId);
 
 u32_newtype!(ScalarId);
 
+u32_newtype!(EnumId);
+
+u32_newtype!(InputObjectId);
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
 pub enum SelectableFieldId {
     Object(ObjectId),
     Scalar(ScalarId),
+    Enum(EnumId),
+    InputObject(InputObjectId),
 }
 
 impl TryFrom<SelectableFieldId> for ScalarId {
@@ -24,7 +30,9 @@ impl TryFrom<SelectableFieldId> for ScalarId {
 
     fn try_from(value: SelectableFieldId) -> Result<Self, Self::Error> {
         match value {
-            SelectableFieldId::Object(_) => Err(()),
+            SelectableFieldId::Object(_)
+            | SelectableFieldId::Enum(_)
+            | SelectableFieldId::InputObject(_) => Err(()),
             SelectableFieldId::Scalar(scalar_id) => Ok(scalar_id),
         }
     }
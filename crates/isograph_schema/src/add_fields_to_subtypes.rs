@@ -1,8 +1,8 @@
 use common_lang_types::{Location, WithLocation};
 
 use crate::{
-    ClientFieldVariant, ProcessTypeDefinitionError, ProcessTypeDefinitionResult, TypeRefinementMap,
-    UnvalidatedSchema,
+    ClientFieldVariant, FieldDefinitionLocation, ProcessTypeDefinitionError,
+    ProcessTypeDefinitionResult, TypeRefinementMap, UnvalidatedSchema,
 };
 
 impl UnvalidatedSchema {
@@ -50,6 +50,17 @@ impl UnvalidatedSchema {
                             }
                             let subtype = self.schema_data.object_mut(*subtype_id);
 
+                            // Since type refinements are transitive, a client field defined on
+                            // an interface can reach a subtype both directly and through an
+                            // intermediate interface. In that case, it was already added.
+                            if let Some(FieldDefinitionLocation::Client(existing_client_field_id)) =
+                                subtype.encountered_fields.get(supertype_field_name)
+                            {
+                                if existing_client_field_id == supertype_client_field_id {
+                                    continue 'field;
+                                }
+                            }
+
                             if let Some(_) = subtype
                                 .encountered_fields
                                 .insert(*supertype_field_name, defined_field.clone())
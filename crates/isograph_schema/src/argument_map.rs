@@ -1,22 +1,20 @@
 use std::collections::HashMap;
 
 use common_lang_types::{
-    DescriptionValue, InputValueName, IsographObjectTypeName, Location, SelectableFieldName, Span,
-    StringLiteralValue, WithLocation, WithSpan,
+    DescriptionValue, GraphQLInputObjectTypeName, InputValueName, IsographObjectTypeName, Location,
+    SelectableFieldName, StringLiteralValue, UnvalidatedTypeName, WithLocation, WithSpan,
 };
 use graphql_lang_types::{
-    ConstantValue, GraphQLDirective, GraphQLFieldDefinition, GraphQLInputValueDefinition,
+    ConstantValue, GraphQLDirective, GraphQLInputObjectTypeDefinition, GraphQLInputValueDefinition,
     TypeAnnotation,
 };
 use intern::{string_key::Intern, Lookup};
-use isograph_lang_types::{ObjectId, SelectableFieldId, ServerFieldId};
+use isograph_lang_types::{InputObjectId, SelectableFieldId};
 
 use crate::{
-    FieldMapItem, IsographObjectTypeDefinition, ProcessObjectTypeDefinitionOutcome,
-    ProcessTypeDefinitionError, ProcessTypeDefinitionResult, ProcessedFieldMapItem,
+    FieldMapItem, ProcessTypeDefinitionError, ProcessTypeDefinitionResult, ProcessedFieldMapItem,
     UnvalidatedSchema,
 };
-use isograph_config::ConfigOptions;
 
 pub(crate) struct ArgumentMap {
     arguments: Vec<WithLocation<PotentiallyModifiedArgument>>,
@@ -81,7 +79,8 @@ impl ArgumentMap {
                             .get(&unmodified_argument.type_.inner().lookup().intern().into())
                         {
                             Some(defined_type) => match defined_type {
-                                SelectableFieldId::Object(_) => return Err(WithLocation::new(
+                                SelectableFieldId::Object(_)
+                                | SelectableFieldId::InputObject(_) => return Err(WithLocation::new(
                                     ProcessTypeDefinitionError::PrimaryDirectiveCannotRemapObject {
                                         primary_type_name,
                                         field_name: split_to_arg
@@ -91,7 +90,7 @@ impl ArgumentMap {
                                     },
                                     Location::generated(),
                                 )),
-                                SelectableFieldId::Scalar(_) => {}
+                                SelectableFieldId::Scalar(_) | SelectableFieldId::Enum(_) => {}
                             },
                             None => panic!(
                                 "Type is not found. This is indicative \
@@ -105,8 +104,11 @@ impl ArgumentMap {
                         processed_field_map_item
                     }
                     Some((first, rest)) => {
-                        let mut arg =
-                            ModifiedArgument::from_unmodified(unmodified_argument, schema);
+                        let mut arg = ModifiedArgument::from_unmodified(
+                            unmodified_argument,
+                            schema,
+                            primary_type_name,
+                        )?;
 
                         let _processed_field_map_item =
                             arg.remove_to_field(schema, *first, rest, primary_type_name)?;
@@ -124,8 +126,8 @@ impl ArgumentMap {
                 match to_field_names.split_first() {
                     None => {
                         // TODO encode this in the type system.
-                        // A modified argument will always have an object type, and cannot be remapped
-                        // at the object level.
+                        // A modified argument will always have an input object type, and cannot be
+                        // remapped at the input object level.
                         return Err(WithLocation::new(
                             ProcessTypeDefinitionError::PrimaryDirectiveCannotRemapObject {
                                 primary_type_name,
@@ -149,7 +151,6 @@ impl ArgumentMap {
     pub(crate) fn into_arguments(
         self,
         schema: &mut UnvalidatedSchema,
-        options: ConfigOptions,
     ) -> Vec<WithLocation<GraphQLInputValueDefinition>> {
         self.arguments
             .into_iter()
@@ -161,7 +162,7 @@ impl ArgumentMap {
                             let ModifiedArgument {
                                 description,
                                 name,
-                                object,
+                                input_object,
                                 default_value,
                                 directives,
                             } = modified;
@@ -169,12 +170,8 @@ impl ArgumentMap {
                             GraphQLInputValueDefinition {
                                 description,
                                 name,
-                                type_: object.map(|modified_object| {
-                                    modified_object
-                                        .create_and_get_name(schema, options)
-                                        .lookup()
-                                        .intern()
-                                        .into()
+                                type_: input_object.map(|modified_input_object| {
+                                    modified_input_object.create_and_get_name(schema).into()
                                 }),
                                 default_value,
                                 directives,
@@ -192,130 +189,251 @@ enum PotentiallyModifiedArgument {
     Modified(ModifiedArgument),
 }
 
-/// An object which has fields that are unmodified, deleted,
-/// or modified (indicating that a new object should be created
-/// for them to point to.) Scalar fields cannot be modified,
+/// An input object which has fields that are unmodified, deleted,
+/// or modified (indicating that a new input object should be created
+/// for them to point to.) Scalar and enum fields cannot be modified,
 /// only deleted.
 #[derive(Debug)]
-pub(crate) struct ModifiedObject {
-    object_id: ObjectId,
-    field_map: HashMap<SelectableFieldName, PotentiallyModifiedField>,
+pub(crate) struct ModifiedInputObject {
+    input_object_id: InputObjectId,
+    field_map: HashMap<InputValueName, PotentiallyModifiedField>,
 }
 
-impl ModifiedObject {
-    fn create_and_get_name(
-        self,
-        schema: &mut UnvalidatedSchema,
-        options: ConfigOptions,
-    ) -> IsographObjectTypeName {
-        let original_object = schema.schema_data.object(self.object_id);
-
-        let fields = original_object
-            .server_fields
-            .iter()
-            .flat_map(|field_id| {
-                let field = schema.field(*field_id);
-
-                // HACK alert
-                if field.name.item == "__typename".intern().into() {
-                    return None;
+impl ModifiedInputObject {
+    /// N.B. this creates a modified input object with no modified fields, which
+    /// is identical to the original input object. Callers should immediately call
+    /// remove_to_field.
+    fn new(input_object_id: InputObjectId, schema: &UnvalidatedSchema) -> Self {
+        ModifiedInputObject {
+            input_object_id,
+            field_map: schema
+                .schema_data
+                .input_object(input_object_id)
+                .fields
+                .iter()
+                .map(|field| {
+                    (
+                        field.item.name.item,
+                        PotentiallyModifiedField::Unmodified(field.item.clone()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn remove_to_field(
+        &mut self,
+        schema: &UnvalidatedSchema,
+        first: StringLiteralValue,
+        rest: &[StringLiteralValue],
+        primary_type_name: IsographObjectTypeName,
+    ) -> ProcessTypeDefinitionResult<IsEmpty> {
+        let key = first.lookup().intern().into();
+        let field = match self.field_map.get_mut(&key) {
+            Some(field) => field,
+            None => {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::PrimaryDirectiveFieldNotFound {
+                        primary_type_name,
+                        field_name: first,
+                    },
+                    Location::generated(),
+                ))
+            }
+        };
+
+        match rest.split_first() {
+            Some((first, rest)) => {
+                match field.remove_to_field(schema, *first, rest, primary_type_name)? {
+                    IsEmpty::IsEmpty => {
+                        // The field's input object has no remaining fields, so we remove
+                        // the item from the parent.
+                        self.field_map.remove(&key).expect(
+                            "Expected to be able to remove item. \
+                            This is indicative of a bug in Isograph",
+                        );
+                    }
+                    IsEmpty::NotEmpty => {}
+                }
+            }
+            None => {
+                // We ran out of path segments, so we remove this item.
+                // It must have a scalar or enum type.
+
+                match field {
+                    PotentiallyModifiedField::Unmodified(field) => {
+                        let field_type: UnvalidatedTypeName = (*field.type_.inner()).into();
+
+                        // N.B. this should be done via a validation pass.
+                        match schema.schema_data.defined_types.get(&field_type) {
+                            Some(type_) => match type_ {
+                                SelectableFieldId::Object(_)
+                                | SelectableFieldId::InputObject(_) => {
+                                    // Otherwise, formatting breaks :(
+                                    use ProcessTypeDefinitionError::PrimaryDirectiveCannotRemapObject;
+                                    return Err(WithLocation::new(
+                                        PrimaryDirectiveCannotRemapObject {
+                                            primary_type_name,
+                                            field_name: key.to_string(),
+                                        },
+                                        Location::generated(),
+                                    ));
+                                }
+                                SelectableFieldId::Scalar(_) | SelectableFieldId::Enum(_) => {
+                                    // Cool! We found a scalar or enum, we can remove it.
+                                    self.field_map.remove(&key).expect(
+                                        "Expected to be able to remove item. \
+                                        This is indicative of a bug in Isograph.",
+                                    );
+                                }
+                            },
+
+                            None => panic!("Encountered a non-existent type."),
+                        }
+                    }
+                    PotentiallyModifiedField::Modified(_) => {
+                        // A field can only be modified if it has an input object type
+                        return Err(WithLocation::new(
+                            ProcessTypeDefinitionError::PrimaryDirectiveCannotRemapObject {
+                                primary_type_name,
+                                field_name: key.to_string(),
+                            },
+                            Location::generated(),
+                        ));
+                    }
                 }
+            }
+        }
 
-                let potentially_modified_field = self.field_map.get(&field.name.item)?;
-
-                let new_field = match potentially_modified_field {
-                    PotentiallyModifiedField::Unmodified(_) => WithLocation::new(
-                        GraphQLFieldDefinition {
-                            description: field.description.map(|description| {
-                                WithSpan::new(description, Span::todo_generated())
-                            }),
-                            name: field.name,
-                            type_: field.associated_data.clone(),
-                            arguments: field.arguments.clone(),
-                            directives: vec![],
-                        },
-                        Location::generated(),
-                    ),
-                    PotentiallyModifiedField::Modified(_) => todo!("modified"),
+        Ok(if self.field_map.is_empty() {
+            IsEmpty::IsEmpty
+        } else {
+            IsEmpty::NotEmpty
+        })
+    }
+
+    fn create_and_get_name(mut self, schema: &mut UnvalidatedSchema) -> GraphQLInputObjectTypeName {
+        let original_input_object = schema.schema_data.input_object(self.input_object_id);
+        let description = original_input_object.description;
+        let original_name = original_input_object.name.item;
+        let original_fields = original_input_object.fields.clone();
+
+        // Fields are kept in the order in which the original input object defines them.
+        let fields: Vec<_> = original_fields
+            .into_iter()
+            .filter_map(|field| {
+                let new_field = match self.field_map.remove(&field.item.name.item)? {
+                    PotentiallyModifiedField::Unmodified(field) => field,
+                    PotentiallyModifiedField::Modified(modified_field) => {
+                        let type_name = modified_field
+                            .modified_input_object
+                            .create_and_get_name(schema);
+                        GraphQLInputValueDefinition {
+                            type_: field.item.type_.map(|_| type_name.into()),
+                            ..field.item
+                        }
+                    }
                 };
 
-                Some(new_field)
+                Some(WithLocation::new(new_field, Location::generated()))
             })
             .collect();
 
-        let item = IsographObjectTypeDefinition {
-            // TODO it looks like we throw away span info for descriptions, which makes sense?
-            description: original_object
-                .description
-                .clone()
-                .map(|description| WithSpan::new(description, Span::todo_generated())),
-            // TODO confirm that names don't have to be unique
-            name: WithLocation::new(
-                format!("{}__generated", original_object.name.lookup())
-                    .intern()
-                    .into(),
-                Location::generated(),
-            ),
-            // Very unclear what to do here
-            interfaces: vec![],
+        // Several @exposeField directives can modify the same input object. If they
+        // remove the same fields, they share the generated input object. Otherwise,
+        // each generated input object gets a unique name.
+        let mut generated_count = 0;
+        let name = loop {
+            generated_count += 1;
+            let name: GraphQLInputObjectTypeName = if generated_count == 1 {
+                format!("{}__generated", original_name)
+            } else {
+                format!("{}__generated{}", original_name, generated_count)
+            }
+            .intern()
+            .into();
+
+            let type_name: UnvalidatedTypeName = name.into();
+            match schema.schema_data.defined_types.get(&type_name) {
+                None => break name,
+                Some(SelectableFieldId::InputObject(input_object_id))
+                    if schema.schema_data.input_object(*input_object_id).fields == fields =>
+                {
+                    return name;
+                }
+                Some(_) => {}
+            }
+        };
+
+        let input_object_definition = GraphQLInputObjectTypeDefinition {
+            description,
+            name: WithLocation::new(name, Location::generated()),
             directives: vec![],
             fields,
         };
 
-        let ProcessObjectTypeDefinitionOutcome { object_id, .. } = schema
-            .process_object_type_definition(
-                item,
-                // Obviously, this is a smell
-                &mut HashMap::new(),
-                &mut HashMap::new(),
-                true,
-                options,
-            )
-            // This is not (yet) true. If you reference a non-existent type in
-            // a @exposeField directive, the compiler panics here. The solution is to
-            // process these directives after the definitions have been validated,
-            // but before resolvers hvae been validated.
+        let input_object_id = schema
+            .process_input_object_definition(input_object_definition)
+            // The name is unused and the fields come from a valid input object.
             .expect(
-                "Expected object creation to work. This is \
+                "Expected input object creation to work. This is \
                 indicative of a bug in Isograph.",
             );
 
-        schema.schema_data.object(object_id).name
+        schema.schema_data.input_object(input_object_id).name.item
     }
 }
 
 #[derive(Debug)]
 pub(crate) enum PotentiallyModifiedField {
-    Unmodified(ServerFieldId),
-    // This is exercised in the case of 3+ segments, e.g. input.foo.id.
-    // For now, we support only up to two segments.
-    #[allow(dead_code)]
+    Unmodified(GraphQLInputValueDefinition),
+    /// A field of an input object type, some of whose fields are removed, e.g.
+    /// the `foo` field if `input.foo.id` is remapped.
     Modified(ModifiedField),
 }
 
 impl PotentiallyModifiedField {
     fn remove_to_field(
         &mut self,
-        _first: StringLiteralValue,
-        _rest: &[StringLiteralValue],
+        schema: &UnvalidatedSchema,
+        first: StringLiteralValue,
+        rest: &[StringLiteralValue],
+        primary_type_name: IsographObjectTypeName,
     ) -> ProcessTypeDefinitionResult<IsEmpty> {
-        unimplemented!("Removing to fields from PotentiallyModifiedField")
+        match self {
+            PotentiallyModifiedField::Unmodified(field) => {
+                let input_object_id = input_object_id_to_modify(field, schema, primary_type_name)?;
+                let mut modified_input_object = ModifiedInputObject::new(input_object_id, schema);
+                let is_empty = modified_input_object.remove_to_field(
+                    schema,
+                    first,
+                    rest,
+                    primary_type_name,
+                )?;
+                *self = PotentiallyModifiedField::Modified(ModifiedField {
+                    modified_input_object,
+                });
+                Ok(is_empty)
+            }
+            PotentiallyModifiedField::Modified(modified_field) => modified_field
+                .modified_input_object
+                .remove_to_field(schema, first, rest, primary_type_name),
+        }
     }
 }
 
-/// A modified field's type must be an object. A scalar field that
-/// is modified is just removed.
+/// A modified field's type must be an input object. A scalar or enum field
+/// that is modified is just removed.
 #[derive(Debug)]
 pub(crate) struct ModifiedField {
-    #[allow(dead_code)]
-    modified_object: ModifiedObject,
+    modified_input_object: ModifiedInputObject,
 }
 
 #[derive(Debug)]
 struct ModifiedArgument {
     description: Option<WithSpan<DescriptionValue>>,
     name: WithLocation<InputValueName>,
-    object: TypeAnnotation<ModifiedObject>,
+    input_object: TypeAnnotation<ModifiedInputObject>,
     default_value: Option<WithLocation<ConstantValue>>,
     directives: Vec<GraphQLDirective<ConstantValue>>,
 }
@@ -325,53 +443,27 @@ impl ModifiedArgument {
     /// in that if we didn't immediately call remove_to_field, we would have
     /// a modified argument with a modified object containing no modified fields.
     ///
-    /// Thus, we would unnecessarily create a new object that is identical to
-    /// an existing object.
+    /// Thus, we would unnecessarily create a new input object that is identical to
+    /// an existing input object.
     ///
-    /// This panics if unmodified's type is a scalar.
+    /// This returns an error if unmodified's type is not an input object, since only
+    /// the fields of input objects can be remapped.
     pub fn from_unmodified(
         unmodified: &GraphQLInputValueDefinition,
         schema: &UnvalidatedSchema,
-    ) -> Self {
-        // TODO I think we have validated that the item exists already.
-        // But we should double check that, and return an error if necessary
-        let object = unmodified.type_.clone().map(|x| {
-            let defined_type_id = *schema.schema_data.defined_types.get(&x.into()).expect(
-                "Expected type to be defined by now. This is indicative of a bug in Isograph.",
-            );
-            match defined_type_id {
-                SelectableFieldId::Object(object_id) => {
-                    let object = schema.schema_data.object(object_id);
-
-                    ModifiedObject {
-                        object_id,
-                        field_map: object
-                            .server_fields
-                            .iter()
-                            .map(|server_field_id| {
-                                (
-                                    schema.field(*server_field_id).name.item,
-                                    PotentiallyModifiedField::Unmodified(*server_field_id),
-                                )
-                            })
-                            .collect(),
-                    }
-                }
-                SelectableFieldId::Scalar(_scalar_id) => {
-                    // TODO don't be lazy, return an error
-                    panic!("Cannot modify a scalar")
-                }
-            }
-        });
+        primary_type_name: IsographObjectTypeName,
+    ) -> ProcessTypeDefinitionResult<Self> {
+        let input_object_id = input_object_id_to_modify(unmodified, schema, primary_type_name)?;
+        let modified_input_object = ModifiedInputObject::new(input_object_id, schema);
 
         // TODO We can probably avoid cloning here
-        Self {
+        Ok(Self {
             name: unmodified.name,
             description: unmodified.description,
             default_value: unmodified.default_value.clone(),
             directives: unmodified.directives.clone(),
-            object,
-        }
+            input_object: unmodified.type_.clone().map(|_| modified_input_object),
+        })
     }
 
     pub fn remove_to_field(
@@ -381,93 +473,43 @@ impl ModifiedArgument {
         rest: &[StringLiteralValue],
         primary_type_name: IsographObjectTypeName,
     ) -> ProcessTypeDefinitionResult<()> {
-        let argument_object = self.object.inner_mut();
-
-        let key = first.lookup().intern().into();
-        let _ = match argument_object
-            .field_map
-            // TODO make this a no-op
-            .get_mut(&key)
-        {
-            Some(field) => {
-                match rest.split_first() {
-                    Some((first, rest)) => {
-                        match field.remove_to_field(*first, rest)? {
-                            IsEmpty::IsEmpty => {
-                                // The field's object has no remaining fields (except for __typename),
-                                // so we remove the item from the parent.
-                                argument_object.field_map.remove(&key).expect(
-                                    "Expected to be able to remove item. \
-                                    This is indicative of a bug in Isograph",
-                                );
-                            }
-                            IsEmpty::NotEmpty => {}
-                        }
-                    }
-                    None => {
-                        // We ran out of path segments, so we remove this item.
-                        // It must have a scalar type.
-
-                        match field {
-                            PotentiallyModifiedField::Unmodified(field_id) => {
-                                let field_object = schema.field(*field_id);
-                                let field_object_type = field_object.associated_data.inner();
-
-                                // N.B. this should be done via a validation pass.
-                                match schema.schema_data.defined_types.get(field_object_type) {
-                                    Some(type_) => match type_ {
-                                        SelectableFieldId::Object(_) => {
-                                            // Otherwise, formatting breaks :(
-                                            use ProcessTypeDefinitionError::PrimaryDirectiveCannotRemapObject;
-                                            return Err(WithLocation::new(
-                                                PrimaryDirectiveCannotRemapObject {
-                                                    primary_type_name,
-                                                    field_name: key.to_string(),
-                                                },
-                                                Location::generated(),
-                                            ));
-                                        }
-                                        SelectableFieldId::Scalar(_scalar_id) => {
-                                            // Cool! We found a scalar, we can remove it.
-                                            argument_object.field_map.remove(&key).expect(
-                                                "Expected to be able to remove item. \
-                                                This is indicative of a bug in Isograph.",
-                                            );
-                                        }
-                                    },
-
-                                    None => panic!("Encountered a non-existent type."),
-                                }
-                            }
-                            PotentiallyModifiedField::Modified(_) => {
-                                // A field can only be modified if it has an object type
-                                return Err(WithLocation::new(
-                                    ProcessTypeDefinitionError::PrimaryDirectiveCannotRemapObject {
-                                        primary_type_name,
-                                        field_name: key.to_string(),
-                                    },
-                                    Location::generated(),
-                                ));
-                            }
-                        }
-                    }
-                }
-            }
-            None => {
-                return Err(WithLocation::new(
-                    ProcessTypeDefinitionError::PrimaryDirectiveFieldNotFound {
-                        primary_type_name,
-                        field_name: first,
-                    },
-                    Location::generated(),
-                ))
-            }
-        };
+        self.input_object
+            .inner_mut()
+            .remove_to_field(schema, first, rest, primary_type_name)?;
         Ok(())
     }
 }
 
-#[allow(dead_code)]
+/// The input object type of an argument or input object field whose fields are
+/// being remapped.
+fn input_object_id_to_modify(
+    input_value: &GraphQLInputValueDefinition,
+    schema: &UnvalidatedSchema,
+    primary_type_name: IsographObjectTypeName,
+) -> ProcessTypeDefinitionResult<InputObjectId> {
+    // TODO I think we have validated that the item exists already.
+    // But we should double check that, and return an error if necessary
+    let type_name: UnvalidatedTypeName = (*input_value.type_.inner()).into();
+    let defined_type_id = *schema
+        .schema_data
+        .defined_types
+        .get(&type_name)
+        .expect("Expected type to be defined by now. This is indicative of a bug in Isograph.");
+    match defined_type_id {
+        SelectableFieldId::InputObject(input_object_id) => Ok(input_object_id),
+        SelectableFieldId::Object(_)
+        | SelectableFieldId::Scalar(_)
+        | SelectableFieldId::Enum(_) => Err(WithLocation::new(
+            ProcessTypeDefinitionError::PrimaryDirectiveCannotRemapFieldsOfNonInputObject {
+                primary_type_name,
+                input_value_name: input_value.name.item,
+                type_name,
+            },
+            Location::generated(),
+        )),
+    }
+}
+
 enum IsEmpty {
     IsEmpty,
     NotEmpty,
@@ -10,8 +10,8 @@ use common_lang_types::{
 use graphql_lang_types::GraphQLInputValueDefinition;
 use intern::{string_key::Intern, Lookup};
 use isograph_lang_types::{
-    ClientFieldId, ObjectId, ScalarFieldSelection, SelectableFieldId, Selection,
-    SelectionFieldArgument, ServerFieldSelection, VariableDefinition,
+    ClientFieldId, FragmentDirectiveUsage, ObjectId, ScalarFieldSelection, SelectableFieldId,
+    Selection, SelectionFieldArgument, ServerFieldSelection, VariableDefinition,
 };
 
 use crate::{
@@ -36,21 +36,32 @@ pub struct RootRefetchedPath {
 pub enum MergedServerFieldSelection {
     ScalarField(MergedScalarFieldSelection),
     LinkedField(MergedLinkedFieldSelection),
+    InlineFragment(MergedInlineFragmentSelection),
 }
 
 impl MergedServerFieldSelection {
     pub fn reachable_variables(&self) -> HashSet<VariableName> {
         match self {
             MergedServerFieldSelection::ScalarField(scalar_field) => {
-                get_variable_selections(&scalar_field.arguments)
+                let mut reachable_variables = get_variable_selections(&scalar_field.arguments);
+                reachable_variables
+                    .extend(get_directive_variable_selections(&scalar_field.directives));
+                reachable_variables
             }
             MergedServerFieldSelection::LinkedField(linked_field) => {
                 let mut reachable_variables = get_variable_selections(&linked_field.arguments);
+                reachable_variables
+                    .extend(get_directive_variable_selections(&linked_field.directives));
                 for selection in linked_field.selection_set.iter() {
                     reachable_variables.extend(selection.item.reachable_variables());
                 }
                 reachable_variables
             }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => inline_fragment
+                .selection_set
+                .iter()
+                .flat_map(|selection| selection.item.reachable_variables())
+                .collect(),
         }
     }
 }
@@ -64,12 +75,48 @@ pub fn get_variable_selections(
         .collect()
 }
 
+fn get_directive_variable_selections(
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+) -> HashSet<VariableName> {
+    directives
+        .iter()
+        .flat_map(|directive| get_variable_selections(&directive.item.arguments))
+        .collect()
+}
+
+/// Whether two lists of directives are the same, ignoring their locations.
+fn directives_are_equivalent(
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+    other_directives: &[WithSpan<FragmentDirectiveUsage>],
+) -> bool {
+    directives.len() == other_directives.len()
+        && directives
+            .iter()
+            .zip(other_directives.iter())
+            .all(|(directive, other_directive)| {
+                directive.item.name.item == other_directive.item.name.item
+                    && directive.item.arguments.len() == other_directive.item.arguments.len()
+                    && directive
+                        .item
+                        .arguments
+                        .iter()
+                        .zip(other_directive.item.arguments.iter())
+                        .all(|(argument, other_argument)| {
+                            argument.item.name.item == other_argument.item.name.item
+                                && argument.item.value.item == other_argument.item.value.item
+                        })
+            })
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct MergedScalarFieldSelection {
     pub name: WithLocation<ScalarFieldName>,
     // TODO calculate this when needed
     pub normalization_alias: Option<WithLocation<ScalarFieldAlias>>,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    /// The `@skip` and `@include` directives under which this field is selected.
+    /// If the field is also selected without them, this is empty.
+    pub directives: Vec<WithSpan<FragmentDirectiveUsage>>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -79,6 +126,17 @@ pub struct MergedLinkedFieldSelection {
     pub normalization_alias: Option<WithLocation<LinkedFieldAlias>>,
     pub selection_set: Vec<WithSpan<MergedServerFieldSelection>>,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    /// The `@skip` and `@include` directives under which this field is selected.
+    /// If the field is also selected without them, this is empty.
+    pub directives: Vec<WithSpan<FragmentDirectiveUsage>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct MergedInlineFragmentSelection {
+    pub type_to_refine_to: IsographObjectTypeName,
+    /// The concrete types of the parent to which the type refinement applies.
+    pub concrete_types: Vec<IsographObjectTypeName>,
+    pub selection_set: Vec<WithSpan<MergedServerFieldSelection>>,
 }
 
 /// A merged selection set is an input for generating:
@@ -118,19 +176,7 @@ fn find_by_path(
     path: &PathToRefetchField,
 ) -> MergedSelectionSet {
     for item in path.linked_fields.iter() {
-        let linked_field_selection = root
-            .iter()
-            .find_map(|linked_field_selection| {
-                if let MergedServerFieldSelection::LinkedField(linked_field) =
-                    &linked_field_selection.item
-                {
-                    let linked_field_name: SelectableFieldName = linked_field.name.item.into();
-                    if linked_field_name == item.name {
-                        return Some(linked_field);
-                    }
-                }
-                None
-            })
+        let linked_field_selection = find_linked_field(root, item.name)
             .expect("Linked field not found. This is indicative of a bug in Isograph.");
 
         root = &linked_field_selection.selection_set;
@@ -140,6 +186,30 @@ fn find_by_path(
     MergedSelectionSet(root.to_vec())
 }
 
+/// Find a linked field in a selection set, including in the selection sets of
+/// its inline fragments (which select from the same object.)
+fn find_linked_field(
+    selections: &[WithSpan<MergedServerFieldSelection>],
+    name: SelectableFieldName,
+) -> Option<&MergedLinkedFieldSelection> {
+    selections
+        .iter()
+        .find_map(|selection| match &selection.item {
+            MergedServerFieldSelection::LinkedField(linked_field) => {
+                let linked_field_name: SelectableFieldName = linked_field.name.item.into();
+                if linked_field_name == name {
+                    Some(linked_field)
+                } else {
+                    None
+                }
+            }
+            MergedServerFieldSelection::ScalarField(_) => None,
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                find_linked_field(&inline_fragment.selection_set, name)
+            }
+        })
+}
+
 impl Into<Vec<WithSpan<MergedServerFieldSelection>>> for MergedSelectionSet {
     fn into(self) -> Vec<WithSpan<MergedServerFieldSelection>> {
         self.0
@@ -151,6 +221,7 @@ enum NormalizationKey {
     // __typename,
     Id,
     ServerField(NameAndArguments),
+    InlineFragment(IsographObjectTypeName),
 }
 
 #[derive(Debug)]
@@ -494,6 +565,59 @@ fn merge_selections_into_set(
                     merge_traversal_state.current_path.linked_fields.pop();
                 }
             },
+            Selection::TypeRefinement(type_refinement) => {
+                let refined_type = schema
+                    .schema_data
+                    .object(type_refinement.associated_data.parent_object_id);
+
+                match merged_selection_map
+                    .entry(NormalizationKey::InlineFragment(refined_type.name))
+                {
+                    Entry::Vacant(vacant_entry) => {
+                        let merged_set = create_merged_selection_set_with_merge_traversal_state(
+                            schema,
+                            refined_type,
+                            &type_refinement.selection_set,
+                            merge_traversal_state,
+                        );
+                        let concrete_types = schema
+                            .schema_data
+                            .concrete_type_ids_of_type_refinement(parent_type.id, refined_type.id)
+                            .into_iter()
+                            .map(|object_id| schema.schema_data.object(object_id).name)
+                            .collect();
+                        vacant_entry.insert(WithSpan::new(
+                            MergedServerFieldSelection::InlineFragment(
+                                MergedInlineFragmentSelection {
+                                    type_to_refine_to: refined_type.name,
+                                    concrete_types,
+                                    selection_set: merged_set.into(),
+                                },
+                            ),
+                            span,
+                        ));
+                    }
+                    Entry::Occupied(mut occupied) => match &mut occupied.get_mut().item {
+                        MergedServerFieldSelection::InlineFragment(existing_inline_fragment) => {
+                            HACK__merge_linked_fields(
+                                schema,
+                                &mut existing_inline_fragment.selection_set,
+                                &type_refinement.selection_set,
+                                refined_type,
+                                merge_traversal_state,
+                            );
+                        }
+                        MergedServerFieldSelection::ScalarField(_)
+                        | MergedServerFieldSelection::LinkedField(_) => {
+                            panic!("expected inline fragment, probably a bug in Isograph")
+                        }
+                    },
+                }
+
+                // The __typename field is used to determine which inline fragments
+                // apply to a given object.
+                select_typename_field_in_merged_selection(merged_selection_map);
+            }
         }
     }
 }
@@ -512,6 +636,7 @@ fn filter_id_fields(field: &&WithSpan<ValidatedSelection>) -> bool {
             }
             ServerFieldSelection::LinkedField(_) => true,
         },
+        Selection::TypeRefinement(_) => true,
     }
 }
 
@@ -538,6 +663,7 @@ fn merge_linked_field_into_vacant_entry(
             },
             arguments: new_linked_field.arguments.clone(),
             normalization_alias: new_linked_field.normalization_alias,
+            directives: new_linked_field.directives.clone(),
         }),
         span,
     ));
@@ -551,10 +677,19 @@ fn merge_linked_field_into_occupied_entry(
 ) {
     let existing_selection = occupied.get_mut();
     match &mut existing_selection.item {
-        MergedServerFieldSelection::ScalarField(_) => {
+        MergedServerFieldSelection::ScalarField(_)
+        | MergedServerFieldSelection::InlineFragment(_) => {
             panic!("expected linked, probably a bug in Isograph")
         }
         MergedServerFieldSelection::LinkedField(existing_linked_field) => {
+            // If the field is selected under different conditions, it must be
+            // selected unconditionally.
+            if !directives_are_equivalent(
+                &existing_linked_field.directives,
+                &new_linked_field.directives,
+            ) {
+                existing_linked_field.directives = vec![];
+            }
             let type_id = new_linked_field.associated_data.parent_object_id;
             let linked_field_parent_type = schema.schema_data.object(type_id);
             HACK__merge_linked_fields(
@@ -630,13 +765,23 @@ fn merge_scalar_server_field(
         &scalar_field.arguments,
     ));
     match merged_selection_set.entry(normalization_key) {
-        Entry::Occupied(occupied) => {
-            match occupied.get().item {
-                MergedServerFieldSelection::ScalarField(_) => {
+        Entry::Occupied(mut occupied) => {
+            match &mut occupied.get_mut().item {
+                MergedServerFieldSelection::ScalarField(existing_scalar_field) => {
                     // TODO check that the existing server field matches the one we
                     // would create.
+
+                    // If the field is selected under different conditions, it must be
+                    // selected unconditionally.
+                    if !directives_are_equivalent(
+                        &existing_scalar_field.directives,
+                        &scalar_field.directives,
+                    ) {
+                        existing_scalar_field.directives = vec![];
+                    }
                 }
-                MergedServerFieldSelection::LinkedField(_) => {
+                MergedServerFieldSelection::LinkedField(_)
+                | MergedServerFieldSelection::InlineFragment(_) => {
                     panic!("Unexpected linked field, probably a bug in Isograph")
                 }
             };
@@ -647,6 +792,7 @@ fn merge_scalar_server_field(
                     name: scalar_field.name,
                     arguments: scalar_field.arguments.clone(),
                     normalization_alias: scalar_field.normalization_alias,
+                    directives: scalar_field.directives.clone(),
                 }),
                 span,
             ));
@@ -713,6 +859,14 @@ fn HACK__merge_linked_fields(
                     ),
                 )
             }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => merged_selection_set
+                .insert(
+                    NormalizationKey::InlineFragment(inline_fragment.type_to_refine_to),
+                    WithSpan::new(
+                        MergedServerFieldSelection::InlineFragment(inline_fragment.clone()),
+                        span,
+                    ),
+                ),
         };
     }
 
@@ -753,7 +907,8 @@ fn select_typename_and_id_fields_in_merged_selection(
                         // TODO check that the existing server field matches the one we
                         // would create.
                     }
-                    MergedServerFieldSelection::LinkedField(_) => {
+                    MergedServerFieldSelection::LinkedField(_)
+                    | MergedServerFieldSelection::InlineFragment(_) => {
                         panic!("Unexpected linked field for id, probably a bug in Isograph")
                     }
                 };
@@ -769,6 +924,7 @@ fn select_typename_and_id_fields_in_merged_selection(
                         arguments: vec![],
                         // This indicates that there should be a separate MergedServerFieldSelection variant
                         normalization_alias: None,
+                        directives: vec![],
                     }),
                     Span::todo_generated(),
                 ));
@@ -776,3 +932,23 @@ fn select_typename_and_id_fields_in_merged_selection(
         }
     }
 }
+
+fn select_typename_field_in_merged_selection(merged_selection_map: &mut MergedSelectionMap) {
+    let typename_name: ScalarFieldName = "__typename".intern().into();
+    merged_selection_map
+        .entry(NormalizationKey::ServerField(name_and_arguments(
+            typename_name.into(),
+            &[],
+        )))
+        .or_insert_with(|| {
+            WithSpan::new(
+                MergedServerFieldSelection::ScalarField(MergedScalarFieldSelection {
+                    name: WithLocation::new(typename_name, Location::generated()),
+                    arguments: vec![],
+                    normalization_alias: None,
+                    directives: vec![],
+                }),
+                Span::todo_generated(),
+            )
+        });
+}
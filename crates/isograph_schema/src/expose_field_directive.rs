@@ -7,7 +7,6 @@ use graphql_lang_types::{
     GraphQLInputValueDefinition,
 };
 use intern::{string_key::Intern, Lookup};
-use isograph_config::ConfigOptions;
 use isograph_lang_types::{
     ClientFieldId, ObjectId, ScalarFieldSelection, SelectableFieldId, Selection, ServerFieldId,
     ServerFieldSelection,
@@ -77,7 +76,6 @@ impl UnvalidatedSchema {
     pub fn create_mutation_fields_from_expose_field_directives(
         &mut self,
         mutation_id: ObjectId,
-        options: ConfigOptions,
     ) -> ProcessTypeDefinitionResult<()> {
         // TODO don't clone if possible
         let mutation_object = self.schema_data.object(mutation_id);
@@ -99,7 +97,6 @@ impl UnvalidatedSchema {
                 expose_field_directive,
                 mutation_object_name,
                 mutation_id,
-                options,
             )?;
         }
 
@@ -111,7 +108,6 @@ impl UnvalidatedSchema {
         expose_field_directive: &ExposeFieldDirective,
         mutation_object_name: IsographObjectTypeName,
         mutation_id: ObjectId,
-        options: ConfigOptions,
     ) -> Result<(), WithLocation<ProcessTypeDefinitionError>> {
         let ExposeFieldDirective {
             expose_as,
@@ -128,6 +124,7 @@ impl UnvalidatedSchema {
         let server_schema_mutation_field_name = mutation_field.name.item;
         let mutation_field_arguments = mutation_field.arguments.clone();
         let description = mutation_field.description.clone();
+        let deprecation_reason = mutation_field.deprecation_reason();
         let payload_id = self
             .schema_data
             .defined_types
@@ -145,7 +142,6 @@ impl UnvalidatedSchema {
                     mutation_field_name,
                     // TODO don't clone
                     field_map.clone(),
-                    options,
                 )?;
 
             // payload object is the object type of the mutation field, e.g. SetBestFriendResponse
@@ -203,6 +199,7 @@ impl UnvalidatedSchema {
                         unwraps: vec![],
                         // TODO what about arguments? How would we handle them?
                         arguments: vec![],
+                        directives: vec![],
                     };
 
                     WithSpan::new(
@@ -229,6 +226,7 @@ impl UnvalidatedSchema {
                     filtered_mutation_field_arguments: mutation_field_args_without_id.to_vec(),
                     mutation_primary_field_return_type_object_id: maybe_abstract_parent_object_id,
                 }),
+                deprecation_reason,
                 variable_definitions: vec![],
                 type_and_field: ObjectTypeAndFieldNames {
                     // TODO make this zero cost?
@@ -346,7 +344,6 @@ fn skip_arguments_contained_in_field_map(
     mutation_object_name: IsographObjectTypeName,
     mutation_field_name: SelectableFieldName,
     field_map_items: Vec<FieldMapItem>,
-    options: ConfigOptions,
 ) -> ProcessTypeDefinitionResult<(
     Vec<WithLocation<GraphQLInputValueDefinition>>,
     Vec<ProcessedFieldMapItem>,
@@ -368,7 +365,7 @@ fn skip_arguments_contained_in_field_map(
     }
 
     Ok((
-        argument_map.into_arguments(schema, options),
+        argument_map.into_arguments(schema),
         processed_field_map_items,
     ))
 }
@@ -1,23 +1,28 @@
 use std::{collections::HashMap, fmt::Debug};
 
 use common_lang_types::{
-    ConstExportName, DescriptionValue, FieldArgumentName, FilePath, GraphQLInterfaceTypeName,
-    GraphQLScalarTypeName, HasName, InputTypeName, IsographObjectTypeName, JavascriptName,
-    SelectableFieldName, UnvalidatedTypeName, WithLocation, WithSpan,
+    ConstExportName, DescriptionValue, DirectiveName, EnumLiteralValue, FieldArgumentName,
+    FilePath, GraphQLEnumTypeName, GraphQLInputObjectTypeName, GraphQLInterfaceTypeName,
+    GraphQLScalarTypeName, HasName, InputTypeName, InputValueName, IsographObjectTypeName,
+    JavascriptName, SelectableFieldName, StringLiteralValue, UnvalidatedTypeName, WithLocation,
+    WithSpan,
 };
 use graphql_lang_types::{
-    ConstantValue, GraphQLDirective, GraphQLFieldDefinition, GraphQLInputObjectTypeDefinition,
-    GraphQLInputValueDefinition, GraphQLInterfaceTypeDefinition, GraphQLObjectTypeDefinition,
-    NamedTypeAnnotation, TypeAnnotation,
+    ConstantValue, GraphQLDirective, GraphQLEnumValueDefinition, GraphQLFieldDefinition,
+    GraphQLInputValueDefinition, GraphQLInterfaceTypeDefinition, GraphQLInterfaceTypeExtension,
+    GraphQLObjectTypeDefinition, GraphQLObjectTypeExtension, NamedTypeAnnotation,
+    RootOperationKind, TypeAnnotation,
 };
-use intern::string_key::Intern;
+use intern::{string_key::Intern, Lookup};
+use isograph_config::CustomScalarTypeImportPath;
 use isograph_lang_types::{
-    ClientFieldId, LinkedFieldSelection, NonConstantValue, ObjectId, ScalarId, SelectableFieldId,
-    Selection, ServerFieldId, ServerStrongIdFieldId, Unwrap, VariableDefinition,
+    ClientFieldId, EnumId, InputObjectId, LinkedFieldSelection, NonConstantValue, ObjectId,
+    ScalarId, SelectableFieldId, Selection, ServerFieldId, ServerStrongIdFieldId, Unwrap,
+    VariableDefinition,
 };
 use lazy_static::lazy_static;
 
-use crate::{ClientFieldVariant, FieldMapItem};
+use crate::{ClientFieldVariant, FieldMapItem, TypeRefinementMap};
 
 lazy_static! {
     pub static ref ID_GRAPHQL_TYPE: GraphQLScalarTypeName = "ID".intern().into();
@@ -25,6 +30,10 @@ lazy_static! {
     // TODO these shouldn't be SelectableFieldName's
     pub static ref READER: SelectableFieldName = "reader".intern().into();
     pub static ref ENTRYPOINT: SelectableFieldName = "entrypoint".intern().into();
+    pub static ref DEPRECATED_DIRECTIVE: DirectiveName = "deprecated".intern().into();
+    /// The reason given to fields that are marked @deprecated without one, per the GraphQL spec.
+    pub static ref DEFAULT_DEPRECATION_REASON: StringLiteralValue =
+        "No longer supported".intern().into();
 }
 
 /// A trait that encapsulates all the types over which a schema, fields, etc.
@@ -98,8 +107,8 @@ pub struct Schema<TValidation: SchemaValidationState> {
     // typename
     // TODO name this root query type?
     pub query_type_id: Option<ObjectId>,
-    // Subscription
-    // Mutation
+    pub subscription_type_id: Option<ObjectId>,
+    pub mutation_type_id: Option<ObjectId>,
 }
 
 /// Distinguishes between server-defined fields and locally-defined fields.
@@ -136,11 +145,16 @@ impl<TFieldAssociatedData, TResolverType>
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SchemaData<TEncounteredField> {
     pub objects: Vec<SchemaObject<TEncounteredField>>,
     pub scalars: Vec<SchemaScalar>,
+    pub enums: Vec<SchemaEnum>,
+    pub input_objects: Vec<SchemaInputObject>,
     pub defined_types: HashMap<UnvalidatedTypeName, SelectableFieldId>,
+    /// For each interface or union, the object types that implement it or
+    /// are members of it, i.e. the types that it can be refined to.
+    pub supertype_to_subtype_map: TypeRefinementMap,
 }
 
 impl<TValidation: SchemaValidationState> Schema<TValidation> {
@@ -170,6 +184,20 @@ impl<TValidation: SchemaValidationState> Schema<TValidation> {
             .as_ref()
             .map(|id| self.schema_data.object(*id))
     }
+
+    /// If the object is a root type that entrypoints can be defined on, the
+    /// kind of operation with which it is fetched.
+    pub fn fetchable_operation_kind(&self, object_id: ObjectId) -> Option<RootOperationKind> {
+        if self.query_type_id == Some(object_id) {
+            Some(RootOperationKind::Query)
+        } else if self.subscription_type_id == Some(object_id) {
+            Some(RootOperationKind::Subscription)
+        } else if self.mutation_type_id == Some(object_id) {
+            Some(RootOperationKind::Mutation)
+        } else {
+            None
+        }
+    }
 }
 
 impl<
@@ -215,6 +243,21 @@ impl<TEncounteredField> SchemaData<TEncounteredField> {
         &self.scalars[scalar_id.as_usize()]
     }
 
+    /// Get a reference to a given enum type by its id.
+    pub fn enum_(&self, enum_id: EnumId) -> &SchemaEnum {
+        &self.enums[enum_id.as_usize()]
+    }
+
+    /// Get a reference to a given input object type by its id.
+    pub fn input_object(&self, input_object_id: InputObjectId) -> &SchemaInputObject {
+        &self.input_objects[input_object_id.as_usize()]
+    }
+
+    /// Get a mutable reference to a given input object type by its id.
+    pub fn input_object_mut(&mut self, input_object_id: InputObjectId) -> &mut SchemaInputObject {
+        &mut self.input_objects[input_object_id.as_usize()]
+    }
+
     pub fn lookup_unvalidated_type(
         &self,
         type_id: SelectableFieldId,
@@ -226,6 +269,10 @@ impl<TEncounteredField> SchemaData<TEncounteredField> {
             SelectableFieldId::Scalar(id) => {
                 SchemaType::Scalar(self.scalars.get(id.as_usize()).unwrap())
             }
+            SelectableFieldId::Enum(id) => SchemaType::Enum(self.enums.get(id.as_usize()).unwrap()),
+            SelectableFieldId::InputObject(id) => {
+                SchemaType::InputObject(self.input_objects.get(id.as_usize()).unwrap())
+            }
         }
     }
 
@@ -238,12 +285,45 @@ impl<TEncounteredField> SchemaData<TEncounteredField> {
     pub fn object_mut(&mut self, object_id: ObjectId) -> &mut SchemaObject<TEncounteredField> {
         &mut self.objects[object_id.as_usize()]
     }
+
+    /// The concrete types (i.e. object types, as opposed to interfaces and unions)
+    /// that an object of the given type can have, including the type itself if it is
+    /// concrete.
+    pub fn concrete_type_ids(&self, object_id: ObjectId) -> Vec<ObjectId> {
+        std::iter::once(object_id)
+            .chain(
+                self.supertype_to_subtype_map
+                    .get(&object_id)
+                    .into_iter()
+                    .flatten()
+                    .copied(),
+            )
+            .filter(|object_id| self.object(*object_id).kind == SchemaObjectKind::Object)
+            .collect()
+    }
+
+    /// The concrete types that an object of the parent type can have, and to which
+    /// a type refinement to the refined type applies. At runtime, the type refinement
+    /// applies if an object's __typename is one of these types.
+    pub fn concrete_type_ids_of_type_refinement(
+        &self,
+        parent_object_id: ObjectId,
+        refined_object_id: ObjectId,
+    ) -> Vec<ObjectId> {
+        let refined_concrete_type_ids = self.concrete_type_ids(refined_object_id);
+        self.concrete_type_ids(parent_object_id)
+            .into_iter()
+            .filter(|object_id| refined_concrete_type_ids.contains(object_id))
+            .collect()
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
 pub enum SchemaType<'a, TEncounteredField> {
     Object(&'a SchemaObject<TEncounteredField>),
     Scalar(&'a SchemaScalar),
+    Enum(&'a SchemaEnum),
+    InputObject(&'a SchemaInputObject),
 }
 
 impl<'a, T> HasName for SchemaType<'a, T> {
@@ -253,6 +333,8 @@ impl<'a, T> HasName for SchemaType<'a, T> {
         match self {
             SchemaType::Object(object) => object.name.into(),
             SchemaType::Scalar(scalar) => scalar.name.item.into(),
+            SchemaType::Enum(enum_) => enum_.name.item.into(),
+            SchemaType::InputObject(input_object) => input_object.name.item.into(),
         }
     }
 }
@@ -261,14 +343,15 @@ impl<'a, T> HasName for SchemaType<'a, T> {
 pub enum SchemaOutputType<'a, TValidation: SchemaValidationState> {
     Object(&'a SchemaObject<TValidation>),
     Scalar(&'a SchemaScalar),
+    Enum(&'a SchemaEnum),
     // excludes input object
 }
 
 #[derive(Clone, Copy, Debug)]
 pub enum SchemaInputType<'a> {
     Scalar(&'a SchemaScalar),
-    // input object
-    // enum
+    Enum(&'a SchemaEnum),
+    InputObject(&'a SchemaInputObject),
 }
 
 impl<'a> HasName for SchemaInputType<'a> {
@@ -277,6 +360,8 @@ impl<'a> HasName for SchemaInputType<'a> {
     fn name(&self) -> Self::Name {
         match self {
             SchemaInputType::Scalar(x) => x.name.item.into(),
+            SchemaInputType::Enum(x) => x.name.item.into(),
+            SchemaInputType::InputObject(x) => x.name.item.into(),
         }
     }
 }
@@ -319,36 +404,74 @@ impl From<GraphQLInterfaceTypeDefinition> for IsographObjectTypeDefinition {
     }
 }
 
-// TODO this is bad. We should instead convert both GraphQL types to a common
-// Isograph type
-impl From<GraphQLInputObjectTypeDefinition> for IsographObjectTypeDefinition {
-    fn from(value: GraphQLInputObjectTypeDefinition) -> Self {
+/// An extension of an object or interface, which adds interfaces,
+/// directives and fields to a type that has already been defined.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct IsographObjectTypeExtension {
+    pub name: WithLocation<IsographObjectTypeName>,
+    pub interfaces: Vec<WithLocation<GraphQLInterfaceTypeName>>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+    pub fields: Vec<WithLocation<GraphQLFieldDefinition>>,
+}
+
+impl From<GraphQLObjectTypeExtension> for IsographObjectTypeExtension {
+    fn from(object_type_extension: GraphQLObjectTypeExtension) -> Self {
+        IsographObjectTypeExtension {
+            name: object_type_extension.name.map(|x| x.into()),
+            interfaces: object_type_extension.interfaces,
+            directives: object_type_extension.directives,
+            fields: object_type_extension.fields,
+        }
+    }
+}
+
+impl From<GraphQLInterfaceTypeExtension> for IsographObjectTypeExtension {
+    fn from(value: GraphQLInterfaceTypeExtension) -> Self {
         Self {
-            description: value.description,
             name: value.name.map(|x| x.into()),
-            interfaces: vec![],
+            interfaces: value.interfaces,
             directives: value.directives,
-            fields: value
-                .fields
-                .into_iter()
-                .map(|with_location| with_location.map(From::from))
-                .collect(),
+            fields: value.fields,
+        }
+    }
+}
+
+/// Objects, interfaces and unions are all represented as SchemaObject's.
+/// SchemaObjectKind records which one a given SchemaObject is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaObjectKind {
+    Object,
+    Interface,
+    Union,
+}
+
+impl SchemaObjectKind {
+    /// e.g. "an interface", for use in error messages
+    pub fn description(&self) -> &'static str {
+        match self {
+            SchemaObjectKind::Object => "an object",
+            SchemaObjectKind::Interface => "an interface",
+            SchemaObjectKind::Union => "a union",
         }
     }
 }
 
 /// An object type in the schema.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SchemaObject<TEncounteredField> {
     pub description: Option<DescriptionValue>,
     pub name: IsographObjectTypeName,
     pub id: ObjectId,
+    pub kind: SchemaObjectKind,
     // We probably don't want this
     pub directives: Vec<GraphQLDirective<ConstantValue>>,
     /// TODO remove id_field from fields, and change the type of Option<ServerFieldId>
     /// to something else.
     pub id_field: Option<ServerStrongIdFieldId>,
     pub server_fields: Vec<ServerFieldId>,
+    /// The interfaces that this type declares that it implements, and the locations
+    /// at which it does so.
+    pub implemented_interfaces: Vec<WithLocation<ObjectId>>,
     pub client_field_ids: Vec<ClientFieldId>,
     pub encountered_fields: HashMap<SelectableFieldName, TEncounteredField>,
 }
@@ -371,11 +494,27 @@ pub struct SchemaServerField<TData> {
     pub id: ServerFieldId,
     pub associated_data: TData,
     pub parent_type_id: ObjectId,
-    // pub directives: Vec<Directive<ConstantValue>>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
     pub arguments: Vec<WithLocation<GraphQLInputValueDefinition>>,
 }
 
 impl<TData> SchemaServerField<TData> {
+    /// If this field is marked @deprecated, the reason it is deprecated.
+    pub fn deprecation_reason(&self) -> Option<StringLiteralValue> {
+        let deprecated_directive = self
+            .directives
+            .iter()
+            .find(|directive| directive.name.item == *DEPRECATED_DIRECTIVE)?;
+        Some(
+            deprecated_directive
+                .arguments
+                .iter()
+                .find(|argument| argument.name.item.lookup() == "reason")
+                .and_then(|argument| argument.value.item.as_string())
+                .unwrap_or(*DEFAULT_DEPRECATION_REASON),
+        )
+    }
+
     pub fn and_then<TData2, E>(
         &self,
         convert: impl FnOnce(&TData) -> Result<TData2, E>,
@@ -386,6 +525,7 @@ impl<TData> SchemaServerField<TData> {
             id: self.id,
             associated_data: convert(&self.associated_data)?,
             parent_type_id: self.parent_type_id,
+            directives: self.directives.clone(),
             arguments: self.arguments.clone(),
         })
     }
@@ -467,7 +607,7 @@ pub struct MutationFieldResolverActionKindInfo {
     pub field_map: Vec<FieldMapItem>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ClientField<
     TResolverSelectionScalarFieldAssociatedData,
     TResolverSelectionLinkedFieldAssociatedData,
@@ -497,6 +637,9 @@ pub struct ClientField<
     // TODO we should probably model this differently
     pub variant: ClientFieldVariant,
 
+    /// If this client field is marked @deprecated, the reason it is deprecated.
+    pub deprecation_reason: Option<StringLiteralValue>,
+
     pub action_kind: ClientFieldActionKind,
 
     pub variable_definitions:
@@ -551,6 +694,7 @@ impl<T> SchemaServerField<T> {
             id,
             associated_data,
             parent_type_id,
+            directives,
             arguments,
         } = self;
         (
@@ -560,6 +704,7 @@ impl<T> SchemaServerField<T> {
                 id,
                 associated_data: (),
                 parent_type_id,
+                directives,
                 arguments,
             },
             associated_data,
@@ -568,10 +713,48 @@ impl<T> SchemaServerField<T> {
 }
 
 /// A scalar type in the schema.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SchemaScalar {
     pub description: Option<WithSpan<DescriptionValue>>,
     pub name: WithLocation<GraphQLScalarTypeName>,
     pub id: ScalarId,
     pub javascript_name: JavascriptName,
+    /// Where javascript_name is imported from, if it is not a built-in type
+    pub javascript_import_path: Option<CustomScalarTypeImportPath>,
+}
+
+/// An enum type in the schema.
+#[derive(Debug, Clone)]
+pub struct SchemaEnum {
+    pub description: Option<WithSpan<DescriptionValue>>,
+    pub name: WithLocation<GraphQLEnumTypeName>,
+    pub id: EnumId,
+    pub values: Vec<WithLocation<GraphQLEnumValueDefinition>>,
+}
+
+impl SchemaEnum {
+    pub fn has_value(&self, value: EnumLiteralValue) -> bool {
+        self.values
+            .iter()
+            .any(|enum_value_definition| enum_value_definition.item.value.item == value)
+    }
+}
+
+/// An input object type in the schema. Unlike objects, input objects cannot be
+/// selected; they can only be the types of arguments, variables and input object fields.
+#[derive(Debug, Clone)]
+pub struct SchemaInputObject {
+    pub description: Option<WithSpan<DescriptionValue>>,
+    pub name: WithLocation<GraphQLInputObjectTypeName>,
+    pub id: InputObjectId,
+    pub fields: Vec<WithLocation<GraphQLInputValueDefinition>>,
+}
+
+impl SchemaInputObject {
+    pub fn field(&self, field_name: InputValueName) -> Option<&GraphQLInputValueDefinition> {
+        self.fields
+            .iter()
+            .map(|field| &field.item)
+            .find(|field| field.name.item == field_name)
+    }
 }
@@ -9,6 +9,7 @@ pub(crate) mod refetched_paths;
 mod root_types;
 mod unvalidated_schema;
 mod validate_entrypoint;
+mod validate_interface_implementations;
 mod validate_schema;
 
 use argument_map::*;
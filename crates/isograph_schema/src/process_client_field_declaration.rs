@@ -1,20 +1,20 @@
 use std::fmt;
 
 use common_lang_types::{
-    IsographDirectiveName, IsographObjectTypeName, Location, SelectableFieldName, TextSource,
-    UnvalidatedTypeName, WithLocation, WithSpan,
+    ErrorCode, IsographDirectiveName, IsographObjectTypeName, Location, SelectableFieldName,
+    StringLiteralValue, TextSource, UnvalidatedTypeName, WithLocation, WithSpan,
 };
 use graphql_lang_types::GraphQLInputValueDefinition;
-use intern::string_key::Intern;
+use intern::{string_key::Intern, Lookup};
 use isograph_lang_types::{
-    ClientFieldDeclaration, FragmentDirectiveUsage, ObjectId, SelectableFieldId,
+    ClientFieldDeclaration, FragmentDirectiveUsage, NonConstantValue, ObjectId, SelectableFieldId,
 };
 use lazy_static::lazy_static;
 use thiserror::Error;
 
 use crate::{
     ClientField, ClientFieldActionKind, FieldDefinitionLocation, ObjectTypeAndFieldNames,
-    UnvalidatedSchema,
+    UnvalidatedSchema, DEFAULT_DEPRECATION_REASON, DEPRECATED_DIRECTIVE,
 };
 
 impl UnvalidatedSchema {
@@ -43,11 +43,32 @@ impl UnvalidatedSchema {
                 let scalar_name = self.schema_data.scalars[scalar_id.as_usize()].name;
                 return Err(WithLocation::new(
                     ProcessClientFieldDeclarationError::InvalidParentType {
+                        parent_type: "a scalar",
                         parent_type_name: scalar_name.item.into(),
                     },
                     Location::new(text_source, client_field_declaration.item.parent_type.span),
                 ));
             }
+            SelectableFieldId::Enum(enum_id) => {
+                let enum_name = self.schema_data.enum_(*enum_id).name;
+                return Err(WithLocation::new(
+                    ProcessClientFieldDeclarationError::InvalidParentType {
+                        parent_type: "an enum",
+                        parent_type_name: enum_name.item.into(),
+                    },
+                    Location::new(text_source, client_field_declaration.item.parent_type.span),
+                ));
+            }
+            SelectableFieldId::InputObject(input_object_id) => {
+                let input_object_name = self.schema_data.input_object(*input_object_id).name;
+                return Err(WithLocation::new(
+                    ProcessClientFieldDeclarationError::InvalidParentType {
+                        parent_type: "an input object",
+                        parent_type_name: input_object_name.item.into(),
+                    },
+                    Location::new(text_source, client_field_declaration.item.parent_type.span),
+                ));
+            }
         }
 
         Ok(())
@@ -87,6 +108,7 @@ impl UnvalidatedSchema {
 
         let name = client_field_declaration.item.client_field_name.item.into();
         let variant = get_resolver_variant(&client_field_declaration.item.directives);
+        let deprecation_reason = get_deprecation_reason(&client_field_declaration.item.directives);
         let action_kind = ClientFieldActionKind::NamedImport((
             client_field_declaration.item.const_export_name,
             client_field_declaration.item.definition_path,
@@ -103,11 +125,15 @@ impl UnvalidatedSchema {
         }
 
         self.client_fields.push(ClientField {
-            description: None,
+            description: client_field_declaration
+                .item
+                .description
+                .map(|description| description.item),
             name,
             id: next_resolver_id,
             selection_set_and_unwraps: client_field_declaration.item.selection_set_and_unwraps,
             variant,
+            deprecation_reason,
             variable_definitions: client_field_declaration.item.variable_definitions,
             type_and_field: ObjectTypeAndFieldNames {
                 type_name: object.name,
@@ -130,9 +156,10 @@ pub enum ProcessClientFieldDeclarationError {
         parent_type_name: UnvalidatedTypeName,
     },
 
-    #[error("Invalid parent type. `{parent_type_name}` is a scalar. You are attempting to define a field on it. \
+    #[error("Invalid parent type. `{parent_type_name}` is {parent_type}. You are attempting to define a field on it. \
         In order to do so, the parent object must be an object, interface or union.")]
     InvalidParentType {
+        parent_type: &'static str,
         parent_type_name: UnvalidatedTypeName,
     },
 
@@ -151,6 +178,25 @@ pub enum ProcessClientFieldDeclarationError {
     ComponentResolverMissingJsFunction,
 }
 
+impl ErrorCode for ProcessClientFieldDeclarationError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            ProcessClientFieldDeclarationError::ParentTypeNotDefined { .. } => {
+                "process_client_field_declaration/parent_type_not_defined"
+            }
+            ProcessClientFieldDeclarationError::InvalidParentType { .. } => {
+                "process_client_field_declaration/invalid_parent_type"
+            }
+            ProcessClientFieldDeclarationError::ParentAlreadyHasField { .. } => {
+                "process_client_field_declaration/parent_already_has_field"
+            }
+            ProcessClientFieldDeclarationError::ComponentResolverMissingJsFunction => {
+                "process_client_field_declaration/component_resolver_missing_js_function"
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct MutationFieldClientFieldVariant {
     pub mutation_field_name: SelectableFieldName,
@@ -192,3 +238,28 @@ fn get_resolver_variant(directives: &[WithSpan<FragmentDirectiveUsage>]) -> Clie
     }
     return ClientFieldVariant::Eager;
 }
+
+/// Client fields can be marked `@deprecated`, optionally with a reason, just like
+/// server fields.
+fn get_deprecation_reason(
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+) -> Option<StringLiteralValue> {
+    let deprecated_directive = directives
+        .iter()
+        .find(|directive| directive.item.name.item.lookup() == DEPRECATED_DIRECTIVE.lookup())?;
+    Some(
+        deprecated_directive
+            .item
+            .arguments
+            .iter()
+            .find_map(|argument| match argument.item.value.item {
+                NonConstantValue::String(reason)
+                    if argument.item.name.item.lookup() == "reason" =>
+                {
+                    Some(reason)
+                }
+                _ => None,
+            })
+            .unwrap_or(*DEFAULT_DEPRECATION_REASON),
+    )
+}
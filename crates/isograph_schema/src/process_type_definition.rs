@@ -1,27 +1,31 @@
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 use crate::{
     ClientField, ClientFieldActionKind, ClientFieldVariant, EncounteredRootTypes,
-    FieldDefinitionLocation, IsographObjectTypeDefinition, ObjectTypeAndFieldNames,
-    ProcessedRootTypes, RootTypes, Schema, SchemaObject, SchemaScalar, SchemaServerField,
-    UnvalidatedClientField, UnvalidatedObjectFieldInfo, UnvalidatedSchema, UnvalidatedSchemaField,
-    ID_GRAPHQL_TYPE, STRING_JAVASCRIPT_TYPE,
+    FieldDefinitionLocation, IsographObjectTypeDefinition, IsographObjectTypeExtension,
+    ObjectTypeAndFieldNames, ProcessedRootTypes, RootTypes, Schema, SchemaEnum, SchemaInputObject,
+    SchemaObject, SchemaObjectKind, SchemaScalar, SchemaServerField, UnvalidatedClientField,
+    UnvalidatedObjectFieldInfo, UnvalidatedSchema, UnvalidatedSchemaField, ID_GRAPHQL_TYPE,
+    STRING_JAVASCRIPT_TYPE,
 };
 use common_lang_types::{
-    GraphQLObjectTypeName, GraphQLScalarTypeName, IsographObjectTypeName, Location,
+    Diagnostic, EnumLiteralValue, ErrorCode, GraphQLEnumTypeName, GraphQLInputObjectTypeName,
+    GraphQLObjectTypeName, GraphQLScalarTypeName, InputValueName, IsographObjectTypeName, Location,
     SelectableFieldName, Span, StringLiteralValue, UnvalidatedTypeName, WithLocation, WithSpan,
 };
 use graphql_lang_types::{
-    GraphQLFieldDefinition, GraphQLScalarTypeDefinition, GraphQLTypeSystemDefinition,
+    GraphQLEnumDefinition, GraphQLEnumTypeExtension, GraphQLFieldDefinition,
+    GraphQLInputObjectTypeDefinition, GraphQLInputObjectTypeExtension, GraphQLInputValueDefinition,
+    GraphQLScalarTypeDefinition, GraphQLSchemaExtension, GraphQLTypeSystemDefinition,
     GraphQLTypeSystemDocument, GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
-    GraphQLTypeSystemExtensionOrDefinition, NamedTypeAnnotation, NonNullTypeAnnotation,
-    RootOperationKind, TypeAnnotation,
+    GraphQLTypeSystemExtensionOrDefinition, GraphQLUnionTypeExtension, NamedTypeAnnotation,
+    NonNullTypeAnnotation, RootOperationKind, TypeAnnotation,
 };
 use intern::{string_key::Intern, Lookup};
-use isograph_config::ConfigOptions;
+use isograph_config::{ConfigOptions, CustomScalarType};
 use isograph_lang_types::{
-    ClientFieldId, ObjectId, ScalarFieldSelection, SelectableFieldId, Selection, ServerFieldId,
-    ServerFieldSelection, ServerStrongIdFieldId,
+    ClientFieldId, InputObjectId, ObjectId, ScalarFieldSelection, SelectableFieldId, Selection,
+    ServerFieldId, ServerFieldSelection, ServerStrongIdFieldId,
 };
 use lazy_static::lazy_static;
 use serde::Deserialize;
@@ -30,6 +34,7 @@ use thiserror::Error;
 lazy_static! {
     static ref QUERY_TYPE: IsographObjectTypeName = "Query".intern().into();
     static ref MUTATION_TYPE: IsographObjectTypeName = "Mutation".intern().into();
+    static ref SUBSCRIPTION_TYPE: IsographObjectTypeName = "Subscription".intern().into();
 }
 
 // When parsing, we have the subtype's ObjectId, but only the Supertype's name
@@ -60,11 +65,12 @@ impl UnvalidatedSchema {
     pub fn process_graphql_type_system_document(
         &mut self,
         type_system_document: GraphQLTypeSystemDocument,
-        options: ConfigOptions,
+        options: &ConfigOptions,
+        warnings: &mut Vec<Diagnostic>,
     ) -> ProcessTypeDefinitionResult<ProcessGraphQLDocumentOutcome> {
         // In the schema, interfaces, unions and objects are the same type of object (SchemaType),
         // with e.g. interfaces "simply" being objects that can be refined to other
-        // concrete objects.
+        // concrete objects. Input objects cannot be selected, so they are modeled separately.
         //
         // Processing type system documents is done in two passes:
         // - First, create types for interfaces, objects, scalars, etc.
@@ -79,6 +85,7 @@ impl UnvalidatedSchema {
             subscription: None,
         };
         let mut processed_root_types = None;
+        let mut union_member_types = vec![];
 
         for with_location in type_system_document.0 {
             let WithLocation {
@@ -87,22 +94,29 @@ impl UnvalidatedSchema {
             } = with_location;
             match type_system_definition {
                 GraphQLTypeSystemDefinition::ObjectTypeDefinition(object_type_definition) => {
-                    let object_type_definition = object_type_definition.into();
+                    let object_type_definition: IsographObjectTypeDefinition =
+                        object_type_definition.into();
+                    let name_location = object_type_definition.name.location;
 
                     let outcome = self.process_object_type_definition(
                         object_type_definition,
                         &mut supertype_to_subtype_map,
                         &mut subtype_to_supertype_map,
-                        true,
+                        SchemaObjectKind::Object,
                         options,
+                        warnings,
                     )?;
                     if let Some(encountered_root_kind) = outcome.encountered_root_kind {
-                        encountered_root_types
-                            .set_root_type(encountered_root_kind, outcome.object_id);
+                        encountered_root_types.set_root_type(
+                            encountered_root_kind,
+                            WithLocation::new(outcome.object_id, name_location),
+                        );
                     }
                 }
                 GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar_type_definition) => {
-                    self.process_scalar_definition(scalar_type_definition)?;
+                    let custom_scalar_type =
+                        get_custom_scalar_type(&scalar_type_definition, options, warnings)?;
+                    self.process_scalar_definition(scalar_type_definition, custom_scalar_type)?;
                     // N.B. we assume that Mutation will be an object, not a scalar
                 }
                 GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface_type_definition) => {
@@ -110,37 +124,28 @@ impl UnvalidatedSchema {
                         interface_type_definition.into(),
                         &mut supertype_to_subtype_map,
                         &mut subtype_to_supertype_map,
-                        true,
+                        SchemaObjectKind::Interface,
                         options,
+                        warnings,
                     )?;
                     // N.B. we assume that Mutation will be an object, not an interface
                 }
                 GraphQLTypeSystemDefinition::InputObjectTypeDefinition(
                     input_object_type_definition,
                 ) => {
-                    self.process_object_type_definition(
-                        input_object_type_definition.into(),
-                        &mut supertype_to_subtype_map,
-                        &mut subtype_to_supertype_map,
-                        false,
-                        options,
-                    )?;
+                    self.process_input_object_definition(input_object_type_definition)?;
                 }
                 GraphQLTypeSystemDefinition::DirectiveDefinition(_) => {
                     // For now, Isograph ignores directive definitions,
                     // but it might choose to allow-list them.
                 }
                 GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => {
-                    // TODO Do not do this
-                    self.process_scalar_definition(GraphQLScalarTypeDefinition {
-                        description: enum_definition.description,
-                        name: enum_definition.name.map(|x| x.lookup().intern().into()),
-                        directives: enum_definition.directives,
-                    })?;
+                    self.process_enum_definition(enum_definition)?;
                 }
                 GraphQLTypeSystemDefinition::UnionTypeDefinition(union_definition) => {
-                    // TODO do something reasonable here, once we add support for type refinements.
-                    self.process_object_type_definition(
+                    // Unions are objects without fields (except __typename), whose members
+                    // are subtypes that can be refined to.
+                    let outcome = self.process_object_type_definition(
                         IsographObjectTypeDefinition {
                             description: union_definition.description,
                             name: union_definition.name.map(|x| x.into()),
@@ -150,9 +155,14 @@ impl UnvalidatedSchema {
                         },
                         &mut supertype_to_subtype_map,
                         &mut subtype_to_supertype_map,
-                        true,
+                        SchemaObjectKind::Union,
                         options,
+                        warnings,
                     )?;
+                    // Members can be defined after the union, so they are looked up
+                    // once all types have been processed.
+                    union_member_types
+                        .push((outcome.object_id, union_definition.union_member_types));
                 }
                 GraphQLTypeSystemDefinition::SchemaDefinition(schema_definition) => {
                     if processed_root_types.is_some() {
@@ -170,32 +180,274 @@ impl UnvalidatedSchema {
             }
         }
 
-        let type_refinement_map =
-            self.get_type_refinement_map(supertype_to_subtype_map, subtype_to_supertype_map)?;
+        let mut interface_implementations = vec![];
+        let mut type_refinement_map = self.get_type_refinement_map(
+            supertype_to_subtype_map,
+            subtype_to_supertype_map,
+            &mut interface_implementations,
+        )?;
+        for (union_id, member_types) in union_member_types {
+            self.add_union_member_types(union_id, member_types, &mut type_refinement_map)?;
+        }
+        for (supertype_id, subtype_ids) in &type_refinement_map.supertype_to_subtype_map {
+            self.schema_data
+                .supertype_to_subtype_map
+                .entry(*supertype_id)
+                .or_default()
+                .extend(subtype_ids.iter().copied());
+        }
+
+        // Cycles can only be detected once all type refinements in this document are
+        // known. Whether the implementations are valid is checked when validating the
+        // type system.
+        for (implementing_type_id, interface_id) in &interface_implementations {
+            self.validate_no_type_refinement_cycle(
+                interface_id.item,
+                *implementing_type_id,
+                interface_id.location,
+            )?;
+        }
+        for (implementing_type_id, interface_id) in interface_implementations {
+            self.schema_data
+                .object_mut(implementing_type_id)
+                .implemented_interfaces
+                .push(interface_id);
+        }
+
+        self.add_transitive_type_refinements(&mut type_refinement_map);
 
         let root_types = self.process_root_types(processed_root_types, encountered_root_types)?;
 
-        if let Some(query_type_id) = root_types.query {
-            debug_assert!(
-                self.query_type_id.is_none(),
-                "Expected query not to be already defined."
-            );
-            self.query_type_id = Some(query_type_id);
+        // Root types can only be defined once. If this is a schema extension document,
+        // defining a root type that the schema already defines is an error.
+        for (root_operation_kind, root_type_id) in [
+            (RootOperationKind::Query, root_types.query),
+            (RootOperationKind::Subscription, root_types.subscription),
+            (RootOperationKind::Mutation, root_types.mutation),
+        ] {
+            if let Some(root_type_id) = root_type_id {
+                self.set_root_type_id(root_operation_kind, root_type_id)?;
+            }
         }
 
         Ok(ProcessGraphQLDocumentOutcome {
-            root_types,
+            root_types: RootTypes {
+                query: root_types.query.map(|id| id.item),
+                mutation: root_types.mutation.map(|id| id.item),
+                subscription: root_types.subscription.map(|id| id.item),
+            },
             type_refinement_maps: type_refinement_map,
         })
     }
 
-    // TODO This is currently a completely useless function, serving only to surface
-    // some validation errors. It might be necessary once we handle __asNode etc.
-    // style fields.
+    fn add_union_member_types(
+        &mut self,
+        union_id: ObjectId,
+        member_types: Vec<WithLocation<GraphQLObjectTypeName>>,
+        type_refinement_maps: &mut TypeRefinementMaps,
+    ) -> ProcessTypeDefinitionResult<()> {
+        for member_type in member_types {
+            let member_object_id = self.look_up_union_member_type(union_id, member_type)?;
+            let member_ids = type_refinement_maps
+                .supertype_to_subtype_map
+                .entry(union_id)
+                .or_default();
+            if member_ids.contains(&member_object_id) {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::DuplicateTypeRefinement {
+                        supertype_name: self.schema_data.object(union_id).name,
+                        subtype_name: member_type.item.into(),
+                    },
+                    member_type.location,
+                ));
+            }
+            member_ids.push(member_object_id);
+            type_refinement_maps
+                .subtype_to_supertype_map
+                .entry(member_object_id)
+                .or_default()
+                .push(union_id);
+        }
+        Ok(())
+    }
+
+    /// Union members must be object types, i.e. not interfaces, unions, etc.
+    fn look_up_union_member_type(
+        &self,
+        union_id: ObjectId,
+        member_type: WithLocation<GraphQLObjectTypeName>,
+    ) -> ProcessTypeDefinitionResult<ObjectId> {
+        let member_type_id = *self
+            .schema_data
+            .defined_types
+            .get(&member_type.item.into())
+            .ok_or(WithLocation::new(
+                ProcessTypeDefinitionError::IsographObjectTypeNameNotDefined {
+                    type_name: member_type.item.into(),
+                },
+                member_type.location,
+            ))?;
+        match member_type_id {
+            SelectableFieldId::Object(member_object_id)
+                if self.schema_data.object(member_object_id).kind == SchemaObjectKind::Object =>
+            {
+                Ok(member_object_id)
+            }
+            _ => Err(WithLocation::new(
+                ProcessTypeDefinitionError::UnionMemberTypeIsNotObject {
+                    union_name: self.schema_data.object(union_id).name,
+                    member_type_name: member_type.item.into(),
+                    is_type: self.type_description(member_type_id),
+                },
+                member_type.location,
+            )),
+        }
+    }
+
+    /// Interfaces can implement other interfaces, but not in a cycle. This is called
+    /// after the type refinement from interface_id to subtype_id was added, so there is
+    /// a cycle if interface_id is (transitively) a subtype of subtype_id.
+    fn validate_no_type_refinement_cycle(
+        &self,
+        interface_id: ObjectId,
+        subtype_id: ObjectId,
+        location: Location,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let supertype_to_subtype_map = &self.schema_data.supertype_to_subtype_map;
+        // The supertype through which each visited type was reached
+        let mut visited_from = HashMap::new();
+        let mut stack = vec![subtype_id];
+        while let Some(current_id) = stack.pop() {
+            for next_id in supertype_to_subtype_map
+                .get(&current_id)
+                .into_iter()
+                .flatten()
+            {
+                if *next_id == interface_id {
+                    let mut cycle = vec![interface_id, current_id];
+                    let mut cycle_id = current_id;
+                    while let Some(previous_id) = visited_from.get(&cycle_id) {
+                        cycle.push(*previous_id);
+                        cycle_id = *previous_id;
+                    }
+                    cycle.push(interface_id);
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::TypeRefinementCycle {
+                            cycle: cycle
+                                .into_iter()
+                                .map(|id| self.schema_data.object(id).name.to_string())
+                                .collect::<Vec<_>>()
+                                .join(" implements "),
+                        },
+                        location,
+                    ));
+                }
+                if *next_id != subtype_id && !visited_from.contains_key(next_id) {
+                    visited_from.insert(*next_id, current_id);
+                    stack.push(*next_id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// If an interface implements another interface, the subtypes of the former are
+    /// subtypes of the latter, too. Add those indirect type refinements to the schema
+    /// and to type_refinement_maps, so that e.g. client fields defined on an interface
+    /// are available on every concrete type beneath it.
+    ///
+    /// This must be called after validating that there are no cycles.
+    fn add_transitive_type_refinements(&mut self, type_refinement_maps: &mut TypeRefinementMaps) {
+        let supertype_to_subtype_map = &self.schema_data.supertype_to_subtype_map;
+        let mut transitive_type_refinements = vec![];
+        for (supertype_id, subtype_ids) in supertype_to_subtype_map {
+            let mut visited: HashSet<ObjectId> = subtype_ids.iter().copied().collect();
+            let mut stack = subtype_ids.clone();
+            while let Some(subtype_id) = stack.pop() {
+                for indirect_subtype_id in supertype_to_subtype_map
+                    .get(&subtype_id)
+                    .into_iter()
+                    .flatten()
+                {
+                    if visited.insert(*indirect_subtype_id) {
+                        transitive_type_refinements.push((*supertype_id, *indirect_subtype_id));
+                        stack.push(*indirect_subtype_id);
+                    }
+                }
+            }
+        }
+        // Keep the order of subtypes deterministic
+        transitive_type_refinements.sort();
+
+        for (supertype_id, subtype_id) in transitive_type_refinements {
+            self.schema_data
+                .supertype_to_subtype_map
+                .entry(supertype_id)
+                .or_default()
+                .push(subtype_id);
+            type_refinement_maps
+                .supertype_to_subtype_map
+                .entry(supertype_id)
+                .or_default()
+                .push(subtype_id);
+            type_refinement_maps
+                .subtype_to_supertype_map
+                .entry(subtype_id)
+                .or_default()
+                .push(supertype_id);
+        }
+    }
+
+    /// Only interfaces can be implemented, and a type cannot implement itself.
+    fn validate_implemented_type(
+        &self,
+        implemented_type_id: ObjectId,
+        implementing_type_id: ObjectId,
+        implemented_type_name: WithLocation<IsographObjectTypeName>,
+    ) -> ProcessTypeDefinitionResult<()> {
+        if implemented_type_id == implementing_type_id {
+            return Err(WithLocation::new(
+                ProcessTypeDefinitionError::ObjectImplementsItself {
+                    type_name: implemented_type_name.item,
+                },
+                implemented_type_name.location,
+            ));
+        }
+        let implemented_type_kind = self.schema_data.object(implemented_type_id).kind;
+        if implemented_type_kind != SchemaObjectKind::Interface {
+            return Err(WithLocation::new(
+                ProcessTypeDefinitionError::ImplementedTypeIsNotInterface {
+                    type_name: implemented_type_name.item,
+                    implementing_object: self.schema_data.object(implementing_type_id).name,
+                    is_type: implemented_type_kind.description(),
+                },
+                implemented_type_name.location,
+            ));
+        }
+        Ok(())
+    }
+
+    /// e.g. "a scalar" or "an interface", for use in error messages
+    fn type_description(&self, type_id: SelectableFieldId) -> &'static str {
+        match type_id {
+            SelectableFieldId::Object(object_id) => {
+                self.schema_data.object(object_id).kind.description()
+            }
+            SelectableFieldId::Scalar(_) => "a scalar",
+            SelectableFieldId::Enum(_) => "an enum",
+            SelectableFieldId::InputObject(_) => "an input object",
+        }
+    }
+
+    /// Resolve the names of implemented interfaces. Each implementation is also added
+    /// to interface_implementations (with the location of the interface name), so that
+    /// it can be added to the implementing type once the type refinements have been
+    /// added to the schema.
     fn get_type_refinement_map(
         &mut self,
         unvalidated_supertype_to_subtype_map: UnvalidatedSupertypeToSubtypeMap,
         unvalidated_subtype_to_supertype_map: UnvalidatedSubtypeToSupertypeMap,
+        interface_implementations: &mut Vec<(ObjectId, WithLocation<ObjectId>)>,
     ) -> ProcessTypeDefinitionResult<TypeRefinementMaps> {
         let mut subtype_to_supertype_map = HashMap::new();
         for (subtype_id, supertype_names) in unvalidated_subtype_to_supertype_map {
@@ -234,7 +486,42 @@ impl UnvalidatedSchema {
                                         supertype_name.location,
                                     ));
                                 }
+                                SelectableFieldId::Enum(_) => {
+                                    let subtype_name = self.schema_data.object(subtype_id).name;
+
+                                    return Err(WithLocation::new(
+                                        ProcessTypeDefinitionError::ObjectIsEnum {
+                                            type_name: supertype_name.item,
+                                            implementing_object: subtype_name,
+                                        },
+                                        supertype_name.location,
+                                    ));
+                                }
+                                SelectableFieldId::InputObject(_) => {
+                                    let subtype_name = self.schema_data.object(subtype_id).name;
+
+                                    return Err(WithLocation::new(
+                                        ProcessTypeDefinitionError::ImplementedTypeIsNotInterface {
+                                            type_name: supertype_name.item,
+                                            implementing_object: subtype_name,
+                                            is_type: "an input object",
+                                        },
+                                        supertype_name.location,
+                                    ));
+                                }
                                 SelectableFieldId::Object(supertype_object_id) => {
+                                    self.validate_implemented_type(
+                                        *supertype_object_id,
+                                        subtype_id,
+                                        supertype_name,
+                                    )?;
+                                    interface_implementations.push((
+                                        subtype_id,
+                                        WithLocation::new(
+                                            *supertype_object_id,
+                                            supertype_name.location,
+                                        ),
+                                    ));
                                     Ok(*supertype_object_id)
                                 }
                             }
@@ -255,19 +542,17 @@ impl UnvalidatedSchema {
                 .expect("Expected Interface to be found. This indicates a bug in Isograph.");
 
             match supertype_id {
-                SelectableFieldId::Scalar(_) => {
+                SelectableFieldId::Scalar(_)
+                | SelectableFieldId::Enum(_)
+                | SelectableFieldId::InputObject(_) => {
                     panic!(
                         "Expected an object id; this is indicative of a bug in Isograph and \
                         should have already been validated."
                     );
                 }
                 SelectableFieldId::Object(supertype_object_id) => {
-                    // TODO validate that supertype was defined as an interface, perhaps by
-                    // including references to the original definition (i.e. as a type parameter)
-                    // and having the schema be able to validate this. (i.e. this should be
-                    // a way to execute GraphQL-specific code in isograph-land without actually
-                    // putting the code here.)
-
+                    // N.B. that the supertype is an interface was validated when
+                    // constructing subtype_to_supertype_map.
                     let value = subtypes.into_iter().map(|subtype_id| subtype_id).collect();
                     match supertype_to_subtype_map.entry(*supertype_object_id) {
                         Entry::Occupied(_) => {
@@ -290,7 +575,8 @@ impl UnvalidatedSchema {
     pub fn process_graphql_type_extension_document(
         &mut self,
         extension_document: GraphQLTypeSystemExtensionDocument,
-        options: ConfigOptions,
+        options: &ConfigOptions,
+        warnings: &mut Vec<Diagnostic>,
     ) -> ProcessTypeDefinitionResult<ProcessGraphQLDocumentOutcome> {
         let mut definitions = Vec::with_capacity(extension_document.0.len());
         let mut extensions = Vec::with_capacity(extension_document.0.len());
@@ -309,60 +595,448 @@ impl UnvalidatedSchema {
 
         // N.B. we should probably restructure this...?
         // Like, we could discover the mutation type right now!
-        let outcome = self.process_graphql_type_system_document(
+        let mut outcome = self.process_graphql_type_system_document(
             GraphQLTypeSystemDocument(definitions),
             options,
+            warnings,
         )?;
 
         for extension in extensions.into_iter() {
             // TODO collect errors into vec
-            // TODO we can encounter new interface implementations; we should account for that
-            self.process_graphql_type_system_extension(extension)?;
+            self.process_graphql_type_system_extension(
+                extension,
+                &mut outcome.type_refinement_maps,
+                options,
+                warnings,
+            )?;
         }
+        self.add_transitive_type_refinements(&mut outcome.type_refinement_maps);
 
         Ok(outcome)
     }
 
+    /// Extensions are processed after all definitions in the same document, so they
+    /// can extend types defined in the schema, in earlier extension documents or in
+    /// the current one. Type refinements added by an extension (e.g. new interface
+    /// implementations or union members) are added to type_refinement_maps.
     fn process_graphql_type_system_extension(
         &mut self,
         extension: WithLocation<GraphQLTypeSystemExtension>,
+        type_refinement_maps: &mut TypeRefinementMaps,
+        options: &ConfigOptions,
+        warnings: &mut Vec<Diagnostic>,
     ) -> ProcessTypeDefinitionResult<()> {
         match extension.item {
-            GraphQLTypeSystemExtension::ObjectTypeExtension(object_extension) => {
-                let name = object_extension.name.item;
+            GraphQLTypeSystemExtension::ObjectTypeExtension(object_extension) => self
+                .process_object_type_extension(
+                    object_extension.into(),
+                    SchemaObjectKind::Object,
+                    type_refinement_maps,
+                    options,
+                    warnings,
+                ),
+            GraphQLTypeSystemExtension::InterfaceTypeExtension(interface_extension) => self
+                .process_object_type_extension(
+                    interface_extension.into(),
+                    SchemaObjectKind::Interface,
+                    type_refinement_maps,
+                    options,
+                    warnings,
+                ),
+            GraphQLTypeSystemExtension::InputObjectTypeExtension(input_object_extension) => {
+                self.process_input_object_type_extension(input_object_extension)
+            }
+            GraphQLTypeSystemExtension::UnionTypeExtension(union_extension) => {
+                self.process_union_type_extension(union_extension, type_refinement_maps)
+            }
+            GraphQLTypeSystemExtension::EnumTypeExtension(enum_extension) => {
+                self.process_enum_type_extension(enum_extension)
+            }
+            GraphQLTypeSystemExtension::ScalarTypeExtension(scalar_extension) => {
+                // Scalar extensions can only add directives, which Isograph does not use
+                // for scalars. So, we only validate that the scalar exists.
+                let name = scalar_extension.name.map(UnvalidatedTypeName::from);
+                match self.look_up_extended_type(name)? {
+                    SelectableFieldId::Scalar(_) => Ok(()),
+                    type_id => Err(type_extension_mismatch_error(
+                        name,
+                        self.type_description(type_id),
+                        "a scalar",
+                    )),
+                }
+            }
+            GraphQLTypeSystemExtension::SchemaExtension(schema_extension) => {
+                self.process_schema_extension(schema_extension)
+            }
+        }
+    }
 
-                let id = self.schema_data.defined_types.get(&name.into()).expect(
-                    "TODO why does this id not exist. This probably indicates a bug in Isograph.",
-                );
+    fn process_object_type_extension(
+        &mut self,
+        object_type_extension: IsographObjectTypeExtension,
+        extended_as_kind: SchemaObjectKind,
+        type_refinement_maps: &mut TypeRefinementMaps,
+        options: &ConfigOptions,
+        warnings: &mut Vec<Diagnostic>,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let name = object_type_extension.name.map(UnvalidatedTypeName::from);
+        let object_id = self.look_up_extended_object(name, extended_as_kind)?;
+
+        self.schema_data
+            .object_mut(object_id)
+            .directives
+            .extend(object_type_extension.directives);
+
+        // Fields are added before interfaces, since the added fields may be required
+        // by the added interfaces.
+        self.add_fields_from_extension(object_id, object_type_extension.fields, options, warnings)?;
+
+        let mut added_interface_ids = vec![];
+        for interface in object_type_extension.interfaces {
+            let interface_name = interface.item.into();
+            let interface_id = match self.schema_data.defined_types.get(&interface.item.into()) {
+                Some(SelectableFieldId::Object(interface_id)) => *interface_id,
+                Some(SelectableFieldId::Scalar(_)) => {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::ObjectIsScalar {
+                            type_name: interface_name,
+                            implementing_object: object_type_extension.name.item,
+                        },
+                        interface.location,
+                    ))
+                }
+                Some(SelectableFieldId::Enum(_)) => {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::ObjectIsEnum {
+                            type_name: interface_name,
+                            implementing_object: object_type_extension.name.item,
+                        },
+                        interface.location,
+                    ))
+                }
+                Some(SelectableFieldId::InputObject(_)) => {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::ImplementedTypeIsNotInterface {
+                            type_name: interface_name,
+                            implementing_object: object_type_extension.name.item,
+                            is_type: "an input object",
+                        },
+                        interface.location,
+                    ))
+                }
+                None => {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::IsographObjectTypeNameNotDefined {
+                            type_name: interface_name,
+                        },
+                        interface.location,
+                    ))
+                }
+            };
+            self.validate_implemented_type(
+                interface_id,
+                object_id,
+                WithLocation::new(interface_name, interface.location),
+            )?;
+            self.add_type_refinement_from_extension(
+                interface_id,
+                object_id,
+                interface.location,
+                type_refinement_maps,
+            )?;
+            added_interface_ids.push(WithLocation::new(interface_id, interface.location));
+        }
 
-                match *id {
-                    SelectableFieldId::Object(object_id) => {
-                        let schema_object = self.schema_data.object_mut(object_id);
+        for interface_id in &added_interface_ids {
+            self.validate_no_type_refinement_cycle(
+                interface_id.item,
+                object_id,
+                interface_id.location,
+            )?;
+        }
+        self.schema_data
+            .object_mut(object_id)
+            .implemented_interfaces
+            .extend(added_interface_ids);
 
-                        if !object_extension.fields.is_empty() {
-                            panic!("Adding fields in schema extensions is not allowed, yet.");
-                        }
-                        if !object_extension.interfaces.is_empty() {
-                            panic!("Adding interfaces in schema extensions is not allowed, yet.");
-                        }
+        Ok(())
+    }
 
-                        schema_object
-                            .directives
-                            .extend(object_extension.directives.into_iter());
+    fn process_union_type_extension(
+        &mut self,
+        union_extension: GraphQLUnionTypeExtension,
+        type_refinement_maps: &mut TypeRefinementMaps,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let name = union_extension.name.map(UnvalidatedTypeName::from);
+        let union_id = self.look_up_extended_object(name, SchemaObjectKind::Union)?;
+
+        self.schema_data
+            .object_mut(union_id)
+            .directives
+            .extend(union_extension.directives);
+
+        for member_type in union_extension.union_member_types {
+            let member_object_id = self.look_up_union_member_type(union_id, member_type)?;
+            self.add_type_refinement_from_extension(
+                union_id,
+                member_object_id,
+                member_type.location,
+                type_refinement_maps,
+            )?;
+        }
+        Ok(())
+    }
 
-                        Ok(())
-                    }
-                    SelectableFieldId::Scalar(_) => Err(WithLocation::new(
-                        ProcessTypeDefinitionError::TypeExtensionMismatch {
-                            type_name: name.into(),
-                            is_type: "a scalar",
-                            extended_as_type: "an object",
-                        },
-                        object_extension.name.location,
-                    )),
-                }
+    fn process_enum_type_extension(
+        &mut self,
+        enum_extension: GraphQLEnumTypeExtension,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let name = enum_extension.name.map(UnvalidatedTypeName::from);
+        let enum_id = match self.look_up_extended_type(name)? {
+            SelectableFieldId::Enum(enum_id) => enum_id,
+            type_id => {
+                return Err(type_extension_mismatch_error(
+                    name,
+                    self.type_description(type_id),
+                    "an enum",
+                ))
+            }
+        };
+
+        // Isograph does not use the directives of enums, so only the values are added.
+        let schema_enum = &mut self.schema_data.enums[enum_id.as_usize()];
+        for enum_value_definition in enum_extension.enum_value_definitions {
+            let value = enum_value_definition.item.value;
+            if schema_enum.has_value(value.item) {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::DuplicateEnumValue {
+                        enum_name: enum_extension.name.item,
+                        value: value.item,
+                    },
+                    value.location,
+                ));
+            }
+            schema_enum.values.push(enum_value_definition);
+        }
+        Ok(())
+    }
+
+    fn process_input_object_type_extension(
+        &mut self,
+        input_object_extension: GraphQLInputObjectTypeExtension,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let name = input_object_extension.name.map(UnvalidatedTypeName::from);
+        let input_object_id = match self.look_up_extended_type(name)? {
+            SelectableFieldId::InputObject(input_object_id) => input_object_id,
+            type_id => {
+                return Err(type_extension_mismatch_error(
+                    name,
+                    self.type_description(type_id),
+                    "an input object",
+                ))
+            }
+        };
+
+        // Isograph does not use the directives of input objects, so only the fields are added.
+        let input_object = self.schema_data.input_object_mut(input_object_id);
+        for field in input_object_extension.fields {
+            add_input_object_field(input_object, field)?;
+        }
+        Ok(())
+    }
+
+    /// A schema extension can define root types that were not previously defined.
+    /// Directives on the schema are ignored.
+    fn process_schema_extension(
+        &mut self,
+        schema_extension: GraphQLSchemaExtension,
+    ) -> ProcessTypeDefinitionResult<()> {
+        for (root_operation_kind, root_type_name) in [
+            (RootOperationKind::Query, schema_extension.query),
+            (
+                RootOperationKind::Subscription,
+                schema_extension.subscription,
+            ),
+            (RootOperationKind::Mutation, schema_extension.mutation),
+        ] {
+            if let Some(root_type_name) = root_type_name {
+                let root_type_id = self.look_up_root_type(root_type_name)?;
+                self.set_root_type_id(
+                    root_operation_kind,
+                    WithLocation::new(root_type_id, root_type_name.location),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn set_root_type_id(
+        &mut self,
+        root_operation_kind: RootOperationKind,
+        root_type_id: WithLocation<ObjectId>,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let existing_root_type_id = match root_operation_kind {
+            RootOperationKind::Query => &mut self.query_type_id,
+            RootOperationKind::Subscription => &mut self.subscription_type_id,
+            RootOperationKind::Mutation => &mut self.mutation_type_id,
+        };
+        if existing_root_type_id.is_some() {
+            return Err(WithLocation::new(
+                ProcessTypeDefinitionError::RootOperationTypeAlreadyDefined {
+                    root_operation_kind,
+                },
+                root_type_id.location,
+            ));
+        }
+        *existing_root_type_id = Some(root_type_id.item);
+        Ok(())
+    }
+
+    /// The object being extended must have been defined, and must be of the same
+    /// kind as the extension (e.g. `extend interface` must extend an interface).
+    fn look_up_extended_object(
+        &self,
+        type_name: WithLocation<UnvalidatedTypeName>,
+        extended_as_kind: SchemaObjectKind,
+    ) -> ProcessTypeDefinitionResult<ObjectId> {
+        let type_id = self.look_up_extended_type(type_name)?;
+        match type_id {
+            SelectableFieldId::Object(object_id)
+                if self.schema_data.object(object_id).kind == extended_as_kind =>
+            {
+                Ok(object_id)
+            }
+            _ => Err(type_extension_mismatch_error(
+                type_name,
+                self.type_description(type_id),
+                extended_as_kind.description(),
+            )),
+        }
+    }
+
+    /// The type being extended must have been defined.
+    fn look_up_extended_type(
+        &self,
+        type_name: WithLocation<UnvalidatedTypeName>,
+    ) -> ProcessTypeDefinitionResult<SelectableFieldId> {
+        self.schema_data
+            .defined_types
+            .get(&type_name.item)
+            .copied()
+            .ok_or(WithLocation::new(
+                ProcessTypeDefinitionError::ExtendedTypeNotDefined {
+                    type_name: type_name.item,
+                },
+                type_name.location,
+            ))
+    }
+
+    fn add_type_refinement_from_extension(
+        &mut self,
+        supertype_id: ObjectId,
+        subtype_id: ObjectId,
+        location: Location,
+        type_refinement_maps: &mut TypeRefinementMaps,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let subtype_ids = self
+            .schema_data
+            .supertype_to_subtype_map
+            .entry(supertype_id)
+            .or_default();
+        if subtype_ids.contains(&subtype_id) {
+            return Err(WithLocation::new(
+                ProcessTypeDefinitionError::DuplicateTypeRefinement {
+                    supertype_name: self.schema_data.object(supertype_id).name,
+                    subtype_name: self.schema_data.object(subtype_id).name,
+                },
+                location,
+            ));
+        }
+        subtype_ids.push(subtype_id);
+
+        type_refinement_maps
+            .supertype_to_subtype_map
+            .entry(supertype_id)
+            .or_default()
+            .push(subtype_id);
+        type_refinement_maps
+            .subtype_to_supertype_map
+            .entry(subtype_id)
+            .or_default()
+            .push(supertype_id);
+        Ok(())
+    }
+
+    /// Add the fields of an extension to an existing object. If this adds an id
+    /// field, the object becomes refetchable.
+    fn add_fields_from_extension(
+        &mut self,
+        object_id: ObjectId,
+        fields: Vec<WithLocation<GraphQLFieldDefinition>>,
+        options: &ConfigOptions,
+        warnings: &mut Vec<Diagnostic>,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let &mut Schema {
+            ref mut server_fields,
+            ref mut schema_data,
+            ref mut client_fields,
+            ..
+        } = self;
+        let object = schema_data.object_mut(object_id);
+        let had_id_field = object.id_field.is_some();
+        let id_name = "id".intern().into();
+
+        for field in fields {
+            let field_name = field.item.name.item;
+            if object.encountered_fields.contains_key(&field_name) {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::DuplicateField {
+                        field_name,
+                        parent_type: object.name,
+                    },
+                    field.item.name.location,
+                ));
+            }
+
+            let current_field_id = server_fields.len();
+            if field_name == id_name {
+                set_and_validate_id_field(
+                    &mut object.id_field,
+                    current_field_id,
+                    &field,
+                    object.name,
+                    options,
+                    warnings,
+                )?;
             }
+
+            object.encountered_fields.insert(
+                field_name,
+                FieldDefinitionLocation::Server(field.item.type_.clone()),
+            );
+            object.server_fields.push(current_field_id.into());
+            server_fields.push(SchemaServerField {
+                description: field.item.description.map(|d| d.item),
+                name: field.item.name,
+                id: current_field_id.into(),
+                associated_data: field.item.type_,
+                parent_type_id: object_id,
+                directives: field.item.directives,
+                arguments: field.item.arguments,
+            });
         }
+
+        if !had_id_field {
+            let refetch_field_ids = get_resolvers_for_schema_object(
+                &object.id_field,
+                &mut object.encountered_fields,
+                client_fields,
+                object_id,
+                object.name,
+            );
+            object.client_field_ids.extend(refetch_field_ids);
+        }
+        Ok(())
     }
 
     pub(crate) fn process_object_type_definition(
@@ -370,9 +1044,9 @@ impl UnvalidatedSchema {
         object_type_definition: IsographObjectTypeDefinition,
         supertype_to_subtype_map: &mut UnvalidatedSupertypeToSubtypeMap,
         subtype_to_supertype_map: &mut UnvalidatedSubtypeToSupertypeMap,
-        // TODO this smells! We should probably pass Option<ServerIdFieldId>
-        may_have_id_field: bool,
-        options: ConfigOptions,
+        kind: SchemaObjectKind,
+        options: &ConfigOptions,
+        warnings: &mut Vec<Diagnostic>,
     ) -> ProcessTypeDefinitionResult<ProcessObjectTypeDefinitionOutcome> {
         let &mut Schema {
             server_fields: ref mut schema_fields,
@@ -410,8 +1084,8 @@ impl UnvalidatedSchema {
                     next_object_id,
                     type_def_2.name.item.into(),
                     get_typename_type(string_type_for_typename.item),
-                    may_have_id_field,
                     options,
+                    warnings,
                 )?;
 
                 let object_resolvers = get_resolvers_for_schema_object(
@@ -419,14 +1093,16 @@ impl UnvalidatedSchema {
                     &mut encountered_fields,
                     schema_resolvers,
                     next_object_id,
-                    &object_type_definition,
+                    object_type_definition.name.item,
                 );
 
                 objects.push(SchemaObject {
                     description: object_type_definition.description.map(|d| d.item),
                     name: object_type_definition.name.item,
                     id: next_object_id,
+                    kind,
                     server_fields,
+                    implemented_interfaces: vec![],
                     client_field_ids: object_resolvers,
                     encountered_fields,
                     id_field,
@@ -441,8 +1117,9 @@ impl UnvalidatedSchema {
                     Some(RootOperationKind::Query)
                 } else if object_type_definition.name.item == *MUTATION_TYPE {
                     Some(RootOperationKind::Mutation)
+                } else if object_type_definition.name.item == *SUBSCRIPTION_TYPE {
+                    Some(RootOperationKind::Subscription)
                 } else {
-                    // TODO subscription
                     None
                 }
             }
@@ -484,6 +1161,8 @@ impl UnvalidatedSchema {
     fn process_scalar_definition(
         &mut self,
         scalar_type_definition: GraphQLScalarTypeDefinition,
+        // If None, the scalar is typed as a string
+        custom_scalar_type: Option<&CustomScalarType>,
     ) -> ProcessTypeDefinitionResult<()> {
         let &mut Schema {
             ref mut schema_data,
@@ -507,7 +1186,13 @@ impl UnvalidatedSchema {
                     description: scalar_type_definition.description,
                     name: scalar_type_definition.name,
                     id: next_scalar_id,
-                    javascript_name: *STRING_JAVASCRIPT_TYPE,
+                    javascript_name: custom_scalar_type
+                        .map(|custom_scalar_type| {
+                            custom_scalar_type.javascript_type.as_str().intern().into()
+                        })
+                        .unwrap_or(*STRING_JAVASCRIPT_TYPE),
+                    javascript_import_path: custom_scalar_type
+                        .and_then(|custom_scalar_type| custom_scalar_type.import_path.clone()),
                 });
 
                 vacant.insert(SelectableFieldId::Scalar(next_scalar_id));
@@ -516,11 +1201,79 @@ impl UnvalidatedSchema {
         Ok(())
     }
 
+    fn process_enum_definition(
+        &mut self,
+        enum_definition: GraphQLEnumDefinition,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let next_enum_id = self.schema_data.enums.len().into();
+        let name: WithLocation<GraphQLEnumTypeName> = enum_definition
+            .name
+            .map(|enum_name| enum_name.lookup().intern().into());
+        match self.schema_data.defined_types.entry(name.item.into()) {
+            Entry::Occupied(_) => {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::DuplicateTypeDefinition {
+                        type_definition_type: "enum",
+                        type_name: name.item.into(),
+                    },
+                    name.location,
+                ));
+            }
+            Entry::Vacant(vacant) => {
+                self.schema_data.enums.push(SchemaEnum {
+                    description: enum_definition.description,
+                    name,
+                    id: next_enum_id,
+                    values: enum_definition.enum_value_definitions,
+                });
+
+                vacant.insert(SelectableFieldId::Enum(next_enum_id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Input objects are not SchemaObject's, since they cannot be selected. Returns the
+    /// id of the new input object.
+    pub(crate) fn process_input_object_definition(
+        &mut self,
+        input_object_definition: GraphQLInputObjectTypeDefinition,
+    ) -> ProcessTypeDefinitionResult<InputObjectId> {
+        let next_input_object_id = self.schema_data.input_objects.len().into();
+        let name = input_object_definition.name;
+        match self.schema_data.defined_types.entry(name.item.into()) {
+            Entry::Occupied(_) => {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::DuplicateTypeDefinition {
+                        type_definition_type: "input object",
+                        type_name: name.item.into(),
+                    },
+                    name.location,
+                ));
+            }
+            Entry::Vacant(vacant) => {
+                let mut input_object = SchemaInputObject {
+                    description: input_object_definition.description,
+                    name,
+                    id: next_input_object_id,
+                    fields: Vec::with_capacity(input_object_definition.fields.len()),
+                };
+                for field in input_object_definition.fields {
+                    add_input_object_field(&mut input_object, field)?;
+                }
+                self.schema_data.input_objects.push(input_object);
+
+                vacant.insert(SelectableFieldId::InputObject(next_input_object_id));
+            }
+        }
+        Ok(next_input_object_id)
+    }
+
     fn process_root_types(
         &self,
         processed_root_types: Option<ProcessedRootTypes>,
-        encountered_root_types: EncounteredRootTypes,
-    ) -> ProcessTypeDefinitionResult<EncounteredRootTypes> {
+        encountered_root_types: RootTypes<WithLocation<ObjectId>>,
+    ) -> ProcessTypeDefinitionResult<RootTypes<WithLocation<ObjectId>>> {
         match processed_root_types {
             Some(processed_root_types) => {
                 let RootTypes {
@@ -530,13 +1283,22 @@ impl UnvalidatedSchema {
                 } = processed_root_types;
 
                 let query_id = query_type_name
-                    .map(|query_type_name| self.look_up_root_type(query_type_name))
+                    .map(|query_type_name| {
+                        self.look_up_root_type(query_type_name)
+                            .map(|id| WithLocation::new(id, query_type_name.location))
+                    })
                     .transpose()?;
                 let mutation_id = mutation_type_name
-                    .map(|mutation_type_name| self.look_up_root_type(mutation_type_name))
+                    .map(|mutation_type_name| {
+                        self.look_up_root_type(mutation_type_name)
+                            .map(|id| WithLocation::new(id, mutation_type_name.location))
+                    })
                     .transpose()?;
                 let subscription_id = subscription_type_name
-                    .map(|subscription_type_name| self.look_up_root_type(subscription_type_name))
+                    .map(|subscription_type_name| {
+                        self.look_up_root_type(subscription_type_name)
+                            .map(|id| WithLocation::new(id, subscription_type_name.location))
+                    })
                     .transpose()?;
 
                 Ok(RootTypes {
@@ -555,7 +1317,9 @@ impl UnvalidatedSchema {
     ) -> ProcessTypeDefinitionResult<ObjectId> {
         match self.schema_data.defined_types.get(&type_name.item.into()) {
             Some(SelectableFieldId::Object(object_id)) => Ok(*object_id),
-            Some(SelectableFieldId::Scalar(_)) => Err(WithLocation::new(
+            Some(SelectableFieldId::Scalar(_))
+            | Some(SelectableFieldId::Enum(_))
+            | Some(SelectableFieldId::InputObject(_)) => Err(WithLocation::new(
                 ProcessTypeDefinitionError::RootTypeMustBeObject,
                 type_name.location,
             )),
@@ -605,7 +1369,7 @@ fn get_resolvers_for_schema_object(
     encountered_fields: &mut HashMap<SelectableFieldName, UnvalidatedObjectFieldInfo>,
     schema_resolvers: &mut Vec<UnvalidatedClientField>,
     parent_object_id: ObjectId,
-    type_definition: &IsographObjectTypeDefinition,
+    type_name: IsographObjectTypeName,
 ) -> Vec<ClientFieldId> {
     if let Some(_id_field_id) = id_field_id {
         let next_resolver_id = schema_resolvers.len().into();
@@ -617,6 +1381,7 @@ fn get_resolvers_for_schema_object(
                 associated_data: (),
                 unwraps: vec![],
                 arguments: vec![],
+                directives: vec![],
             })),
             Span::todo_generated(),
         );
@@ -626,9 +1391,10 @@ fn get_resolvers_for_schema_object(
             id: next_resolver_id,
             selection_set_and_unwraps: Some((vec![id_field_selection], vec![])),
             variant: ClientFieldVariant::RefetchField,
+            deprecation_reason: None,
             variable_definitions: vec![],
             type_and_field: ObjectTypeAndFieldNames {
-                type_name: type_definition.name.item,
+                type_name,
                 field_name: "__refetch".intern().into(),
             },
             parent_object_id,
@@ -646,6 +1412,23 @@ fn get_resolvers_for_schema_object(
     }
 }
 
+fn add_input_object_field(
+    input_object: &mut SchemaInputObject,
+    field: WithLocation<GraphQLInputValueDefinition>,
+) -> ProcessTypeDefinitionResult<()> {
+    if input_object.field(field.item.name.item).is_some() {
+        return Err(WithLocation::new(
+            ProcessTypeDefinitionError::DuplicateInputObjectField {
+                field_name: field.item.name.item,
+                parent_type: input_object.name.item,
+            },
+            field.item.name.location,
+        ));
+    }
+    input_object.fields.push(field);
+    Ok(())
+}
+
 fn get_typename_type(
     string_type_for_typename: GraphQLScalarTypeName,
 ) -> TypeAnnotation<UnvalidatedTypeName> {
@@ -675,9 +1458,8 @@ fn get_field_objects_ids_and_names(
     parent_type_id: ObjectId,
     parent_type_name: IsographObjectTypeName,
     typename_type: TypeAnnotation<UnvalidatedTypeName>,
-    // TODO this is hacky
-    may_have_field_id: bool,
-    options: ConfigOptions,
+    options: &ConfigOptions,
+    warnings: &mut Vec<Diagnostic>,
 ) -> ProcessTypeDefinitionResult<FieldObjectIdsEtc> {
     let new_field_count = new_fields.len();
     let mut encountered_fields = HashMap::with_capacity(new_field_count);
@@ -695,13 +1477,14 @@ fn get_field_objects_ids_and_names(
                 let current_field_id = next_field_id + current_field_index;
 
                 // TODO check for @strong directive instead!
-                if may_have_field_id && field.item.name.item == id_name {
+                if field.item.name.item == id_name {
                     set_and_validate_id_field(
                         &mut id_field,
                         current_field_id,
                         &field,
                         parent_type_name,
                         options,
+                        warnings,
                     )?;
                 }
 
@@ -711,6 +1494,7 @@ fn get_field_objects_ids_and_names(
                     id: current_field_id.into(),
                     associated_data: field.item.type_,
                     parent_type_id,
+                    directives: field.item.directives,
                     arguments: field.item.arguments,
                 });
                 field_ids.push(current_field_id.into());
@@ -742,6 +1526,7 @@ fn get_field_objects_ids_and_names(
         id: typename_field_id,
         associated_data: typename_type.clone(),
         parent_type_id,
+        directives: vec![],
         arguments: vec![],
     });
 
@@ -771,6 +1556,44 @@ fn get_field_objects_ids_and_names(
     })
 }
 
+fn type_extension_mismatch_error(
+    type_name: WithLocation<UnvalidatedTypeName>,
+    is_type: &'static str,
+    extended_as_type: &'static str,
+) -> WithLocation<ProcessTypeDefinitionError> {
+    WithLocation::new(
+        ProcessTypeDefinitionError::TypeExtensionMismatch {
+            type_name: type_name.item,
+            is_type,
+            extended_as_type,
+        },
+        type_name.location,
+    )
+}
+
+/// Look up the custom scalar type of a scalar defined in the schema. Scalars without
+/// a custom scalar type are typed as a string.
+fn get_custom_scalar_type<'a>(
+    scalar_type_definition: &GraphQLScalarTypeDefinition,
+    options: &'a ConfigOptions,
+    warnings: &mut Vec<Diagnostic>,
+) -> ProcessTypeDefinitionResult<Option<&'a CustomScalarType>> {
+    let custom_scalar_type = options
+        .custom_scalar_types
+        .get(scalar_type_definition.name.item.lookup());
+    if custom_scalar_type.is_none() {
+        options.on_unknown_scalar_type.on_failure(warnings, || {
+            WithLocation::new(
+                ProcessTypeDefinitionError::UnknownScalarType {
+                    scalar_name: scalar_type_definition.name.item,
+                },
+                scalar_type_definition.name.location,
+            )
+        })?;
+    }
+    Ok(custom_scalar_type)
+}
+
 /// If we have encountered an id field, we can:
 /// - validate that the id field is properly defined, i.e. has type ID!
 /// - set the id field
@@ -779,7 +1602,8 @@ fn set_and_validate_id_field(
     current_field_id: usize,
     field: &WithLocation<GraphQLFieldDefinition>,
     parent_type_name: IsographObjectTypeName,
-    options: ConfigOptions,
+    options: &ConfigOptions,
+    warnings: &mut Vec<Diagnostic>,
 ) -> ProcessTypeDefinitionResult<()> {
     // N.B. id_field is guaranteed to be None; otherwise field_names_to_type_name would
     // have contained this field name already.
@@ -792,7 +1616,7 @@ fn set_and_validate_id_field(
     match field.item.type_.inner_non_null_named_type() {
         Some(type_) => {
             if (*type_).0.item.lookup() != ID_GRAPHQL_TYPE.lookup() {
-                options.on_invalid_id_type.on_failure(|| {
+                options.on_invalid_id_type.on_failure(warnings, || {
                     WithLocation::new(
                         ProcessTypeDefinitionError::IdFieldMustBeNonNullIdType {
                             strong_field_name: "id",
@@ -806,7 +1630,7 @@ fn set_and_validate_id_field(
             Ok(())
         }
         None => {
-            options.on_invalid_id_type.on_failure(|| {
+            options.on_invalid_id_type.on_failure(warnings, || {
                 WithLocation::new(
                     ProcessTypeDefinitionError::IdFieldMustBeNonNullIdType {
                         strong_field_name: "id",
@@ -846,6 +1670,12 @@ pub enum ProcessTypeDefinitionError {
         parent_type: IsographObjectTypeName,
     },
 
+    #[error("Duplicate field named \"{field_name}\" on input object \"{parent_type}\"")]
+    DuplicateInputObjectField {
+        field_name: InputValueName,
+        parent_type: GraphQLInputObjectTypeName,
+    },
+
     #[error(
         "The Isograph compiler attempted to create a field named \
     \"{field_name}\" on type \"{parent_type}\", but a field with that name already exists."
@@ -870,6 +1700,13 @@ pub enum ProcessTypeDefinitionError {
         implementing_object: IsographObjectTypeName,
     },
 
+    // When type Foo implements Bar and Bar is an enum
+    #[error("\"{implementing_object}\" attempted to implement \"{type_name}\". However, \"{type_name}\" is an enum, but only other object types can be implemented.")]
+    ObjectIsEnum {
+        type_name: IsographObjectTypeName,
+        implementing_object: IsographObjectTypeName,
+    },
+
     #[error(
         "You cannot manually defined the \"__typename\" field, which is defined in \"{parent_type}\"."
     )]
@@ -884,6 +1721,13 @@ pub enum ProcessTypeDefinitionError {
         strong_field_name: &'static str,
     },
 
+    #[error(
+        "The scalar \"{scalar_name}\" has no entry in the \"custom_scalar_types\" config option, \
+        so it will be typed as a string.\n\
+        This warning can be suppressed using the \"on_unknown_scalar_type\" config parameter."
+    )]
+    UnknownScalarType { scalar_name: GraphQLScalarTypeName },
+
     #[error("The @exposeField directive should have three arguments")]
     InvalidPrimaryDirectiveArgumentCount,
 
@@ -938,6 +1782,17 @@ pub enum ProcessTypeDefinitionError {
         field_name: String,
     },
 
+    #[error(
+        "Error when processing @exposeField directive on type `{primary_type_name}`. \
+        The argument or input object field `{input_value_name}` has type `{type_name}`, \
+        which is not an input object, so it has no fields that can be remapped."
+    )]
+    PrimaryDirectiveCannotRemapFieldsOfNonInputObject {
+        primary_type_name: IsographObjectTypeName,
+        input_value_name: InputValueName,
+        type_name: UnvalidatedTypeName,
+    },
+
     #[error(
         "Error when processing @exposeField directive on type `{primary_type_name}`. \
         The field `{field_name}` is not found."
@@ -956,6 +1811,121 @@ pub enum ProcessTypeDefinitionError {
         extended_as_type: &'static str,
     },
 
+    #[error("\"{type_name}\" attempted to implement itself.")]
+    ObjectImplementsItself { type_name: IsographObjectTypeName },
+
+    #[error("Interfaces cannot implement each other in a cycle: {cycle}.")]
+    TypeRefinementCycle { cycle: String },
+
+    #[error("\"{implementing_object}\" attempted to implement \"{type_name}\". However, \"{type_name}\" is {is_type}, but only interfaces can be implemented.")]
+    ImplementedTypeIsNotInterface {
+        type_name: IsographObjectTypeName,
+        implementing_object: IsographObjectTypeName,
+        is_type: &'static str,
+    },
+
+    #[error("The union `{union_name}` contains `{member_type_name}`, which is {is_type}. Unions can only contain object types.")]
+    UnionMemberTypeIsNotObject {
+        union_name: IsographObjectTypeName,
+        member_type_name: UnvalidatedTypeName,
+        is_type: &'static str,
+    },
+
+    #[error(
+        "`{implementing_type_name}` implements `{interface_name}`, which implements \
+        `{transitive_interface_name}`. So, `{implementing_type_name}` must also \
+        implement `{transitive_interface_name}`."
+    )]
+    MissingTransitiveInterface {
+        implementing_type_name: IsographObjectTypeName,
+        interface_name: IsographObjectTypeName,
+        transitive_interface_name: IsographObjectTypeName,
+    },
+
+    #[error(
+        "`{implementing_type_name}` implements `{interface_name}`, but does not have \
+        the field `{field_name}`."
+    )]
+    MissingInterfaceField {
+        implementing_type_name: IsographObjectTypeName,
+        interface_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        interface_field_location: Location,
+    },
+
+    #[error(
+        "The field `{implementing_type_name}.{field_name}` has type `{field_type}`, which is \
+        not compatible with the type `{interface_field_type}` of `{interface_name}.{field_name}`."
+    )]
+    InterfaceFieldTypeMismatch {
+        implementing_type_name: IsographObjectTypeName,
+        interface_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        field_type: String,
+        interface_field_type: String,
+        interface_field_location: Location,
+    },
+
+    #[error(
+        "The field `{implementing_type_name}.{field_name}` does not accept the argument \
+        `{argument_name}`, which is accepted by `{interface_name}.{field_name}`."
+    )]
+    MissingInterfaceFieldArgument {
+        implementing_type_name: IsographObjectTypeName,
+        interface_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: InputValueName,
+        interface_argument_location: Location,
+    },
+
+    #[error(
+        "The argument `{argument_name}` of `{implementing_type_name}.{field_name}` has type \
+        `{argument_type}`, but it has type `{interface_argument_type}` on \
+        `{interface_name}.{field_name}`."
+    )]
+    InterfaceFieldArgumentTypeMismatch {
+        implementing_type_name: IsographObjectTypeName,
+        interface_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: InputValueName,
+        argument_type: String,
+        interface_argument_type: String,
+        interface_argument_location: Location,
+    },
+
+    #[error(
+        "The argument `{argument_name}` of `{implementing_type_name}.{field_name}` is required, \
+        but it is not an argument of `{interface_name}.{field_name}`. Arguments that are not \
+        defined on the interface field must be optional."
+    )]
+    RequiredArgumentNotInInterfaceField {
+        implementing_type_name: IsographObjectTypeName,
+        interface_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: InputValueName,
+        interface_field_location: Location,
+    },
+
+    #[error("The type `{type_name}` is extended, but it is never defined.")]
+    ExtendedTypeNotDefined { type_name: UnvalidatedTypeName },
+
+    #[error("`{subtype_name}` is already a subtype of `{supertype_name}`.")]
+    DuplicateTypeRefinement {
+        supertype_name: IsographObjectTypeName,
+        subtype_name: IsographObjectTypeName,
+    },
+
+    #[error("The enum `{enum_name}` already has a value named `{value}`.")]
+    DuplicateEnumValue {
+        enum_name: GraphQLEnumTypeName,
+        value: EnumLiteralValue,
+    },
+
+    #[error("The {root_operation_kind} type has already been defined, so it cannot be defined in a schema extension.")]
+    RootOperationTypeAlreadyDefined {
+        root_operation_kind: RootOperationKind,
+    },
+
     #[error("Duplicate schema definition")]
     DuplicateSchemaDefinition,
 
@@ -965,3 +1935,165 @@ pub enum ProcessTypeDefinitionError {
     #[error("Failed to deserialize {0}")]
     FailedToDeserialize(String),
 }
+
+impl ErrorCode for ProcessTypeDefinitionError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            ProcessTypeDefinitionError::DuplicateTypeDefinition { .. } => {
+                "process_type_definition/duplicate_type_definition"
+            }
+            ProcessTypeDefinitionError::DuplicateField { .. } => {
+                "process_type_definition/duplicate_field"
+            }
+            ProcessTypeDefinitionError::DuplicateInputObjectField { .. } => {
+                "process_type_definition/duplicate_input_object_field"
+            }
+            ProcessTypeDefinitionError::FieldExistsOnSubtype { .. } => {
+                "process_type_definition/field_exists_on_subtype"
+            }
+            ProcessTypeDefinitionError::IsographObjectTypeNameNotDefined { .. } => {
+                "process_type_definition/isograph_object_type_name_not_defined"
+            }
+            ProcessTypeDefinitionError::ObjectIsScalar { .. } => {
+                "process_type_definition/object_is_scalar"
+            }
+            ProcessTypeDefinitionError::ObjectIsEnum { .. } => {
+                "process_type_definition/object_is_enum"
+            }
+            ProcessTypeDefinitionError::TypenameCannotBeDefined { .. } => {
+                "process_type_definition/typename_cannot_be_defined"
+            }
+            ProcessTypeDefinitionError::IdFieldMustBeNonNullIdType { .. } => {
+                "process_type_definition/id_field_must_be_non_null_id_type"
+            }
+            ProcessTypeDefinitionError::UnknownScalarType { .. } => {
+                "process_type_definition/unknown_scalar_type"
+            }
+            ProcessTypeDefinitionError::InvalidPrimaryDirectiveArgumentCount => {
+                "process_type_definition/invalid_primary_directive_argument_count"
+            }
+            ProcessTypeDefinitionError::MissingPathArg => {
+                "process_type_definition/missing_path_arg"
+            }
+            ProcessTypeDefinitionError::MissingFieldMapArg => {
+                "process_type_definition/missing_field_map_arg"
+            }
+            ProcessTypeDefinitionError::PathValueShouldBeString => {
+                "process_type_definition/path_value_should_be_string"
+            }
+            ProcessTypeDefinitionError::InvalidFieldMap => {
+                "process_type_definition/invalid_field_map"
+            }
+            ProcessTypeDefinitionError::InvalidField => "process_type_definition/invalid_field",
+            ProcessTypeDefinitionError::InvalidMutationField => {
+                "process_type_definition/invalid_mutation_field"
+            }
+            ProcessTypeDefinitionError::NotAllToFieldsUsed { .. } => {
+                "process_type_definition/not_all_to_fields_used"
+            }
+            ProcessTypeDefinitionError::FieldMapToCannotJustBeADot => {
+                "process_type_definition/field_map_to_cannot_just_be_a_dot"
+            }
+            ProcessTypeDefinitionError::PrimaryDirectiveArgumentDoesNotExistOnField { .. } => {
+                "process_type_definition/primary_directive_argument_does_not_exist_on_field"
+            }
+            ProcessTypeDefinitionError::PrimaryDirectiveCannotRemapObject { .. } => {
+                "process_type_definition/primary_directive_cannot_remap_object"
+            }
+            ProcessTypeDefinitionError::PrimaryDirectiveCannotRemapFieldsOfNonInputObject {
+                ..
+            } => {
+                "process_type_definition/primary_directive_cannot_remap_fields_of_non_input_object"
+            }
+            ProcessTypeDefinitionError::PrimaryDirectiveFieldNotFound { .. } => {
+                "process_type_definition/primary_directive_field_not_found"
+            }
+            ProcessTypeDefinitionError::TypeExtensionMismatch { .. } => {
+                "process_type_definition/type_extension_mismatch"
+            }
+            ProcessTypeDefinitionError::ObjectImplementsItself { .. } => {
+                "process_type_definition/object_implements_itself"
+            }
+            ProcessTypeDefinitionError::TypeRefinementCycle { .. } => {
+                "process_type_definition/type_refinement_cycle"
+            }
+            ProcessTypeDefinitionError::ImplementedTypeIsNotInterface { .. } => {
+                "process_type_definition/implemented_type_is_not_interface"
+            }
+            ProcessTypeDefinitionError::UnionMemberTypeIsNotObject { .. } => {
+                "process_type_definition/union_member_type_is_not_object"
+            }
+            ProcessTypeDefinitionError::MissingTransitiveInterface { .. } => {
+                "process_type_definition/missing_transitive_interface"
+            }
+            ProcessTypeDefinitionError::MissingInterfaceField { .. } => {
+                "process_type_definition/missing_interface_field"
+            }
+            ProcessTypeDefinitionError::InterfaceFieldTypeMismatch { .. } => {
+                "process_type_definition/interface_field_type_mismatch"
+            }
+            ProcessTypeDefinitionError::MissingInterfaceFieldArgument { .. } => {
+                "process_type_definition/missing_interface_field_argument"
+            }
+            ProcessTypeDefinitionError::InterfaceFieldArgumentTypeMismatch { .. } => {
+                "process_type_definition/interface_field_argument_type_mismatch"
+            }
+            ProcessTypeDefinitionError::RequiredArgumentNotInInterfaceField { .. } => {
+                "process_type_definition/required_argument_not_in_interface_field"
+            }
+            ProcessTypeDefinitionError::ExtendedTypeNotDefined { .. } => {
+                "process_type_definition/extended_type_not_defined"
+            }
+            ProcessTypeDefinitionError::DuplicateTypeRefinement { .. } => {
+                "process_type_definition/duplicate_type_refinement"
+            }
+            ProcessTypeDefinitionError::DuplicateEnumValue { .. } => {
+                "process_type_definition/duplicate_enum_value"
+            }
+            ProcessTypeDefinitionError::RootOperationTypeAlreadyDefined { .. } => {
+                "process_type_definition/root_operation_type_already_defined"
+            }
+            ProcessTypeDefinitionError::DuplicateSchemaDefinition => {
+                "process_type_definition/duplicate_schema_definition"
+            }
+            ProcessTypeDefinitionError::RootTypeMustBeObject => {
+                "process_type_definition/root_type_must_be_object"
+            }
+            ProcessTypeDefinitionError::FailedToDeserialize(_) => {
+                "process_type_definition/failed_to_deserialize"
+            }
+        }
+    }
+
+    fn related_locations(&self) -> Vec<WithLocation<String>> {
+        match self {
+            ProcessTypeDefinitionError::MissingInterfaceField {
+                interface_field_location,
+                ..
+            }
+            | ProcessTypeDefinitionError::InterfaceFieldTypeMismatch {
+                interface_field_location,
+                ..
+            }
+            | ProcessTypeDefinitionError::RequiredArgumentNotInInterfaceField {
+                interface_field_location,
+                ..
+            } => vec![WithLocation::new(
+                "The interface field is defined here:".to_string(),
+                *interface_field_location,
+            )],
+            ProcessTypeDefinitionError::MissingInterfaceFieldArgument {
+                interface_argument_location,
+                ..
+            }
+            | ProcessTypeDefinitionError::InterfaceFieldArgumentTypeMismatch {
+                interface_argument_location,
+                ..
+            } => vec![WithLocation::new(
+                "The interface argument is defined here:".to_string(),
+                *interface_argument_location,
+            )],
+            _ => vec![],
+        }
+    }
+}
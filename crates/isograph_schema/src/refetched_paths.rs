@@ -71,6 +71,13 @@ pub fn refetched_paths_with_path(
                     path.pop();
                 }
             },
+            Selection::TypeRefinement(type_refinement) => {
+                // A type refinement selects from the same object, so the path is unchanged.
+                let new_paths =
+                    refetched_paths_with_path(&type_refinement.selection_set, schema, path);
+
+                paths.extend(new_paths.into_iter());
+            }
         };
     }
 
@@ -109,7 +109,10 @@ impl UnvalidatedSchema {
             schema_data: SchemaData {
                 objects,
                 scalars,
+                enums: vec![],
+                input_objects: vec![],
                 defined_types,
+                supertype_to_subtype_map: Default::default(),
             },
 
             id_type_id,
@@ -119,6 +122,32 @@ impl UnvalidatedSchema {
             boolean_type_id,
 
             query_type_id: None,
+            subscription_type_id: None,
+            mutation_type_id: None,
+        }
+    }
+}
+
+// Deriving Clone on Schema would require every associated type of every
+// SchemaValidationState to be Clone, so we only implement it for the unvalidated
+// schema, which the CLI clones in order to reuse the processed server schema.
+impl Clone for UnvalidatedSchema {
+    fn clone(&self) -> Self {
+        Self {
+            server_fields: self.server_fields.clone(),
+            client_fields: self.client_fields.clone(),
+            entrypoints: self.entrypoints.clone(),
+            schema_data: self.schema_data.clone(),
+
+            id_type_id: self.id_type_id,
+            string_type_id: self.string_type_id,
+            int_type_id: self.int_type_id,
+            float_type_id: self.float_type_id,
+            boolean_type_id: self.boolean_type_id,
+
+            query_type_id: self.query_type_id,
+            subscription_type_id: self.subscription_type_id,
+            mutation_type_id: self.mutation_type_id,
         }
     }
 }
@@ -140,6 +169,7 @@ fn add_schema_defined_scalar_type(
         name: typename,
         id: scalar_id,
         javascript_name,
+        javascript_import_path: None,
     });
     defined_types.insert(
         typename.item.into(),
@@ -1,5 +1,5 @@
 use common_lang_types::{
-    IsographObjectTypeName, Location, ScalarFieldName, TextSource, UnvalidatedTypeName,
+    ErrorCode, IsographObjectTypeName, Location, ScalarFieldName, TextSource, UnvalidatedTypeName,
     WithLocation, WithSpan,
 };
 use isograph_lang_types::{ClientFieldId, EntrypointTypeAndField, ObjectId, SelectableFieldId};
@@ -42,26 +42,31 @@ impl UnvalidatedSchema {
 
         match parent_type_id {
             SelectableFieldId::Object(object_id) => {
-                // For now, only the query object is fetchable, and thus
-                // can be used as a parent type in an iso entrypoint declaration.
+                // For now, only the root query, subscription and mutation objects are
+                // fetchable, and thus can be used as a parent type in an iso entrypoint
+                // declaration.
                 //
                 // This requirement should be loosened — anything that we
                 // know how to fetch (e.g. viewer, an item implementing Node, etc.)
                 // should be fetchable.
-                let query_id = self.query_type_id.ok_or(WithLocation::new(
-                    ValidateEntrypointDeclarationError::RootQueryTypeMustExist,
-                    Location::generated(),
-                ))?;
+                //
+                // Every schema must have a root query type, even if the entrypoint is
+                // on the subscription or mutation type.
+                if self.query_type_id.is_none() {
+                    return Err(WithLocation::new(
+                        ValidateEntrypointDeclarationError::RootQueryTypeMustExist,
+                        Location::generated(),
+                    ));
+                }
 
-                if query_id != *object_id {
-                    Err(WithLocation::new(
+                match self.fetchable_operation_kind(*object_id) {
+                    Some(_) => Ok(*object_id),
+                    None => Err(WithLocation::new(
                         ValidateEntrypointDeclarationError::NonFetchableParentType {
                             parent_type_name: parent_type.item,
                         },
                         Location::new(text_source, parent_type.span),
-                    ))
-                } else {
-                    Ok(*object_id)
+                    )),
                 }
             }
             SelectableFieldId::Scalar(scalar_id) => {
@@ -74,6 +79,26 @@ impl UnvalidatedSchema {
                     Location::new(text_source, parent_type.span),
                 ))
             }
+            SelectableFieldId::Enum(enum_id) => {
+                let enum_name = self.schema_data.enum_(*enum_id).name;
+                Err(WithLocation::new(
+                    ValidateEntrypointDeclarationError::InvalidParentType {
+                        parent_type: "enum",
+                        parent_type_name: enum_name.item.into(),
+                    },
+                    Location::new(text_source, parent_type.span),
+                ))
+            }
+            SelectableFieldId::InputObject(input_object_id) => {
+                let input_object_name = self.schema_data.input_object(*input_object_id).name;
+                Err(WithLocation::new(
+                    ValidateEntrypointDeclarationError::InvalidParentType {
+                        parent_type: "input object",
+                        parent_type_name: input_object_name.item.into(),
+                    },
+                    Location::new(text_source, parent_type.span),
+                ))
+            }
         }
     }
 
@@ -127,7 +152,8 @@ pub enum ValidateEntrypointDeclarationError {
     RootQueryTypeMustExist,
 
     #[error(
-        "The type `{parent_type_name}` is not fetchable. (Currently, only Query is fetchable.)"
+        "The type `{parent_type_name}` is not fetchable. \
+        (Currently, only Query, Subscription and Mutation are fetchable.)"
     )]
     NonFetchableParentType {
         parent_type_name: UnvalidatedTypeName,
@@ -146,3 +172,28 @@ pub enum ValidateEntrypointDeclarationError {
         resolver_field_name: ScalarFieldName,
     },
 }
+
+impl ErrorCode for ValidateEntrypointDeclarationError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            ValidateEntrypointDeclarationError::ParentTypeNotDefined { .. } => {
+                "validate_entrypoint_declaration/parent_type_not_defined"
+            }
+            ValidateEntrypointDeclarationError::InvalidParentType { .. } => {
+                "validate_entrypoint_declaration/invalid_parent_type"
+            }
+            ValidateEntrypointDeclarationError::RootQueryTypeMustExist => {
+                "validate_entrypoint_declaration/root_query_type_must_exist"
+            }
+            ValidateEntrypointDeclarationError::NonFetchableParentType { .. } => {
+                "validate_entrypoint_declaration/non_fetchable_parent_type"
+            }
+            ValidateEntrypointDeclarationError::ResolverFieldMustExist { .. } => {
+                "validate_entrypoint_declaration/resolver_field_must_exist"
+            }
+            ValidateEntrypointDeclarationError::FieldMustBeResolverField { .. } => {
+                "validate_entrypoint_declaration/field_must_be_resolver_field"
+            }
+        }
+    }
+}
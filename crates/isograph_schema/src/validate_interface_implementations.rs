@@ -0,0 +1,249 @@
+use common_lang_types::{InputTypeName, Location, UnvalidatedTypeName, WithLocation};
+use graphql_lang_types::{NonNullTypeAnnotation, TypeAnnotation};
+use isograph_lang_types::{ObjectId, SelectableFieldId};
+
+use crate::{ProcessTypeDefinitionError, ProcessTypeDefinitionResult, UnvalidatedSchema};
+
+impl UnvalidatedSchema {
+    /// Validate that implementing_type_id is a valid implementation of interface_id,
+    /// as described in https://spec.graphql.org/October2021/#IsValidImplementation():
+    /// - the implementing type must implement every interface the interface implements,
+    /// - it must have every field of the interface, with a compatible type, and
+    /// - those fields must accept every argument of the interface field (with the same
+    ///   type), and any additional argument must be optional.
+    ///
+    /// location is the location at which the implementing type implements the interface.
+    pub(crate) fn validate_interface_implementation(
+        &self,
+        interface_id: ObjectId,
+        implementing_type_id: ObjectId,
+        location: Location,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let interface = self.schema_data.object(interface_id);
+        let implementing_type = self.schema_data.object(implementing_type_id);
+
+        for transitive_interface_id in &interface.implemented_interfaces {
+            let implements_transitive_interface = implementing_type
+                .implemented_interfaces
+                .iter()
+                .any(|interface_id| interface_id.item == transitive_interface_id.item);
+            if !implements_transitive_interface {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::MissingTransitiveInterface {
+                        implementing_type_name: implementing_type.name,
+                        interface_name: interface.name,
+                        transitive_interface_name: self
+                            .schema_data
+                            .object(transitive_interface_id.item)
+                            .name,
+                    },
+                    location,
+                ));
+            }
+        }
+
+        for interface_field_id in &interface.server_fields {
+            let interface_field = &self.server_fields[interface_field_id.as_usize()];
+            let interface_field_location = interface_field.name.location;
+
+            let field = match implementing_type
+                .server_fields
+                .iter()
+                .map(|field_id| &self.server_fields[field_id.as_usize()])
+                .find(|field| field.name.item == interface_field.name.item)
+            {
+                Some(field) => field,
+                None => {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::MissingInterfaceField {
+                            implementing_type_name: implementing_type.name,
+                            interface_name: interface.name,
+                            field_name: interface_field.name.item,
+                            interface_field_location,
+                        },
+                        location,
+                    ))
+                }
+            };
+
+            if !self.is_valid_implementation_field_type(
+                &field.associated_data,
+                &interface_field.associated_data,
+            ) {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::InterfaceFieldTypeMismatch {
+                        implementing_type_name: implementing_type.name,
+                        interface_name: interface.name,
+                        field_name: field.name.item,
+                        field_type: field.associated_data.to_string(),
+                        interface_field_type: interface_field.associated_data.to_string(),
+                        interface_field_location,
+                    },
+                    field.name.location,
+                ));
+            }
+
+            for interface_argument in &interface_field.arguments {
+                let interface_argument_location = interface_argument.item.name.location;
+                let argument =
+                    match field.arguments.iter().find(|argument| {
+                        argument.item.name.item == interface_argument.item.name.item
+                    }) {
+                        Some(argument) => argument,
+                        None => {
+                            return Err(WithLocation::new(
+                                ProcessTypeDefinitionError::MissingInterfaceFieldArgument {
+                                    implementing_type_name: implementing_type.name,
+                                    interface_name: interface.name,
+                                    field_name: field.name.item,
+                                    argument_name: interface_argument.item.name.item,
+                                    interface_argument_location,
+                                },
+                                field.name.location,
+                            ))
+                        }
+                    };
+
+                if !is_same_type(&argument.item.type_, &interface_argument.item.type_) {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::InterfaceFieldArgumentTypeMismatch {
+                            implementing_type_name: implementing_type.name,
+                            interface_name: interface.name,
+                            field_name: field.name.item,
+                            argument_name: argument.item.name.item,
+                            argument_type: argument.item.type_.to_string(),
+                            interface_argument_type: interface_argument.item.type_.to_string(),
+                            interface_argument_location,
+                        },
+                        argument.item.name.location,
+                    ));
+                }
+            }
+
+            for argument in &field.arguments {
+                let is_interface_argument =
+                    interface_field.arguments.iter().any(|interface_argument| {
+                        interface_argument.item.name.item == argument.item.name.item
+                    });
+                let is_required =
+                    !argument.item.type_.is_nullable() && argument.item.default_value.is_none();
+                if !is_interface_argument && is_required {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::RequiredArgumentNotInInterfaceField {
+                            implementing_type_name: implementing_type.name,
+                            interface_name: interface.name,
+                            field_name: field.name.item,
+                            argument_name: argument.item.name.item,
+                            interface_field_location,
+                        },
+                        argument.item.name.location,
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The type of a field is a valid implementation of the type of an interface field
+    /// if it is the same type, or a non-null or more specific version of it.
+    fn is_valid_implementation_field_type(
+        &self,
+        field_type: &TypeAnnotation<UnvalidatedTypeName>,
+        interface_field_type: &TypeAnnotation<UnvalidatedTypeName>,
+    ) -> bool {
+        match (field_type, interface_field_type) {
+            (TypeAnnotation::NonNull(non_null), TypeAnnotation::NonNull(interface_non_null)) => {
+                match (non_null.as_ref(), interface_non_null.as_ref()) {
+                    (
+                        NonNullTypeAnnotation::Named(named),
+                        NonNullTypeAnnotation::Named(interface_named),
+                    ) => self
+                        .is_valid_implementation_named_type(named.0.item, interface_named.0.item),
+                    (
+                        NonNullTypeAnnotation::List(list),
+                        NonNullTypeAnnotation::List(interface_list),
+                    ) => self.is_valid_implementation_field_type(&list.0, &interface_list.0),
+                    _ => false,
+                }
+            }
+            (_, TypeAnnotation::NonNull(_)) => false,
+            (TypeAnnotation::NonNull(non_null), _) => match non_null.as_ref() {
+                NonNullTypeAnnotation::Named(named) => match interface_field_type {
+                    TypeAnnotation::Named(interface_named) => self
+                        .is_valid_implementation_named_type(named.0.item, interface_named.0.item),
+                    _ => false,
+                },
+                NonNullTypeAnnotation::List(list) => match interface_field_type {
+                    TypeAnnotation::List(interface_list) => {
+                        self.is_valid_implementation_field_type(&list.0, &interface_list.0)
+                    }
+                    _ => false,
+                },
+            },
+            (TypeAnnotation::Named(named), TypeAnnotation::Named(interface_named)) => {
+                self.is_valid_implementation_named_type(named.0.item, interface_named.0.item)
+            }
+            (TypeAnnotation::List(list), TypeAnnotation::List(interface_list)) => {
+                self.is_valid_implementation_field_type(&list.0, &interface_list.0)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_valid_implementation_named_type(
+        &self,
+        type_name: UnvalidatedTypeName,
+        interface_type_name: UnvalidatedTypeName,
+    ) -> bool {
+        if type_name == interface_type_name {
+            return true;
+        }
+        match (
+            self.schema_data.defined_types.get(&type_name),
+            self.schema_data.defined_types.get(&interface_type_name),
+        ) {
+            (
+                Some(SelectableFieldId::Object(object_id)),
+                Some(SelectableFieldId::Object(supertype_id)),
+            ) => self
+                .schema_data
+                .supertype_to_subtype_map
+                .get(supertype_id)
+                .map(|subtype_ids| subtype_ids.contains(object_id))
+                .unwrap_or(false),
+            // Types that do not exist are reported when validating the type system,
+            // so they are not reported here.
+            (None, _) | (_, None) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Whether two types are the same, ignoring spans.
+fn is_same_type(
+    type_: &TypeAnnotation<InputTypeName>,
+    other_type: &TypeAnnotation<InputTypeName>,
+) -> bool {
+    match (type_, other_type) {
+        (TypeAnnotation::Named(named), TypeAnnotation::Named(other_named)) => {
+            named.0.item == other_named.0.item
+        }
+        (TypeAnnotation::List(list), TypeAnnotation::List(other_list)) => {
+            is_same_type(&list.0, &other_list.0)
+        }
+        (TypeAnnotation::NonNull(non_null), TypeAnnotation::NonNull(other_non_null)) => {
+            match (non_null.as_ref(), other_non_null.as_ref()) {
+                (
+                    NonNullTypeAnnotation::Named(named),
+                    NonNullTypeAnnotation::Named(other_named),
+                ) => named.0.item == other_named.0.item,
+                (NonNullTypeAnnotation::List(list), NonNullTypeAnnotation::List(other_list)) => {
+                    is_same_type(&list.0, &other_list.0)
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
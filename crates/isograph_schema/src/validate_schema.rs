@@ -1,22 +1,32 @@
+use std::collections::HashSet;
+
 use common_lang_types::{
-    InputTypeName, InputValueName, IsographObjectTypeName, ScalarFieldName, SelectableFieldName,
-    UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
+    Diagnostic, EnumLiteralValue, ErrorCode, FieldArgumentName, GraphQLInputObjectTypeName,
+    InputTypeName, InputValueName, IsographDirectiveName, IsographObjectTypeName, Location,
+    SelectableFieldName, StringLiteralValue, UnvalidatedTypeName, VariableName, WithLocation,
+    WithSpan,
+};
+use graphql_lang_types::{
+    ConstantValue, GraphQLInputValueDefinition, NamedTypeAnnotation, NonNullTypeAnnotation,
+    TypeAnnotation,
 };
-use graphql_lang_types::{GraphQLInputValueDefinition, NamedTypeAnnotation, TypeAnnotation};
+use intern::Lookup;
+use isograph_config::ConfigOptions;
 use isograph_lang_types::{
-    ClientFieldId, LinkedFieldSelection, ObjectId, ScalarFieldSelection, ScalarId,
-    SelectableFieldId, Selection, ServerFieldId, UnvalidatedScalarFieldSelection,
-    UnvalidatedSelection, VariableDefinition,
+    ClientFieldId, FragmentDirectiveUsage, LinkedFieldSelection, NonConstantValue, ObjectId,
+    ScalarFieldSelection, ScalarId, SelectableFieldId, Selection, SelectionFieldArgument,
+    ServerFieldId, ServerFieldSelection, TypeRefinementSelection, UnvalidatedScalarFieldSelection,
+    UnvalidatedSelection, UnvalidatedTypeRefinementSelection, Unwrap, VariableDefinition,
 };
 use thiserror::Error;
 
 use crate::{
-    refetched_paths::refetched_paths_with_path, ClientField, FieldDefinitionLocation,
-    NameAndArguments, PathToRefetchField, Schema, SchemaData, SchemaIdField, SchemaObject,
-    SchemaServerField, SchemaValidationState, UnvalidatedClientField,
-    UnvalidatedLinkedFieldSelection, UnvalidatedSchema, UnvalidatedSchemaData,
-    UnvalidatedSchemaField, UnvalidatedSchemaObject, UnvalidatedSchemaServerField,
-    ValidateEntrypointDeclarationError,
+    refetched_paths::refetched_paths_with_path, ClientField, ClientFieldVariant,
+    FieldDefinitionLocation, NameAndArguments, PathToRefetchField, ProcessTypeDefinitionError,
+    Schema, SchemaData, SchemaIdField, SchemaObject, SchemaServerField, SchemaValidationState,
+    UnvalidatedClientField, UnvalidatedLinkedFieldSelection, UnvalidatedSchema,
+    UnvalidatedSchemaData, UnvalidatedSchemaField, UnvalidatedSchemaObject,
+    UnvalidatedSchemaServerField, ValidateEntrypointDeclarationError,
 };
 
 pub type ValidatedSchemaServerField = SchemaServerField<TypeAnnotation<SelectableFieldId>>;
@@ -33,6 +43,14 @@ pub type ValidatedLinkedFieldSelection = LinkedFieldSelection<
 pub type ValidatedScalarFieldSelection = ScalarFieldSelection<
     <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
 >;
+pub type ValidatedServerFieldSelection = ServerFieldSelection<
+    <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
+    <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionLinkedFieldAssociatedData,
+>;
+pub type ValidatedTypeRefinementSelection = TypeRefinementSelection<
+    <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
+    <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionLinkedFieldAssociatedData,
+>;
 
 pub type ValidatedVariableDefinition = VariableDefinition<SelectableFieldId>;
 pub type ValidatedClientField = ClientField<
@@ -70,6 +88,8 @@ pub type ValidatedSchema = Schema<ValidatedSchemaState>;
 impl ValidatedSchema {
     pub fn validate_and_construct(
         unvalidated_schema: UnvalidatedSchema,
+        options: &ConfigOptions,
+        warnings: &mut Vec<Diagnostic>,
     ) -> Result<Self, Vec<WithLocation<ValidateSchemaError>>> {
         let mut errors = vec![];
 
@@ -98,6 +118,8 @@ impl ValidatedSchema {
             id_type_id: id_type,
             string_type_id: string_type,
             query_type_id,
+            subscription_type_id,
+            mutation_type_id,
             float_type_id,
             boolean_type_id,
             int_type_id,
@@ -127,10 +149,33 @@ impl ValidatedSchema {
             }
         };
 
+        errors.extend(validate_deprecated_field_selections(
+            &schema_data,
+            &updated_fields,
+            &updated_client_fields,
+            options,
+            warnings,
+        ));
+        errors.extend(validate_conditional_directive_variables(
+            &schema_data,
+            &updated_client_fields,
+            boolean_type_id,
+        ));
+        errors.extend(validate_unwraps(
+            &schema_data,
+            &updated_fields,
+            &updated_client_fields,
+            options,
+            warnings,
+        ));
+
         let SchemaData {
             objects,
             scalars,
+            enums,
+            input_objects,
             defined_types,
+            supertype_to_subtype_map,
         } = schema_data;
 
         if errors.is_empty() {
@@ -148,11 +193,16 @@ impl ValidatedSchema {
                 schema_data: SchemaData {
                     objects,
                     scalars,
+                    enums,
+                    input_objects,
                     defined_types,
+                    supertype_to_subtype_map,
                 },
                 id_type_id: id_type,
                 string_type_id: string_type,
                 query_type_id,
+                subscription_type_id,
+                mutation_type_id,
                 float_type_id,
                 boolean_type_id,
                 int_type_id,
@@ -163,6 +213,125 @@ impl ValidatedSchema {
     }
 }
 
+impl UnvalidatedSchema {
+    /// Validate the server fields, input objects and interface implementations defined
+    /// so far, i.e. that the types of fields, arguments and input object fields exist,
+    /// that fields have output types and arguments and input object fields have input
+    /// types, that no argument is defined twice, and that types that implement an
+    /// interface are valid implementations of it.
+    ///
+    /// This is run after processing the schema and again after processing each schema
+    /// extension, so that an invalid schema cannot be made valid by a later extension.
+    pub fn validate_type_system(&self) -> Result<(), Vec<WithLocation<ValidateSchemaError>>> {
+        let mut errors = vec![];
+
+        for field in &self.server_fields {
+            let parent_type_name = self.schema_data.object(field.parent_type_id).name;
+            let field_type = *field.associated_data.inner();
+            match self.schema_data.defined_types.get(&field_type) {
+                Some(SelectableFieldId::InputObject(_)) => errors.push(WithLocation::new(
+                    ValidateSchemaError::FieldTypeIsInputObject {
+                        parent_type_name,
+                        field_name: field.name.item,
+                        field_type,
+                    },
+                    field.name.location,
+                )),
+                Some(_) => {}
+                None => errors.push(WithLocation::new(
+                    ValidateSchemaError::FieldTypenameDoesNotExist {
+                        parent_type_name,
+                        field_name: field.name.item,
+                        field_type,
+                    },
+                    field.name.location,
+                )),
+            }
+
+            let mut argument_names = HashSet::new();
+            for argument in &field.arguments {
+                let argument_type = *argument.item.type_.inner();
+                match self.schema_data.defined_types.get(&argument_type.into()) {
+                    Some(SelectableFieldId::Object(_)) => errors.push(WithLocation::new(
+                        ValidateSchemaError::FieldArgumentTypeIsNotInputType {
+                            argument_name: argument.item.name.item,
+                            parent_type_name,
+                            field_name: field.name.item,
+                            argument_type,
+                        },
+                        argument.item.name.location,
+                    )),
+                    Some(_) => {}
+                    None => errors.push(WithLocation::new(
+                        ValidateSchemaError::FieldArgumentTypeDoesNotExist {
+                            argument_name: argument.item.name.item,
+                            parent_type_name,
+                            field_name: field.name.item,
+                            argument_type,
+                        },
+                        field.name.location,
+                    )),
+                }
+                if !argument_names.insert(argument.item.name.item) {
+                    errors.push(WithLocation::new(
+                        ValidateSchemaError::DuplicateFieldArgument {
+                            argument_name: argument.item.name.item,
+                            parent_type_name,
+                            field_name: field.name.item,
+                        },
+                        argument.item.name.location,
+                    ));
+                }
+            }
+        }
+
+        for input_object in &self.schema_data.input_objects {
+            for field in &input_object.fields {
+                let field_type = *field.item.type_.inner();
+                match self.schema_data.defined_types.get(&field_type.into()) {
+                    Some(SelectableFieldId::Object(_)) => errors.push(WithLocation::new(
+                        ValidateSchemaError::InputObjectFieldTypeIsNotInputType {
+                            parent_type_name: input_object.name.item,
+                            field_name: field.item.name.item,
+                            field_type,
+                        },
+                        field.item.name.location,
+                    )),
+                    Some(_) => {}
+                    None => errors.push(WithLocation::new(
+                        ValidateSchemaError::InputObjectFieldTypeDoesNotExist {
+                            parent_type_name: input_object.name.item,
+                            field_name: field.item.name.item,
+                            field_type,
+                        },
+                        field.item.name.location,
+                    )),
+                }
+            }
+        }
+
+        for object in &self.schema_data.objects {
+            for interface_id in &object.implemented_interfaces {
+                if let Err(error) = self.validate_interface_implementation(
+                    interface_id.item,
+                    object.id,
+                    interface_id.location,
+                ) {
+                    errors.push(error.map(|message| {
+                        ValidateSchemaError::InvalidInterfaceImplementation { message }
+                    }));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
 fn transform_object_field_ids(
     schema_fields: &[ValidatedSchemaServerField],
     validated_client_fields: &[ValidatedClientField],
@@ -173,10 +342,12 @@ fn transform_object_field_ids(
         server_fields,
         description,
         id,
+        kind,
         encountered_fields: unvalidated_encountered_fields,
         client_field_ids,
         id_field,
         directives,
+        implemented_interfaces,
     } = unvalidated_object;
 
     let validated_encountered_fields = unvalidated_encountered_fields
@@ -211,11 +382,13 @@ fn transform_object_field_ids(
         description,
         name,
         id,
+        kind,
         server_fields,
         encountered_fields: validated_encountered_fields,
         client_field_ids,
         id_field,
         directives,
+        implemented_interfaces,
     }
 }
 
@@ -313,6 +486,7 @@ fn validate_and_transform_field(
                 id: empty_field.id,
                 associated_data: field_type,
                 parent_type_id: empty_field.parent_type_id,
+                directives: empty_field.directives,
                 arguments: valid_arguments,
             });
         }
@@ -408,6 +582,7 @@ fn validate_client_field_selection_set(
                 id: unvalidated_client_field.id,
                 selection_set_and_unwraps: Some((selection_set, unwraps)),
                 variant: unvalidated_client_field.variant,
+                deprecation_reason: unvalidated_client_field.deprecation_reason,
                 variable_definitions,
                 type_and_field: unvalidated_client_field.type_and_field,
                 parent_object_id: unvalidated_client_field.parent_object_id,
@@ -420,6 +595,7 @@ fn validate_client_field_selection_set(
             id: unvalidated_client_field.id,
             selection_set_and_unwraps: None,
             variant: unvalidated_client_field.variant,
+            deprecation_reason: unvalidated_client_field.deprecation_reason,
             variable_definitions,
             type_and_field: unvalidated_client_field.type_and_field,
             parent_object_id: unvalidated_client_field.parent_object_id,
@@ -439,21 +615,46 @@ fn validate_variable_definitions(
                 // TODO this should be doable in the error branch
                 let type_string = vd.type_.to_string();
                 let inner_type = *vd.type_.inner();
+                let type_ = vd.type_.and_then(|type_name| {
+                    match schema_data.defined_types.get(&type_name) {
+                        // Variables are passed as arguments, so they must have input types.
+                        Some(SelectableFieldId::Object(_)) => Err(WithLocation::new(
+                            ValidateSchemaError::VariableDefinitionInnerTypeIsNotInputType {
+                                variable_name: vd.name.item,
+                                type_: type_string.clone(),
+                                inner_type,
+                            },
+                            vd.name.location,
+                        )),
+                        Some(type_id) => Ok(*type_id),
+                        None => Err(WithLocation::new(
+                            ValidateSchemaError::VariableDefinitionInnerTypeDoesNotExist {
+                                variable_name: vd.name.item,
+                                type_: type_string.clone(),
+                                inner_type,
+                            },
+                            vd.name.location,
+                        )),
+                    }
+                })?;
+
+                if let Some(default_value) = &vd.default_value {
+                    if !value_matches_type(schema_data, &default_value.item, &type_) {
+                        return Err(WithLocation::new(
+                            ValidateSchemaError::VariableDefinitionDefaultValueDoesNotMatchType {
+                                variable_name: vd.name.item,
+                                type_: type_string,
+                                default_value: default_value.item.to_string(),
+                            },
+                            default_value.location,
+                        ));
+                    }
+                }
+
                 Ok(VariableDefinition {
                     name: vd.name,
-                    type_: vd.type_.and_then(|type_name| {
-                        match schema_data.defined_types.get(&type_name) {
-                            Some(type_id) => Ok(*type_id),
-                            None => Err(WithLocation::new(
-                                ValidateSchemaError::VariableDefinitionInnerTypeDoesNotExist {
-                                    variable_name: vd.name.item,
-                                    type_: type_string,
-                                    inner_type,
-                                },
-                                vd.name.location,
-                            )),
-                        }
-                    })?,
+                    type_,
+                    default_value: vd.default_value,
                 })
             })
         })
@@ -509,6 +710,91 @@ fn validate_selections_error_to_validate_schema_error(
             field_parent_type_name,
             field_name,
         },
+        ValidateSelectionsError::TypeRefinementTypeDoesNotExist {
+            parent_type_name,
+            type_to_refine_to,
+        } => ValidateSchemaError::ClientFieldSelectionTypeRefinementTypeDoesNotExist {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name,
+            parent_type_name,
+            type_to_refine_to,
+        },
+        ValidateSelectionsError::TypeRefinementIsNotSubtype {
+            parent_type_name,
+            type_to_refine_to,
+        } => ValidateSchemaError::ClientFieldSelectionTypeRefinementIsNotSubtype {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name,
+            parent_type_name,
+            type_to_refine_to,
+        },
+        ValidateSelectionsError::InvalidEnumValue {
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            enum_name,
+            enum_value,
+        } => ValidateSchemaError::ClientFieldSelectionInvalidEnumValue {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name,
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            enum_name,
+            enum_value,
+        },
+        ValidateSelectionsError::EnumValueForNonEnumArgument {
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type_name,
+            enum_value,
+        } => ValidateSchemaError::ClientFieldSelectionEnumValueForNonEnumArgument {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name,
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type_name,
+            enum_value,
+        },
+        ValidateSelectionsError::InvalidArgumentValue {
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type,
+            value,
+        } => ValidateSchemaError::ClientFieldSelectionInvalidArgumentValue {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name,
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type,
+            value,
+        },
+        ValidateSelectionsError::UnsupportedSelectionDirective {
+            field_parent_type_name,
+            field_name,
+            directive_name,
+        } => ValidateSchemaError::ClientFieldSelectionUnsupportedDirective {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name,
+            field_parent_type_name,
+            field_name,
+            directive_name,
+        },
+        ValidateSelectionsError::InvalidConditionalDirectiveArguments {
+            field_parent_type_name,
+            field_name,
+            directive_name,
+        } => ValidateSchemaError::ClientFieldSelectionInvalidConditionalDirectiveArguments {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name,
+            field_parent_type_name,
+            field_name,
+            directive_name,
+        },
     })
 }
 
@@ -534,6 +820,45 @@ enum ValidateSelectionsError {
         field_parent_type_name: IsographObjectTypeName,
         field_name: SelectableFieldName,
     },
+    TypeRefinementTypeDoesNotExist {
+        parent_type_name: IsographObjectTypeName,
+        type_to_refine_to: UnvalidatedTypeName,
+    },
+    TypeRefinementIsNotSubtype {
+        parent_type_name: IsographObjectTypeName,
+        type_to_refine_to: UnvalidatedTypeName,
+    },
+    InvalidEnumValue {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        enum_name: InputTypeName,
+        enum_value: EnumLiteralValue,
+    },
+    EnumValueForNonEnumArgument {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        argument_type_name: InputTypeName,
+        enum_value: EnumLiteralValue,
+    },
+    InvalidArgumentValue {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        argument_type: String,
+        value: String,
+    },
+    UnsupportedSelectionDirective {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        directive_name: IsographDirectiveName,
+    },
+    InvalidConditionalDirectiveArguments {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        directive_name: IsographDirectiveName,
+    },
 }
 
 fn validate_client_field_definition_selections_exist_and_types_match(
@@ -565,26 +890,112 @@ fn validate_client_field_definition_selection_exists_and_type_matches(
     server_fields: &[UnvalidatedSchemaServerField],
 ) -> ValidateSelectionsResult<WithSpan<ValidatedSelection>> {
     selection.and_then(|selection| {
-        selection.and_then(&mut |field_selection| {
-            field_selection.and_then(
-                &mut |scalar_field_selection| {
-                    validate_field_type_exists_and_is_scalar(
-                        schema_data,
-                        parent_object,
-                        scalar_field_selection,
-                        server_fields,
-                    )
+        selection.and_then(
+            &mut |field_selection| {
+                field_selection.and_then(
+                    &mut |scalar_field_selection| {
+                        validate_field_type_exists_and_is_scalar(
+                            schema_data,
+                            parent_object,
+                            scalar_field_selection,
+                            server_fields,
+                        )
+                    },
+                    &mut |linked_field_selection| {
+                        validate_field_type_exists_and_is_linked(
+                            schema_data,
+                            parent_object,
+                            linked_field_selection,
+                            server_fields,
+                        )
+                    },
+                )
+            },
+            &mut |type_refinement_selection| {
+                validate_type_refinement(
+                    schema_data,
+                    parent_object,
+                    type_refinement_selection,
+                    server_fields,
+                )
+            },
+        )
+    })
+}
+
+/// Given that we refined the parent to a type, that type should be the parent
+/// itself, a subtype of the parent (e.g. an object that implements the parent
+/// interface), or a supertype of the parent (e.g. an interface that the parent
+/// implements).
+fn validate_type_refinement(
+    schema_data: &UnvalidatedSchemaData,
+    parent_object: &UnvalidatedSchemaObject,
+    type_refinement_selection: UnvalidatedTypeRefinementSelection,
+    server_fields: &[UnvalidatedSchemaServerField],
+) -> ValidateSelectionsResult<ValidatedTypeRefinementSelection> {
+    let type_to_refine_to = type_refinement_selection.type_to_refine_to;
+    let refined_object_id = match schema_data.defined_types.get(&type_to_refine_to.item) {
+        Some(SelectableFieldId::Object(object_id)) => *object_id,
+        Some(SelectableFieldId::Scalar(_))
+        | Some(SelectableFieldId::Enum(_))
+        | Some(SelectableFieldId::InputObject(_)) => {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::TypeRefinementIsNotSubtype {
+                    parent_type_name: parent_object.name,
+                    type_to_refine_to: type_to_refine_to.item,
                 },
-                &mut |linked_field_selection| {
-                    validate_field_type_exists_and_is_linked(
-                        schema_data,
-                        parent_object,
-                        linked_field_selection,
-                        server_fields,
-                    )
+                type_to_refine_to.location,
+            ))
+        }
+        None => {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::TypeRefinementTypeDoesNotExist {
+                    parent_type_name: parent_object.name,
+                    type_to_refine_to: type_to_refine_to.item,
                 },
-            )
-        })
+                type_to_refine_to.location,
+            ))
+        }
+    };
+
+    let is_subtype = |subtype_id: ObjectId, supertype_id: ObjectId| {
+        schema_data
+            .supertype_to_subtype_map
+            .get(&supertype_id)
+            .map(|subtype_ids| subtype_ids.contains(&subtype_id))
+            .unwrap_or(false)
+    };
+    if refined_object_id != parent_object.id
+        && !is_subtype(refined_object_id, parent_object.id)
+        && !is_subtype(parent_object.id, refined_object_id)
+    {
+        return Err(WithLocation::new(
+            ValidateSelectionsError::TypeRefinementIsNotSubtype {
+                parent_type_name: parent_object.name,
+                type_to_refine_to: type_to_refine_to.item,
+            },
+            type_to_refine_to.location,
+        ));
+    }
+
+    let refined_object = schema_data.object(refined_object_id);
+    Ok(TypeRefinementSelection {
+        type_to_refine_to,
+        associated_data: ValidatedLinkedFieldAssociatedData {
+            parent_object_id: refined_object_id,
+        },
+        selection_set: type_refinement_selection
+            .selection_set
+            .into_iter()
+            .map(|selection| {
+                validate_client_field_definition_selection_exists_and_type_matches(
+                    selection,
+                    refined_object,
+                    schema_data,
+                    server_fields,
+                )
+            })
+            .collect::<Result<_, _>>()?,
     })
 }
 
@@ -608,35 +1019,67 @@ fn validate_field_type_exists_and_is_scalar(
                         was validated earlier, probably indicates a bug in Isograph",
                     );
                 match field_type_id {
-                    SelectableFieldId::Scalar(_scalar_id) => Ok(ScalarFieldSelection {
-                        name: scalar_field_selection.name,
-                        associated_data: FieldDefinitionLocation::Server(
-                            find_server_field_id(
-                                server_fields,
-                                scalar_field_selection.name.item,
-                                &parent_object.server_fields,
-                            )
-                            .expect("Expected to find scalar field, this probably indicates a bug in Isograph"),
-                        ),
-                        reader_alias: scalar_field_selection.reader_alias,
-                        normalization_alias: scalar_field_selection.normalization_alias,
-                        unwraps: scalar_field_selection.unwraps,
-                        arguments: scalar_field_selection.arguments,
-                    }),
-                    SelectableFieldId::Object(_) => Err(
-                        WithLocation::new(
-                            ValidateSelectionsError::FieldSelectedAsScalarButTypeIsNotScalar {
-                                field_parent_type_name: parent_object.name,
-                                field_name: scalar_field_name,
-                                target_type: "an object",
-                                target_type_name: *server_field_name.inner(),
-                            },
-                            scalar_field_selection.name.location
-                        ),
-                    ),
+                    SelectableFieldId::Scalar(_) | SelectableFieldId::Enum(_) => {
+                        let server_field_id = find_server_field_id(
+                            server_fields,
+                            scalar_field_name,
+                            &parent_object.server_fields,
+                        )
+                        .expect("Expected to find scalar field, this probably indicates a bug in Isograph");
+                        validate_argument_values(
+                            schema_data,
+                            parent_object,
+                            &server_fields[server_field_id.as_usize()],
+                            &scalar_field_selection.arguments,
+                        )?;
+                        validate_selection_directives(
+                            parent_object,
+                            scalar_field_name,
+                            scalar_field_selection.name.location,
+                            &scalar_field_selection.directives,
+                            true,
+                        )?;
+                        Ok(ScalarFieldSelection {
+                            name: scalar_field_selection.name,
+                            associated_data: FieldDefinitionLocation::Server(server_field_id),
+                            reader_alias: scalar_field_selection.reader_alias,
+                            normalization_alias: scalar_field_selection.normalization_alias,
+                            unwraps: scalar_field_selection.unwraps,
+                            arguments: scalar_field_selection.arguments,
+                            directives: scalar_field_selection.directives,
+                        })
+                    }
+                    SelectableFieldId::Object(_) => Err(WithLocation::new(
+                        ValidateSelectionsError::FieldSelectedAsScalarButTypeIsNotScalar {
+                            field_parent_type_name: parent_object.name,
+                            field_name: scalar_field_name,
+                            target_type: "an object",
+                            target_type_name: *server_field_name.inner(),
+                        },
+                        scalar_field_selection.name.location,
+                    )),
+                    // Input objects can never be selected.
+                    SelectableFieldId::InputObject(_) => Err(WithLocation::new(
+                        ValidateSelectionsError::FieldSelectedAsScalarButTypeIsNotScalar {
+                            field_parent_type_name: parent_object.name,
+                            field_name: scalar_field_name,
+                            target_type: "an input object",
+                            target_type_name: *server_field_name.inner(),
+                        },
+                        scalar_field_selection.name.location,
+                    )),
                 }
             }
             FieldDefinitionLocation::Client(client_field_id) => {
+                // Client fields are not part of the query text, so they cannot
+                // be conditionally included.
+                validate_selection_directives(
+                    parent_object,
+                    scalar_field_name,
+                    scalar_field_selection.name.location,
+                    &scalar_field_selection.directives,
+                    false,
+                )?;
                 // TODO confirm this works if resolver_name is an alias
                 Ok(ScalarFieldSelection {
                     name: scalar_field_selection.name,
@@ -645,6 +1088,7 @@ fn validate_field_type_exists_and_is_scalar(
                     associated_data: FieldDefinitionLocation::Client(*client_field_id),
                     arguments: scalar_field_selection.arguments,
                     normalization_alias: scalar_field_selection.normalization_alias,
+                    directives: scalar_field_selection.directives,
                 })
             }
         },
@@ -684,8 +1128,46 @@ fn validate_field_type_exists_and_is_linked(
                         },
                         linked_field_selection.name.location,
                     )),
+                    SelectableFieldId::Enum(_) => Err(WithLocation::new(
+                        ValidateSelectionsError::FieldSelectedAsLinkedButTypeIsScalar {
+                            field_parent_type_name: parent_object.name,
+                            field_name: linked_field_name,
+                            target_type: "an enum",
+                            target_type_name: *server_field_name.inner(),
+                        },
+                        linked_field_selection.name.location,
+                    )),
+                    // Input objects can never be selected.
+                    SelectableFieldId::InputObject(_) => Err(WithLocation::new(
+                        ValidateSelectionsError::FieldSelectedAsLinkedButTypeIsScalar {
+                            field_parent_type_name: parent_object.name,
+                            field_name: linked_field_name,
+                            target_type: "an input object",
+                            target_type_name: *server_field_name.inner(),
+                        },
+                        linked_field_selection.name.location,
+                    )),
                     SelectableFieldId::Object(object_id) => {
                         let object = schema_data.objects.get(object_id.as_usize()).unwrap();
+                        let server_field_id = find_server_field_id(
+                            server_fields,
+                            linked_field_name,
+                            &parent_object.server_fields,
+                        )
+                        .expect("Expected to find linked field, this probably indicates a bug in Isograph");
+                        validate_argument_values(
+                            schema_data,
+                            parent_object,
+                            &server_fields[server_field_id.as_usize()],
+                            &linked_field_selection.arguments,
+                        )?;
+                        validate_selection_directives(
+                            parent_object,
+                            linked_field_name,
+                            linked_field_selection.name.location,
+                            &linked_field_selection.directives,
+                            true,
+                        )?;
                         Ok(LinkedFieldSelection {
                                 name: linked_field_selection.name,
                                 reader_alias: linked_field_selection.reader_alias,
@@ -705,6 +1187,7 @@ fn validate_field_type_exists_and_is_linked(
                                     parent_object_id: object_id,
                                 },
                                 arguments: linked_field_selection.arguments,
+                                directives: linked_field_selection.directives,
                             })
                     }
                 }
@@ -724,68 +1207,726 @@ fn validate_field_type_exists_and_is_linked(
     }
 }
 
-fn find_server_field_id(
-    server_fields: &[UnvalidatedSchemaServerField],
-    field_name: ScalarFieldName,
-    parent_server_fields: &[ServerFieldId],
-) -> Option<ServerFieldId> {
-    parent_server_fields.iter().find_map(|server_field_id| {
-        let server_field = &server_fields[server_field_id.as_usize()];
-        if server_field.name.item == field_name.into() {
-            Some(*server_field_id)
-        } else {
-            None
+/// Only `@skip(if: ...)` and `@include(if: ...)` may be used on selections, and only
+/// on server fields, as they are passed through to the query text. The `if` argument
+/// must be a boolean literal or a variable.
+fn validate_selection_directives(
+    parent_object: &UnvalidatedSchemaObject,
+    field_name: SelectableFieldName,
+    location: Location,
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+    is_server_field: bool,
+) -> ValidateSelectionsResult<()> {
+    for directive in directives {
+        let directive_name = directive.item.name.item;
+        if !is_server_field || !matches!(directive_name.lookup(), "skip" | "include") {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::UnsupportedSelectionDirective {
+                    field_parent_type_name: parent_object.name,
+                    field_name,
+                    directive_name,
+                },
+                location,
+            ));
         }
-    })
+
+        let has_valid_arguments = match directive.item.arguments.as_slice() {
+            [argument] => {
+                argument.item.name.item.lookup() == "if"
+                    && matches!(
+                        argument.item.value.item,
+                        NonConstantValue::Variable(_) | NonConstantValue::Boolean(_)
+                    )
+            }
+            _ => false,
+        };
+        if !has_valid_arguments {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::InvalidConditionalDirectiveArguments {
+                    field_parent_type_name: parent_object.name,
+                    field_name,
+                    directive_name,
+                },
+                location,
+            ));
+        }
+    }
+    Ok(())
 }
 
-type ValidateSchemaResult<T> = Result<T, WithLocation<ValidateSchemaError>>;
+/// Literals passed as arguments to a server field must match the type that the
+/// argument is declared with. Variables are validated elsewhere.
+fn validate_argument_values(
+    schema_data: &UnvalidatedSchemaData,
+    parent_object: &UnvalidatedSchemaObject,
+    server_field: &UnvalidatedSchemaServerField,
+    arguments: &[WithLocation<SelectionFieldArgument>],
+) -> ValidateSelectionsResult<()> {
+    for argument in arguments {
+        let value = match argument.item.value.item.to_constant_value() {
+            Some(value) => value,
+            None => continue,
+        };
+        let argument_definition = match server_field.arguments.iter().find(|definition| {
+            FieldArgumentName::from(definition.item.name.item) == argument.item.name.item
+        }) {
+            Some(argument_definition) => argument_definition,
+            None => continue,
+        };
+        let argument_type_name = *argument_definition.item.type_.inner();
 
-#[derive(Debug, Error)]
-pub enum ValidateSchemaError {
-    #[error(
-        "The field `{parent_type_name}.{field_name}` has inner type `{field_type}`, which does not exist."
-    )]
-    FieldTypenameDoesNotExist {
-        parent_type_name: IsographObjectTypeName,
-        field_name: SelectableFieldName,
-        field_type: UnvalidatedTypeName,
-    },
+        if let ConstantValue::Enum(enum_value) = value {
+            match schema_data
+                .defined_types
+                .get(&UnvalidatedTypeName::from(argument_type_name))
+            {
+                Some(SelectableFieldId::Enum(enum_id)) => {
+                    let schema_enum = schema_data.enum_(*enum_id);
+                    if !schema_enum.has_value(enum_value) {
+                        return Err(WithLocation::new(
+                            ValidateSelectionsError::InvalidEnumValue {
+                                field_parent_type_name: parent_object.name,
+                                field_name: server_field.name.item,
+                                argument_name: argument.item.name.item,
+                                enum_name: argument_type_name,
+                                enum_value,
+                            },
+                            argument.location,
+                        ));
+                    }
+                }
+                Some(_) => {
+                    return Err(WithLocation::new(
+                        ValidateSelectionsError::EnumValueForNonEnumArgument {
+                            field_parent_type_name: parent_object.name,
+                            field_name: server_field.name.item,
+                            argument_name: argument.item.name.item,
+                            argument_type_name,
+                            enum_value,
+                        },
+                        argument.location,
+                    ));
+                }
+                // Argument types that do not exist are reported elsewhere.
+                None => {}
+            }
+            continue;
+        }
 
-    #[error(
-        "The argument `{argument_name}` on field `{parent_type_name}.{field_name}` has inner type `{argument_type}`, which does not exist."
-    )]
-    FieldArgumentTypeDoesNotExist {
-        argument_name: InputValueName,
-        parent_type_name: IsographObjectTypeName,
-        field_name: SelectableFieldName,
-        argument_type: InputTypeName,
-    },
+        // Argument types that do not exist are reported elsewhere.
+        let argument_type =
+            match resolve_type_annotation(schema_data, &argument_definition.item.type_) {
+                Some(argument_type) => argument_type,
+                None => continue,
+            };
+        if !value_matches_type(schema_data, &value, &argument_type) {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::InvalidArgumentValue {
+                    field_parent_type_name: parent_object.name,
+                    field_name: server_field.name.item,
+                    argument_name: argument.item.name.item,
+                    argument_type: argument_definition.item.type_.to_string(),
+                    value: value.to_string(),
+                },
+                argument.location,
+            ));
+        }
+    }
+    Ok(())
+}
 
-    #[error(
-        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
-        the field `{field_parent_type_name}.{field_name}` is selected, but that \
-        field does not exist on `{field_parent_type_name}`"
-    )]
-    ClientFieldSelectionFieldDoesNotExist {
-        client_field_parent_type_name: IsographObjectTypeName,
-        client_field_name: SelectableFieldName,
-        field_parent_type_name: IsographObjectTypeName,
-        field_name: SelectableFieldName,
-    },
+fn resolve_type_annotation<TName: Into<UnvalidatedTypeName> + Clone>(
+    schema_data: &UnvalidatedSchemaData,
+    type_: &TypeAnnotation<TName>,
+) -> Option<TypeAnnotation<SelectableFieldId>> {
+    type_
+        .clone()
+        .and_then(|type_name| {
+            schema_data
+                .defined_types
+                .get(&type_name.into())
+                .copied()
+                .ok_or(())
+        })
+        .ok()
+}
 
-    #[error(
-        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
-        the field `{field_parent_type_name}.{field_name}` is selected as a scalar, \
-        but that field's type is `{target_type_name}`, which is {field_type}."
-    )]
-    ClientFieldSelectionFieldIsNotScalar {
-        client_field_parent_type_name: IsographObjectTypeName,
-        client_field_name: SelectableFieldName,
-        field_parent_type_name: IsographObjectTypeName,
-        field_name: SelectableFieldName,
-        field_type: &'static str,
-        target_type_name: UnvalidatedTypeName,
+fn value_matches_type(
+    schema_data: &UnvalidatedSchemaData,
+    value: &ConstantValue,
+    type_: &TypeAnnotation<SelectableFieldId>,
+) -> bool {
+    match type_ {
+        TypeAnnotation::Named(named) => {
+            value == &ConstantValue::Null || value_matches_named_type(schema_data, value, named)
+        }
+        TypeAnnotation::List(list) => {
+            value == &ConstantValue::Null || value_matches_list_type(schema_data, value, &list.0)
+        }
+        TypeAnnotation::NonNull(non_null) => match non_null.as_ref() {
+            NonNullTypeAnnotation::Named(named) => {
+                value_matches_named_type(schema_data, value, named)
+            }
+            NonNullTypeAnnotation::List(list) => {
+                value_matches_list_type(schema_data, value, &list.0)
+            }
+        },
+    }
+}
+
+fn value_matches_list_type(
+    schema_data: &UnvalidatedSchemaData,
+    value: &ConstantValue,
+    item_type: &TypeAnnotation<SelectableFieldId>,
+) -> bool {
+    match value {
+        ConstantValue::List(items) => items
+            .iter()
+            .all(|item| value_matches_type(schema_data, &item.item, item_type)),
+        // A single value is coerced to a list containing that value.
+        _ => value_matches_type(schema_data, value, item_type),
+    }
+}
+
+fn value_matches_named_type(
+    schema_data: &UnvalidatedSchemaData,
+    value: &ConstantValue,
+    named_type: &NamedTypeAnnotation<SelectableFieldId>,
+) -> bool {
+    match named_type.0.item {
+        SelectableFieldId::Scalar(scalar_id) => {
+            match schema_data.scalar(scalar_id).name.item.lookup() {
+                "Int" => matches!(value, ConstantValue::Int(_)),
+                "Float" => matches!(value, ConstantValue::Int(_) | ConstantValue::Float(_)),
+                "String" => matches!(value, ConstantValue::String(_)),
+                "Boolean" => matches!(value, ConstantValue::Boolean(_)),
+                "ID" => matches!(value, ConstantValue::Int(_) | ConstantValue::String(_)),
+                // We do not know which values custom scalars accept.
+                _ => true,
+            }
+        }
+        SelectableFieldId::Enum(enum_id) => match value {
+            ConstantValue::Enum(enum_value) => schema_data.enum_(enum_id).has_value(*enum_value),
+            _ => false,
+        },
+        SelectableFieldId::InputObject(input_object_id) => match value {
+            ConstantValue::Object(fields) => {
+                let input_object = schema_data.input_object(input_object_id);
+                let fields_match = fields.iter().all(|field| {
+                    match input_object.field(field.name.item.into()) {
+                        Some(field_definition) => {
+                            match resolve_type_annotation(schema_data, &field_definition.type_) {
+                                Some(field_type) => {
+                                    value_matches_type(schema_data, &field.value.item, &field_type)
+                                }
+                                // Field types that do not exist are reported elsewhere.
+                                None => true,
+                            }
+                        }
+                        None => false,
+                    }
+                });
+                // Fields that are non-null and have no default value must be provided.
+                let required_fields_are_provided =
+                    input_object.fields.iter().all(|field_definition| {
+                        field_definition.item.type_.is_nullable()
+                            || field_definition.item.default_value.is_some()
+                            || fields.iter().any(|field| {
+                                InputValueName::from(field.name.item)
+                                    == field_definition.item.name.item
+                            })
+                    });
+                fields_match && required_fields_are_provided
+            }
+            _ => false,
+        },
+        // Objects are not input types, so no value matches them.
+        SelectableFieldId::Object(_) => false,
+    }
+}
+
+/// Each selection of a field that is marked @deprecated in an iso literal is reported,
+/// according to the on_deprecated_field_selected config option.
+fn validate_deprecated_field_selections(
+    schema_data: &UnvalidatedSchemaData,
+    server_fields: &[ValidatedSchemaServerField],
+    client_fields: &[ValidatedClientField],
+    options: &ConfigOptions,
+    warnings: &mut Vec<Diagnostic>,
+) -> Vec<WithLocation<ValidateSchemaError>> {
+    let mut errors = vec![];
+    for client_field in client_fields {
+        // Refetch and mutation fields are generated, and not written in iso literals.
+        if !matches!(
+            client_field.variant,
+            ClientFieldVariant::Eager | ClientFieldVariant::Component
+        ) {
+            continue;
+        }
+        if let Some((selection_set, _)) = &client_field.selection_set_and_unwraps {
+            errors.extend(validate_deprecated_field_selections_in_selection_set(
+                schema_data,
+                server_fields,
+                client_fields,
+                options,
+                warnings,
+                client_field,
+                schema_data.object(client_field.parent_object_id),
+                selection_set,
+            ));
+        }
+    }
+    errors
+}
+
+fn validate_deprecated_field_selections_in_selection_set(
+    schema_data: &UnvalidatedSchemaData,
+    server_fields: &[ValidatedSchemaServerField],
+    client_fields: &[ValidatedClientField],
+    options: &ConfigOptions,
+    warnings: &mut Vec<Diagnostic>,
+    client_field: &ValidatedClientField,
+    parent_object: &UnvalidatedSchemaObject,
+    selection_set: &[WithSpan<ValidatedSelection>],
+) -> Vec<WithLocation<ValidateSchemaError>> {
+    let mut errors = vec![];
+    for selection in selection_set {
+        let (field_name, location, deprecation_reason) = match &selection.item {
+            Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                let deprecation_reason = match scalar_field.associated_data {
+                    FieldDefinitionLocation::Server(server_field_id) => {
+                        server_fields[server_field_id.as_usize()].deprecation_reason()
+                    }
+                    FieldDefinitionLocation::Client(client_field_id) => {
+                        client_fields[client_field_id.as_usize()].deprecation_reason
+                    }
+                };
+                (
+                    scalar_field.name.item.into(),
+                    scalar_field.name.location,
+                    deprecation_reason,
+                )
+            }
+            Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                errors.extend(validate_deprecated_field_selections_in_selection_set(
+                    schema_data,
+                    server_fields,
+                    client_fields,
+                    options,
+                    warnings,
+                    client_field,
+                    schema_data.object(linked_field.associated_data.parent_object_id),
+                    &linked_field.selection_set,
+                ));
+                let field_name = linked_field.name.item.into();
+                let deprecation_reason =
+                    find_server_field_id(server_fields, field_name, &parent_object.server_fields)
+                        .and_then(|server_field_id| {
+                            server_fields[server_field_id.as_usize()].deprecation_reason()
+                        });
+                (field_name, linked_field.name.location, deprecation_reason)
+            }
+            Selection::TypeRefinement(type_refinement) => {
+                errors.extend(validate_deprecated_field_selections_in_selection_set(
+                    schema_data,
+                    server_fields,
+                    client_fields,
+                    options,
+                    warnings,
+                    client_field,
+                    schema_data.object(type_refinement.associated_data.parent_object_id),
+                    &type_refinement.selection_set,
+                ));
+                continue;
+            }
+        };
+
+        if let Some(deprecation_reason) = deprecation_reason {
+            if let Err(error) = options
+                .on_deprecated_field_selected
+                .on_failure(warnings, || {
+                    WithLocation::new(
+                        ValidateSchemaError::ClientFieldSelectionFieldIsDeprecated {
+                            client_field_parent_type_name: client_field.type_and_field.type_name,
+                            client_field_name: client_field.name,
+                            field_parent_type_name: parent_object.name,
+                            field_name,
+                            deprecation_reason,
+                        },
+                        location,
+                    )
+                })
+            {
+                errors.push(error);
+            }
+        }
+    }
+    errors
+}
+
+/// The `if` argument of `@skip` and `@include` must be a boolean. Variables that
+/// are passed as the `if` argument must therefore be defined with type `Boolean`
+/// or `Boolean!`.
+fn validate_conditional_directive_variables(
+    schema_data: &UnvalidatedSchemaData,
+    client_fields: &[ValidatedClientField],
+    boolean_type_id: ScalarId,
+) -> Vec<WithLocation<ValidateSchemaError>> {
+    let mut errors = vec![];
+    for client_field in client_fields {
+        if let Some((selection_set, _)) = &client_field.selection_set_and_unwraps {
+            errors.extend(validate_conditional_directive_variables_in_selection_set(
+                schema_data,
+                boolean_type_id,
+                client_field,
+                schema_data.object(client_field.parent_object_id),
+                selection_set,
+            ));
+        }
+    }
+    errors
+}
+
+fn validate_conditional_directive_variables_in_selection_set(
+    schema_data: &UnvalidatedSchemaData,
+    boolean_type_id: ScalarId,
+    client_field: &ValidatedClientField,
+    parent_object: &UnvalidatedSchemaObject,
+    selection_set: &[WithSpan<ValidatedSelection>],
+) -> Vec<WithLocation<ValidateSchemaError>> {
+    let mut errors = vec![];
+    for selection in selection_set {
+        let (field_name, directives) = match &selection.item {
+            Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                (scalar_field.name.item.into(), &scalar_field.directives)
+            }
+            Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                errors.extend(validate_conditional_directive_variables_in_selection_set(
+                    schema_data,
+                    boolean_type_id,
+                    client_field,
+                    schema_data.object(linked_field.associated_data.parent_object_id),
+                    &linked_field.selection_set,
+                ));
+                (linked_field.name.item.into(), &linked_field.directives)
+            }
+            Selection::TypeRefinement(type_refinement) => {
+                errors.extend(validate_conditional_directive_variables_in_selection_set(
+                    schema_data,
+                    boolean_type_id,
+                    client_field,
+                    schema_data.object(type_refinement.associated_data.parent_object_id),
+                    &type_refinement.selection_set,
+                ));
+                continue;
+            }
+        };
+
+        for directive in directives {
+            for argument in directive.item.arguments.iter() {
+                let variable_name = match argument.item.value.item {
+                    NonConstantValue::Variable(variable_name) => variable_name,
+                    _ => continue,
+                };
+                // Variables that are not defined on this client field are defined
+                // by the client fields that (transitively) select it.
+                let variable_definition = match client_field
+                    .variable_definitions
+                    .iter()
+                    .find(|definition| definition.item.name.item == variable_name)
+                {
+                    Some(variable_definition) => variable_definition,
+                    None => continue,
+                };
+
+                let boolean_type = SelectableFieldId::Scalar(boolean_type_id);
+                let is_boolean = match &variable_definition.item.type_ {
+                    TypeAnnotation::Named(named_type) => named_type.0.item == boolean_type,
+                    type_ => type_
+                        .inner_non_null_named_type()
+                        .map(|named_type| named_type.0.item == boolean_type)
+                        .unwrap_or(false),
+                };
+                if !is_boolean {
+                    errors.push(WithLocation::new(
+                        ValidateSchemaError::ClientFieldSelectionConditionVariableIsNotBoolean {
+                            client_field_parent_type_name: client_field.type_and_field.type_name,
+                            client_field_name: client_field.name,
+                            field_parent_type_name: parent_object.name,
+                            field_name,
+                            directive_name: directive.item.name.item,
+                            variable_name,
+                        },
+                        argument.location,
+                    ));
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// `field!` asserts that the field is non-null, `field!!` additionally asserts that
+/// its list items are non-null, and so on. Only server fields can be unwrapped, and
+/// unwrapping a level that is already non-null is reported according to the
+/// on_unnecessary_unwrap config option.
+fn validate_unwraps(
+    schema_data: &UnvalidatedSchemaData,
+    server_fields: &[ValidatedSchemaServerField],
+    client_fields: &[ValidatedClientField],
+    options: &ConfigOptions,
+    warnings: &mut Vec<Diagnostic>,
+) -> Vec<WithLocation<ValidateSchemaError>> {
+    let mut errors = vec![];
+    for client_field in client_fields {
+        if let Some((selection_set, _)) = &client_field.selection_set_and_unwraps {
+            errors.extend(validate_unwraps_in_selection_set(
+                schema_data,
+                server_fields,
+                options,
+                warnings,
+                client_field,
+                schema_data.object(client_field.parent_object_id),
+                selection_set,
+            ));
+        }
+    }
+    errors
+}
+
+fn validate_unwraps_in_selection_set(
+    schema_data: &UnvalidatedSchemaData,
+    server_fields: &[ValidatedSchemaServerField],
+    options: &ConfigOptions,
+    warnings: &mut Vec<Diagnostic>,
+    client_field: &ValidatedClientField,
+    parent_object: &UnvalidatedSchemaObject,
+    selection_set: &[WithSpan<ValidatedSelection>],
+) -> Vec<WithLocation<ValidateSchemaError>> {
+    let mut errors = vec![];
+    for selection in selection_set {
+        let (field_name, location, unwraps, field_type) = match &selection.item {
+            Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                let field_type = match scalar_field.associated_data {
+                    FieldDefinitionLocation::Server(server_field_id) => {
+                        Some(&server_fields[server_field_id.as_usize()].associated_data)
+                    }
+                    FieldDefinitionLocation::Client(_) => None,
+                };
+                (
+                    scalar_field.name.item.into(),
+                    scalar_field.name.location,
+                    &scalar_field.unwraps,
+                    field_type,
+                )
+            }
+            Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                errors.extend(validate_unwraps_in_selection_set(
+                    schema_data,
+                    server_fields,
+                    options,
+                    warnings,
+                    client_field,
+                    schema_data.object(linked_field.associated_data.parent_object_id),
+                    &linked_field.selection_set,
+                ));
+                let field_name = linked_field.name.item.into();
+                let field_type =
+                    find_server_field_id(server_fields, field_name, &parent_object.server_fields)
+                        .map(|server_field_id| {
+                            &server_fields[server_field_id.as_usize()].associated_data
+                        });
+                (
+                    field_name,
+                    linked_field.name.location,
+                    &linked_field.unwraps,
+                    field_type,
+                )
+            }
+            Selection::TypeRefinement(type_refinement) => {
+                errors.extend(validate_unwraps_in_selection_set(
+                    schema_data,
+                    server_fields,
+                    options,
+                    warnings,
+                    client_field,
+                    schema_data.object(type_refinement.associated_data.parent_object_id),
+                    &type_refinement.selection_set,
+                ));
+                continue;
+            }
+        };
+
+        if unwraps.is_empty() {
+            continue;
+        }
+
+        let field_type = match field_type {
+            Some(field_type) => field_type,
+            None => {
+                errors.push(WithLocation::new(
+                    ValidateSchemaError::ClientFieldSelectionUnwrapsClientField {
+                        client_field_parent_type_name: client_field.type_and_field.type_name,
+                        client_field_name: client_field.name,
+                        field_parent_type_name: parent_object.name,
+                        field_name,
+                    },
+                    location,
+                ));
+                continue;
+            }
+        };
+
+        // The first unwrap applies to the field's value, the second to its list items,
+        // and so on.
+        let mut level_type = Some(field_type);
+        for unwrap in unwraps {
+            let current_level_type = match level_type {
+                Some(current_level_type) => current_level_type,
+                None => {
+                    errors.push(WithLocation::new(
+                        ValidateSchemaError::ClientFieldSelectionHasTooManyUnwraps {
+                            client_field_parent_type_name: client_field.type_and_field.type_name,
+                            client_field_name: client_field.name,
+                            field_parent_type_name: parent_object.name,
+                            field_name,
+                        },
+                        location,
+                    ));
+                    break;
+                }
+            };
+
+            if unwrap.item == Unwrap::ActualUnwrap && !current_level_type.is_nullable() {
+                if let Err(error) = options.on_unnecessary_unwrap.on_failure(warnings, || {
+                    WithLocation::new(
+                        ValidateSchemaError::ClientFieldSelectionHasUnnecessaryUnwrap {
+                            client_field_parent_type_name: client_field.type_and_field.type_name,
+                            client_field_name: client_field.name,
+                            field_parent_type_name: parent_object.name,
+                            field_name,
+                        },
+                        location,
+                    )
+                }) {
+                    errors.push(error);
+                }
+            }
+            level_type = current_level_type.list_item_type();
+        }
+    }
+    errors
+}
+
+fn find_server_field_id<TData>(
+    server_fields: &[SchemaServerField<TData>],
+    field_name: SelectableFieldName,
+    parent_server_fields: &[ServerFieldId],
+) -> Option<ServerFieldId> {
+    parent_server_fields.iter().find_map(|server_field_id| {
+        let server_field = &server_fields[server_field_id.as_usize()];
+        if server_field.name.item == field_name {
+            Some(*server_field_id)
+        } else {
+            None
+        }
+    })
+}
+
+type ValidateSchemaResult<T> = Result<T, WithLocation<ValidateSchemaError>>;
+
+#[derive(Debug, Error)]
+pub enum ValidateSchemaError {
+    #[error(
+        "The field `{parent_type_name}.{field_name}` has inner type `{field_type}`, which does not exist."
+    )]
+    FieldTypenameDoesNotExist {
+        parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        field_type: UnvalidatedTypeName,
+    },
+
+    #[error(
+        "The argument `{argument_name}` on field `{parent_type_name}.{field_name}` has inner type `{argument_type}`, which does not exist."
+    )]
+    FieldArgumentTypeDoesNotExist {
+        argument_name: InputValueName,
+        parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_type: InputTypeName,
+    },
+
+    #[error(
+        "The field `{parent_type_name}.{field_name}` has inner type `{field_type}`, which is \
+        an input object. Input objects can only be the types of arguments and input object fields."
+    )]
+    FieldTypeIsInputObject {
+        parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        field_type: UnvalidatedTypeName,
+    },
+
+    #[error(
+        "The argument `{argument_name}` on field `{parent_type_name}.{field_name}` has inner \
+        type `{argument_type}`, which is not a scalar, enum or input object."
+    )]
+    FieldArgumentTypeIsNotInputType {
+        argument_name: InputValueName,
+        parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_type: InputTypeName,
+    },
+
+    #[error(
+        "The field `{parent_type_name}.{field_name}` has inner type `{field_type}`, which does not exist."
+    )]
+    InputObjectFieldTypeDoesNotExist {
+        parent_type_name: GraphQLInputObjectTypeName,
+        field_name: InputValueName,
+        field_type: InputTypeName,
+    },
+
+    #[error(
+        "The field `{parent_type_name}.{field_name}` has inner type `{field_type}`, which is \
+        not a scalar, enum or input object."
+    )]
+    InputObjectFieldTypeIsNotInputType {
+        parent_type_name: GraphQLInputObjectTypeName,
+        field_name: InputValueName,
+        field_type: InputTypeName,
+    },
+
+    #[error("The argument `{argument_name}` is defined more than once on field `{parent_type_name}.{field_name}`.")]
+    DuplicateFieldArgument {
+        argument_name: InputValueName,
+        parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected, but that \
+        field does not exist on `{field_parent_type_name}`"
+    )]
+    ClientFieldSelectionFieldDoesNotExist {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected as a scalar, \
+        but that field's type is `{target_type_name}`, which is {field_type}."
+    )]
+    ClientFieldSelectionFieldIsNotScalar {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        field_type: &'static str,
+        target_type_name: UnvalidatedTypeName,
     },
 
     #[error(
@@ -814,6 +1955,172 @@ pub enum ValidateSchemaError {
         field_name: SelectableFieldName,
     },
 
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        `{parent_type_name}` is refined to `{type_to_refine_to}`, which is not a type \
+        that has been defined."
+    )]
+    ClientFieldSelectionTypeRefinementTypeDoesNotExist {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        parent_type_name: IsographObjectTypeName,
+        type_to_refine_to: UnvalidatedTypeName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        `{parent_type_name}` is refined to `{type_to_refine_to}`. However, \
+        `{type_to_refine_to}` is neither `{parent_type_name}`, nor a type that implements \
+        `{parent_type_name}` or is a member of it, nor an interface or union that \
+        `{parent_type_name}` implements or is a member of."
+    )]
+    ClientFieldSelectionTypeRefinementIsNotSubtype {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        parent_type_name: IsographObjectTypeName,
+        type_to_refine_to: UnvalidatedTypeName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the argument `{argument_name}` of the field `{field_parent_type_name}.{field_name}` \
+        is passed `{enum_value}`, which is not a value of the enum `{enum_name}`."
+    )]
+    ClientFieldSelectionInvalidEnumValue {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        enum_name: InputTypeName,
+        enum_value: EnumLiteralValue,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the argument `{argument_name}` of the field `{field_parent_type_name}.{field_name}` \
+        is passed the enum value `{enum_value}`, but that argument's type is \
+        `{argument_type_name}`, which is not an enum."
+    )]
+    ClientFieldSelectionEnumValueForNonEnumArgument {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        argument_type_name: InputTypeName,
+        enum_value: EnumLiteralValue,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the argument `{argument_name}` of the field `{field_parent_type_name}.{field_name}` \
+        has type `{argument_type}`, but is passed `{value}`."
+    )]
+    ClientFieldSelectionInvalidArgumentValue {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        argument_type: String,
+        value: String,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is annotated with \
+        `@{directive_name}`, but only `@skip` and `@include` are supported, and \
+        only on server fields."
+    )]
+    ClientFieldSelectionUnsupportedDirective {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        directive_name: IsographDirectiveName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is annotated with \
+        `@{directive_name}`, which must be passed exactly one argument, `if`, \
+        whose value is a boolean literal or a variable."
+    )]
+    ClientFieldSelectionInvalidConditionalDirectiveArguments {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        directive_name: IsographDirectiveName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is annotated with \
+        `@{directive_name}(if: ${variable_name})`, but `${variable_name}` is not defined \
+        with type `Boolean` or `Boolean!`."
+    )]
+    ClientFieldSelectionConditionVariableIsNotBoolean {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        directive_name: IsographDirectiveName,
+        variable_name: VariableName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected, but that field \
+        is deprecated: {deprecation_reason}\n\
+        This can be suppressed using the \"on_deprecated_field_selected\" config parameter."
+    )]
+    ClientFieldSelectionFieldIsDeprecated {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        deprecation_reason: StringLiteralValue,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the client field `{field_parent_type_name}.{field_name}` is selected with `!`. \
+        Only server fields can be unwrapped."
+    )]
+    ClientFieldSelectionUnwrapsClientField {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected with more `!` \
+        than its type has levels of nesting."
+    )]
+    ClientFieldSelectionHasTooManyUnwraps {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected with `!`, but \
+        its type is already non-null at that level.\n\
+        This can be suppressed using the \"on_unnecessary_unwrap\" config parameter."
+    )]
+    ClientFieldSelectionHasUnnecessaryUnwrap {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
     #[error(
         "The variable `{variable_name}` has type `{type_}`, but the inner type \
         `{inner_type}` does not exist."
@@ -824,10 +2131,134 @@ pub enum ValidateSchemaError {
         inner_type: UnvalidatedTypeName,
     },
 
+    #[error(
+        "The variable `{variable_name}` has type `{type_}`, but the inner type \
+        `{inner_type}` is not a scalar, enum or input object."
+    )]
+    VariableDefinitionInnerTypeIsNotInputType {
+        variable_name: VariableName,
+        type_: String,
+        inner_type: UnvalidatedTypeName,
+    },
+
+    #[error(
+        "The variable `{variable_name}` has type `{type_}`, but its default value \
+        `{default_value}` is not of that type."
+    )]
+    VariableDefinitionDefaultValueDoesNotMatchType {
+        variable_name: VariableName,
+        type_: String,
+        default_value: String,
+    },
+
     #[error("Error when validating iso entrypoint calls.\nMessage: {message}")]
     ErrorValidatingEntrypointDeclaration {
         message: ValidateEntrypointDeclarationError,
     },
+
+    #[error("{message}")]
+    InvalidInterfaceImplementation { message: ProcessTypeDefinitionError },
+}
+
+impl ErrorCode for ValidateSchemaError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            ValidateSchemaError::FieldTypenameDoesNotExist { .. } => {
+                "validate_schema/field_typename_does_not_exist"
+            }
+            ValidateSchemaError::FieldArgumentTypeDoesNotExist { .. } => {
+                "validate_schema/field_argument_type_does_not_exist"
+            }
+            ValidateSchemaError::FieldTypeIsInputObject { .. } => {
+                "validate_schema/field_type_is_input_object"
+            }
+            ValidateSchemaError::FieldArgumentTypeIsNotInputType { .. } => {
+                "validate_schema/field_argument_type_is_not_input_type"
+            }
+            ValidateSchemaError::InputObjectFieldTypeDoesNotExist { .. } => {
+                "validate_schema/input_object_field_type_does_not_exist"
+            }
+            ValidateSchemaError::InputObjectFieldTypeIsNotInputType { .. } => {
+                "validate_schema/input_object_field_type_is_not_input_type"
+            }
+            ValidateSchemaError::DuplicateFieldArgument { .. } => {
+                "validate_schema/duplicate_field_argument"
+            }
+            ValidateSchemaError::ClientFieldSelectionFieldDoesNotExist { .. } => {
+                "validate_schema/client_field_selection_field_does_not_exist"
+            }
+            ValidateSchemaError::ClientFieldSelectionFieldIsNotScalar { .. } => {
+                "validate_schema/client_field_selection_field_is_not_scalar"
+            }
+            ValidateSchemaError::ClientFieldSelectionFieldIsScalar { .. } => {
+                "validate_schema/client_field_selection_field_is_scalar"
+            }
+            ValidateSchemaError::ClientFieldSelectionFieldIsResolver { .. } => {
+                "validate_schema/client_field_selection_field_is_resolver"
+            }
+            ValidateSchemaError::ClientFieldSelectionTypeRefinementTypeDoesNotExist { .. } => {
+                "validate_schema/client_field_selection_type_refinement_type_does_not_exist"
+            }
+            ValidateSchemaError::ClientFieldSelectionTypeRefinementIsNotSubtype { .. } => {
+                "validate_schema/client_field_selection_type_refinement_is_not_subtype"
+            }
+            ValidateSchemaError::ClientFieldSelectionInvalidEnumValue { .. } => {
+                "validate_schema/client_field_selection_invalid_enum_value"
+            }
+            ValidateSchemaError::ClientFieldSelectionEnumValueForNonEnumArgument { .. } => {
+                "validate_schema/client_field_selection_enum_value_for_non_enum_argument"
+            }
+            ValidateSchemaError::ClientFieldSelectionInvalidArgumentValue { .. } => {
+                "validate_schema/client_field_selection_invalid_argument_value"
+            }
+            ValidateSchemaError::ClientFieldSelectionUnsupportedDirective { .. } => {
+                "validate_schema/client_field_selection_unsupported_directive"
+            }
+            ValidateSchemaError::ClientFieldSelectionInvalidConditionalDirectiveArguments {
+                ..
+            } => "validate_schema/client_field_selection_invalid_conditional_directive_arguments",
+            ValidateSchemaError::ClientFieldSelectionConditionVariableIsNotBoolean { .. } => {
+                "validate_schema/client_field_selection_condition_variable_is_not_boolean"
+            }
+            ValidateSchemaError::ClientFieldSelectionFieldIsDeprecated { .. } => {
+                "validate_schema/client_field_selection_field_is_deprecated"
+            }
+            ValidateSchemaError::ClientFieldSelectionUnwrapsClientField { .. } => {
+                "validate_schema/client_field_selection_unwraps_client_field"
+            }
+            ValidateSchemaError::ClientFieldSelectionHasTooManyUnwraps { .. } => {
+                "validate_schema/client_field_selection_has_too_many_unwraps"
+            }
+            ValidateSchemaError::ClientFieldSelectionHasUnnecessaryUnwrap { .. } => {
+                "validate_schema/client_field_selection_has_unnecessary_unwrap"
+            }
+            ValidateSchemaError::VariableDefinitionDefaultValueDoesNotMatchType { .. } => {
+                "validate_schema/variable_definition_default_value_does_not_match_type"
+            }
+            ValidateSchemaError::VariableDefinitionInnerTypeDoesNotExist { .. } => {
+                "validate_schema/variable_definition_inner_type_does_not_exist"
+            }
+            ValidateSchemaError::VariableDefinitionInnerTypeIsNotInputType { .. } => {
+                "validate_schema/variable_definition_inner_type_is_not_input_type"
+            }
+            ValidateSchemaError::ErrorValidatingEntrypointDeclaration { message } => {
+                message.error_code()
+            }
+            ValidateSchemaError::InvalidInterfaceImplementation { message } => message.error_code(),
+        }
+    }
+
+    fn related_locations(&self) -> Vec<WithLocation<String>> {
+        match self {
+            ValidateSchemaError::ErrorValidatingEntrypointDeclaration { message } => {
+                message.related_locations()
+            }
+            ValidateSchemaError::InvalidInterfaceImplementation { message } => {
+                message.related_locations()
+            }
+            _ => vec![],
+        }
+    }
 }
 
 pub fn refetched_paths_for_client_field(
@@ -0,0 +1,113 @@
+use common_lang_types::{Location, WithLocation};
+use graphql_lang_types::{ConstantValue, NameValuePair};
+use intern::string_key::Intern;
+use isograph_lang_types::NonConstantValue;
+
+// The expected chunks must be kept in sync with the tests of getNetworkResponseKey
+// in @isograph/react (libs/isograph-react/src/cache.test.ts), which checks that
+// the runtime produces the same aliases.
+
+fn generated<T>(item: T) -> WithLocation<T> {
+    WithLocation::new(item, Location::generated())
+}
+
+fn string(value: &str) -> NonConstantValue {
+    NonConstantValue::String(value.intern().into())
+}
+
+fn enum_(value: &str) -> ConstantValue {
+    ConstantValue::Enum(value.intern().into())
+}
+
+#[test]
+fn variable_alias_str_chunk() {
+    assert_eq!(
+        NonConstantValue::Variable("foo".intern().into()).to_alias_str_chunk(),
+        "v_foo"
+    );
+}
+
+#[test]
+fn scalar_literal_alias_str_chunks() {
+    assert_eq!(NonConstantValue::Integer(10).to_alias_str_chunk(), "l_10");
+    assert_eq!(
+        NonConstantValue::Integer(-3).to_alias_str_chunk(),
+        "l__2d_3"
+    );
+    assert_eq!(
+        NonConstantValue::Float(10.5.into()).to_alias_str_chunk(),
+        "l_10_2e_5"
+    );
+    assert_eq!(
+        NonConstantValue::Boolean(false).to_alias_str_chunk(),
+        "l_false"
+    );
+    assert_eq!(NonConstantValue::Null.to_alias_str_chunk(), "l_null");
+    assert_eq!(
+        NonConstantValue::Enum("OPEN".intern().into()).to_alias_str_chunk(),
+        "l_OPEN"
+    );
+}
+
+#[test]
+fn float_literal_alias_str_chunks_are_formatted_like_javascript() {
+    assert_eq!(
+        NonConstantValue::Float(1e-7.into()).to_alias_str_chunk(),
+        "l_1e_2d_7"
+    );
+    assert_eq!(
+        NonConstantValue::Float(1.5e-7.into()).to_alias_str_chunk(),
+        "l_1_2e_5e_2d_7"
+    );
+    assert_eq!(
+        NonConstantValue::Float(1e21.into()).to_alias_str_chunk(),
+        "l_1e_2b_21"
+    );
+    assert_eq!(
+        NonConstantValue::Float(0.000001.into()).to_alias_str_chunk(),
+        "l_0_2e_000001"
+    );
+}
+
+#[test]
+fn string_literal_alias_str_chunks() {
+    assert_eq!(string("a_b c").to_alias_str_chunk(), "l_a_5f_b_20_c");
+    assert_eq!(
+        string("say \"hi\"\n").to_alias_str_chunk(),
+        "l_say_20__22_hi_22__a_"
+    );
+}
+
+#[test]
+fn escaped_strings_do_not_collide() {
+    assert_ne!(
+        string("a b").to_alias_str_chunk(),
+        string("a_20_b").to_alias_str_chunk()
+    );
+}
+
+#[test]
+fn list_and_object_literal_alias_str_chunks() {
+    assert_eq!(
+        NonConstantValue::List(vec![
+            generated(ConstantValue::Int(1)),
+            generated(ConstantValue::Int(2)),
+        ])
+        .to_alias_str_chunk(),
+        "l__5b_1_2c_2_5d_"
+    );
+    assert_eq!(
+        NonConstantValue::Object(vec![
+            NameValuePair {
+                name: generated("state".intern().into()),
+                value: generated(enum_("OPEN")),
+            },
+            NameValuePair {
+                name: generated("text".intern().into()),
+                value: generated(ConstantValue::String("a\"b".intern().into())),
+            },
+        ])
+        .to_alias_str_chunk(),
+        "l__7b__22_state_22__3a__22_OPEN_22__2c__22_text_22__3a__22_a_5c__22_b_22__7d_"
+    );
+}
@@ -0,0 +1,54 @@
+// Each test file compiles this module separately, and not every test file uses
+// every helper.
+#![allow(dead_code)]
+
+use common_lang_types::{TextSource, WithLocation, WithSpan};
+use intern::string_key::Intern;
+use isograph_lang_parser::{
+    parse_iso_literal, IsoLiteralExtractionResult, IsographLiteralParseError,
+};
+use isograph_lang_types::ClientFieldDeclaration;
+use isograph_schema::UnvalidatedSchema;
+
+pub fn text_source() -> TextSource {
+    TextSource {
+        path: "dummy".intern().into(),
+        span: None,
+    }
+}
+
+/// Parse an iso literal that declares a client field, which is exported under the
+/// given name from dummy.ts.
+pub fn parse_client_field_declaration(
+    iso_literal_text: &str,
+    const_export_name: &str,
+) -> Result<WithSpan<ClientFieldDeclaration>, WithLocation<IsographLiteralParseError>> {
+    match parse_iso_literal(
+        iso_literal_text,
+        "dummy.ts".intern().into(),
+        Some(const_export_name),
+        text_source(),
+    )? {
+        IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration) => {
+            Ok(client_field_declaration)
+        }
+        IsoLiteralExtractionResult::EntrypointDeclaration(_) => {
+            panic!("Expected a client field declaration")
+        }
+    }
+}
+
+/// Parse an iso literal that declares a client field, and add the client field to
+/// the schema.
+pub fn add_client_field(
+    schema: &mut UnvalidatedSchema,
+    iso_literal_text: &str,
+    const_export_name: &str,
+) {
+    let client_field_declaration =
+        parse_client_field_declaration(iso_literal_text, const_export_name)
+            .expect("Expected iso literal to parse");
+    schema
+        .process_client_field_declaration(client_field_declaration, text_source())
+        .expect("Expected client field declaration to be processed");
+}
@@ -11,8 +11,10 @@ use graphql_lang_types::{GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionO
 fn unwrap_directive(
     extension_or_definition: GraphQLTypeSystemExtensionOrDefinition,
 ) -> Result<Vec<GraphQLDirective<ConstantValue>>, Box<dyn Error>> {
-    if let GraphQLTypeSystemExtensionOrDefinition::Extension(extension) = extension_or_definition {
-        let GraphQLTypeSystemExtension::ObjectTypeExtension(object_type_extension) = extension;
+    if let GraphQLTypeSystemExtensionOrDefinition::Extension(
+        GraphQLTypeSystemExtension::ObjectTypeExtension(object_type_extension),
+    ) = extension_or_definition
+    {
         return Ok(object_type_extension.directives.clone());
     }
     Err("unexpected structure of directive".into())
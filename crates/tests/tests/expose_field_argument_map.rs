@@ -0,0 +1,208 @@
+mod common;
+
+use common::text_source;
+use common_lang_types::{ErrorCode, UnvalidatedTypeName, WithLocation};
+use graphql_lang_types::GraphQLInputValueDefinition;
+use intern::string_key::Intern;
+use isograph_config::ConfigOptions;
+use isograph_lang_types::SelectableFieldId;
+use isograph_schema::{ClientFieldVariant, ProcessTypeDefinitionError, UnvalidatedSchema};
+
+/// Process the schema, extended with the given @exposeField directives on Mutation.
+fn process_expose_field_directives(
+    directives: &str,
+) -> Result<UnvalidatedSchema, WithLocation<ProcessTypeDefinitionError>> {
+    let options = ConfigOptions::default();
+    let mut warnings = vec![];
+
+    let type_system_document = graphql_schema_parser::parse_schema(
+        include_str!("fixtures/expose_field/schema.graphql"),
+        text_source(),
+    )
+    .expect("Expected schema to parse");
+    let mut schema = UnvalidatedSchema::new();
+    schema
+        .process_graphql_type_system_document(type_system_document, &options, &mut warnings)
+        .expect("Expected schema to be processed");
+
+    let extension_document = graphql_schema_parser::parse_schema_extensions(
+        &format!("extend type Mutation\n{directives}"),
+        text_source(),
+    )
+    .expect("Expected schema extension to parse");
+    schema
+        .process_graphql_type_extension_document(extension_document, &options, &mut warnings)
+        .expect("Expected schema extension to be processed");
+
+    let mutation_id = schema
+        .mutation_type_id
+        .expect("Expected Mutation to be defined");
+    schema.create_mutation_fields_from_expose_field_directives(mutation_id)?;
+    Ok(schema)
+}
+
+/// The arguments of the client field that exposes the given mutation field.
+fn filtered_arguments(
+    schema: &UnvalidatedSchema,
+    mutation_field_name: &str,
+) -> Vec<GraphQLInputValueDefinition> {
+    schema
+        .client_fields
+        .iter()
+        .find_map(|client_field| match &client_field.variant {
+            ClientFieldVariant::MutationField(variant)
+                if variant.mutation_field_name.to_string() == mutation_field_name =>
+            {
+                Some(
+                    variant
+                        .filtered_mutation_field_arguments
+                        .iter()
+                        .map(|argument| argument.item.clone())
+                        .collect(),
+                )
+            }
+            _ => None,
+        })
+        .expect("Expected mutation field to be exposed")
+}
+
+/// The names and types of the fields of the given input object.
+fn input_object_fields(schema: &UnvalidatedSchema, type_name: &str) -> Vec<(String, String)> {
+    let unvalidated_type_name: UnvalidatedTypeName = type_name.intern().into();
+    match schema.schema_data.defined_types.get(&unvalidated_type_name) {
+        Some(SelectableFieldId::InputObject(input_object_id)) => schema
+            .schema_data
+            .input_object(*input_object_id)
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.item.name.item.to_string(),
+                    field.item.type_.inner().to_string(),
+                )
+            })
+            .collect(),
+        _ => panic!("Expected {type_name} to be an input object"),
+    }
+}
+
+fn is_defined(schema: &UnvalidatedSchema, type_name: &str) -> bool {
+    let type_name: UnvalidatedTypeName = type_name.intern().into();
+    schema.schema_data.defined_types.contains_key(&type_name)
+}
+
+#[test]
+fn nested_field_is_removed_from_generated_input_objects() {
+    let schema = process_expose_field_directives(
+        r#"@exposeField(
+            field: "set_pet"
+            path: "pet"
+            field_map: [{ from: "id", to: "input.owner.id" }]
+        )"#,
+    )
+    .expect("Expected @exposeField directive to be processed");
+
+    let arguments = filtered_arguments(&schema, "set_pet");
+    assert_eq!(arguments.len(), 1);
+    assert_eq!(arguments[0].name.item.to_string(), "input");
+    assert_eq!(
+        arguments[0].type_.inner().to_string(),
+        "SetPetInput__generated"
+    );
+
+    assert_eq!(
+        input_object_fields(&schema, "SetPetInput__generated"),
+        [
+            ("owner".to_string(), "OwnerInput__generated".to_string()),
+            ("tagline".to_string(), "String".to_string()),
+        ]
+    );
+    assert_eq!(
+        input_object_fields(&schema, "OwnerInput__generated"),
+        [("name".to_string(), "String".to_string())]
+    );
+    // The original input objects are unchanged.
+    assert_eq!(
+        input_object_fields(&schema, "OwnerInput"),
+        [
+            ("id".to_string(), "ID".to_string()),
+            ("name".to_string(), "String".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn mutations_removing_the_same_fields_share_generated_input_objects() {
+    let schema = process_expose_field_directives(
+        r#"@exposeField(
+            field: "set_pet"
+            path: "pet"
+            field_map: [{ from: "id", to: "input.owner.id" }]
+        )
+        @exposeField(
+            field: "set_pet_again"
+            path: "pet"
+            field_map: [{ from: "id", to: "input.owner.id" }]
+        )"#,
+    )
+    .expect("Expected @exposeField directives to be processed");
+
+    for mutation_field_name in ["set_pet", "set_pet_again"] {
+        assert_eq!(
+            filtered_arguments(&schema, mutation_field_name)[0]
+                .type_
+                .inner()
+                .to_string(),
+            "SetPetInput__generated"
+        );
+    }
+    assert!(!is_defined(&schema, "SetPetInput__generated2"));
+    assert!(!is_defined(&schema, "OwnerInput__generated2"));
+}
+
+#[test]
+fn mutations_removing_different_fields_get_distinct_generated_input_objects() {
+    let schema = process_expose_field_directives(
+        r#"@exposeField(
+            field: "set_pet"
+            path: "pet"
+            field_map: [{ from: "id", to: "input.owner.id" }]
+        )
+        @exposeField(
+            field: "set_pet_again"
+            path: "pet"
+            field_map: [{ from: "name", to: "input.tagline" }]
+        )"#,
+    )
+    .expect("Expected @exposeField directives to be processed");
+
+    assert_eq!(
+        filtered_arguments(&schema, "set_pet_again")[0]
+            .type_
+            .inner()
+            .to_string(),
+        "SetPetInput__generated2"
+    );
+    assert_eq!(
+        input_object_fields(&schema, "SetPetInput__generated2"),
+        [("owner".to_string(), "OwnerInput".to_string())]
+    );
+}
+
+#[test]
+fn fields_of_scalar_and_enum_arguments_cannot_be_remapped() {
+    for to in ["id.value", "mood.value"] {
+        let error = process_expose_field_directives(&format!(
+            r#"@exposeField(
+                field: "set_pet_mood"
+                path: "pet"
+                field_map: [{{ from: "id", to: "{to}" }}]
+            )"#
+        ))
+        .expect_err("Expected remapping a field of a scalar or enum argument to fail");
+        assert_eq!(
+            error.item.error_code(),
+            "process_type_definition/primary_directive_cannot_remap_fields_of_non_input_object"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use common::{add_client_field, text_source};
+use common_lang_types::{ErrorCode, WithLocation};
+use intern::string_key::Intern;
+use isograph_config::ConfigOptions;
+use isograph_lang_types::{ClientFieldId, ObjectId, SelectableFieldId};
+use isograph_schema::{
+    FieldDefinitionLocation, ProcessGraphQLDocumentOutcome, ProcessTypeDefinitionError,
+    UnvalidatedSchema,
+};
+
+fn process_schema(
+    source: &str,
+) -> (
+    UnvalidatedSchema,
+    Result<ProcessGraphQLDocumentOutcome, WithLocation<ProcessTypeDefinitionError>>,
+) {
+    let type_system_document = graphql_schema_parser::parse_schema(source, text_source())
+        .expect("Expected schema to parse");
+    let mut schema = UnvalidatedSchema::new();
+    let outcome = schema.process_graphql_type_system_document(
+        type_system_document,
+        &ConfigOptions::default(),
+        &mut vec![],
+    );
+    (schema, outcome)
+}
+
+fn object_id(schema: &UnvalidatedSchema, name: &str) -> ObjectId {
+    match schema.schema_data.defined_types.get(&name.intern().into()) {
+        Some(SelectableFieldId::Object(object_id)) => *object_id,
+        _ => panic!("Expected {name} to be an object"),
+    }
+}
+
+fn greeting_id(schema: &UnvalidatedSchema, object_name: &str) -> Option<ClientFieldId> {
+    match schema
+        .schema_data
+        .object(object_id(schema, object_name))
+        .encountered_fields
+        .get(&"Greeting".intern().into())
+    {
+        Some(FieldDefinitionLocation::Client(client_field_id)) => Some(*client_field_id),
+        _ => None,
+    }
+}
+
+fn subtype_names(schema: &UnvalidatedSchema, supertype_name: &str) -> Vec<String> {
+    let mut subtype_names: Vec<_> = schema
+        .schema_data
+        .supertype_to_subtype_map
+        .get(&object_id(schema, supertype_name))
+        .into_iter()
+        .flatten()
+        .map(|subtype_id| schema.schema_data.object(*subtype_id).name.to_string())
+        .collect();
+    subtype_names.sort();
+    subtype_names
+}
+
+#[test]
+fn three_level_interface_chain_is_refined_transitively() {
+    let (schema, outcome) = process_schema(include_str!(
+        "fixtures/interfaces/three_level_interface_chain.graphql"
+    ));
+    let outcome = outcome.expect("Expected schema to be processed");
+
+    assert_eq!(subtype_names(&schema, "Node"), ["Actor", "Bot", "Robot"]);
+    assert_eq!(subtype_names(&schema, "Actor"), ["Bot", "Robot"]);
+    assert_eq!(subtype_names(&schema, "Bot"), ["Robot"]);
+
+    let mut supertype_ids =
+        outcome.type_refinement_maps.subtype_to_supertype_map[&object_id(&schema, "Robot")].clone();
+    supertype_ids.sort();
+    assert_eq!(
+        supertype_ids,
+        [
+            object_id(&schema, "Node"),
+            object_id(&schema, "Actor"),
+            object_id(&schema, "Bot"),
+        ]
+    );
+
+    assert!(schema.validate_type_system().is_ok());
+}
+
+#[test]
+fn missing_transitive_interface_is_reported() {
+    let (schema, outcome) = process_schema(include_str!(
+        "fixtures/interfaces/missing_transitive_interface.graphql"
+    ));
+    outcome.expect("Expected schema to be processed");
+
+    let errors = schema
+        .validate_type_system()
+        .expect_err("Expected type system to be invalid");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].item.error_code(),
+        "process_type_definition/missing_transitive_interface"
+    );
+}
+
+#[test]
+fn interface_cycle_is_reported() {
+    let (_, outcome) = process_schema(include_str!("fixtures/interfaces/interface_cycle.graphql"));
+
+    let error = outcome
+        .err()
+        .expect("Expected interface cycle to be reported");
+    assert_eq!(
+        error.item.error_code(),
+        "process_type_definition/type_refinement_cycle"
+    );
+}
+
+#[test]
+fn client_field_reaches_subtype_through_two_paths() {
+    let (mut schema, outcome) = process_schema(include_str!(
+        "fixtures/interfaces/client_field_reaches_subtype_through_two_paths.graphql"
+    ));
+    let outcome = outcome.expect("Expected schema to be processed");
+    add_client_field(&mut schema, "field Node.Greeting { id }", "Greeting");
+
+    // User is a subtype of Node both directly and through Actor.
+    schema
+        .add_fields_to_subtypes(&outcome.type_refinement_maps.supertype_to_subtype_map)
+        .expect("Expected fields to be added to subtypes");
+
+    let node_greeting_id = greeting_id(&schema, "Node").expect("Expected Node.Greeting to exist");
+    for subtype_name in ["Actor", "User"] {
+        assert_eq!(greeting_id(&schema, subtype_name), Some(node_greeting_id));
+        let subtype = schema.schema_data.object(object_id(&schema, subtype_name));
+        assert_eq!(
+            subtype
+                .client_field_ids
+                .iter()
+                .filter(|client_field_id| **client_field_id == node_greeting_id)
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::parse_client_field_declaration;
+use intern::Lookup;
+use isograph_lang_parser::IsographLiteralParseError;
+use isograph_lang_types::{NonConstantValue, Selection, ServerFieldSelection};
+
+/// The value of the argument of the only selection of the client field.
+fn argument_value(argument: &str) -> NonConstantValue {
+    let client_field_declaration = parse_client_field_declaration(
+        &format!("field User.Greeting {{\n  name(format: {argument})\n}}"),
+        "Greeting",
+    )
+    .expect("Expected iso literal to parse");
+    let (selection_set, _) = client_field_declaration
+        .item
+        .selection_set_and_unwraps
+        .expect("Expected client field to have a selection set");
+    match &selection_set[0].item {
+        Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field_selection)) => {
+            scalar_field_selection.arguments[0].item.value.item.clone()
+        }
+        other => panic!("Expected a scalar field selection, got {:?}", other),
+    }
+}
+
+#[test]
+fn string_value_decodes_escape_sequences() {
+    match argument_value(r#""say \"hi\"\\\n\u00e9\uD83D\uDE00""#) {
+        NonConstantValue::String(string_value) => {
+            assert_eq!(string_value.lookup(), "say \"hi\"\\\n\u{e9}\u{1f600}")
+        }
+        other => panic!("Expected string value, got {:?}", other),
+    }
+}
+
+#[test]
+fn integer_value_out_of_range() {
+    let error = parse_client_field_declaration(
+        "field User.Greeting {\n  name(format: 9223372036854775808)\n}",
+        "Greeting",
+    )
+    .expect_err("Expected integer value to be out of range");
+    assert!(matches!(
+        error.item,
+        IsographLiteralParseError::IntegerOutOfRange { .. }
+    ));
+}
@@ -0,0 +1,170 @@
+use common_lang_types::{TextSource, WithSpan};
+use graphql_lang_types::{
+    ConstantValue, GraphQLDirective, GraphQLTypeSystemExtension,
+    GraphQLTypeSystemExtensionOrDefinition, TypeAnnotation,
+};
+use graphql_schema_parser::SchemaParseError;
+use intern::string_key::Intern;
+
+fn parse_extension(source: &str) -> Result<GraphQLTypeSystemExtension, WithSpan<SchemaParseError>> {
+    let text_source = TextSource {
+        path: "dummy".intern().into(),
+        span: None,
+    };
+    let mut document = graphql_schema_parser::parse_schema_extensions(source, text_source)?;
+    assert_eq!(document.0.len(), 1);
+    match document.0.remove(0).item {
+        GraphQLTypeSystemExtensionOrDefinition::Extension(extension) => Ok(extension),
+        GraphQLTypeSystemExtensionOrDefinition::Definition(_) => {
+            panic!("Expected a type system extension")
+        }
+    }
+}
+
+fn directive_names(directives: &[GraphQLDirective<ConstantValue>]) -> Vec<String> {
+    directives
+        .iter()
+        .map(|directive| directive.name.item.to_string())
+        .collect()
+}
+
+#[test]
+fn parse_object_type_extension() {
+    let extension = parse_extension(include_str!(
+        "fixtures/schema_extensions/object_extension.graphql"
+    ))
+    .expect("Expected object type extension to parse");
+
+    let object_type_extension = match extension {
+        GraphQLTypeSystemExtension::ObjectTypeExtension(object_type_extension) => {
+            object_type_extension
+        }
+        other => panic!("Expected an object type extension, got {other:?}"),
+    };
+    assert_eq!(object_type_extension.name.item.to_string(), "User");
+    assert_eq!(
+        object_type_extension
+            .interfaces
+            .iter()
+            .map(|interface| interface.item.to_string())
+            .collect::<Vec<_>>(),
+        ["Node", "Actor"]
+    );
+    assert_eq!(
+        directive_names(&object_type_extension.directives),
+        ["component"]
+    );
+
+    let fields = &object_type_extension.fields;
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].item.name.item.to_string(), "nickname");
+    assert!(matches!(fields[0].item.type_, TypeAnnotation::NonNull(_)));
+    assert_eq!(fields[1].item.name.item.to_string(), "friends");
+    assert!(matches!(fields[1].item.type_, TypeAnnotation::List(_)));
+    assert_eq!(fields[1].item.arguments.len(), 1);
+    assert_eq!(
+        fields[1].item.arguments[0].item.name.item.to_string(),
+        "first"
+    );
+}
+
+#[test]
+fn parse_union_type_extension() {
+    let extension = parse_extension(include_str!(
+        "fixtures/schema_extensions/union_extension.graphql"
+    ))
+    .expect("Expected union type extension to parse");
+
+    let union_type_extension = match extension {
+        GraphQLTypeSystemExtension::UnionTypeExtension(union_type_extension) => {
+            union_type_extension
+        }
+        other => panic!("Expected a union type extension, got {other:?}"),
+    };
+    assert_eq!(union_type_extension.name.item.to_string(), "SearchResult");
+    assert_eq!(
+        directive_names(&union_type_extension.directives),
+        ["component"]
+    );
+    assert_eq!(
+        union_type_extension
+            .union_member_types
+            .iter()
+            .map(|member_type| member_type.item.to_string())
+            .collect::<Vec<_>>(),
+        ["User", "Page"]
+    );
+}
+
+#[test]
+fn parse_enum_type_extension() {
+    let extension = parse_extension(include_str!(
+        "fixtures/schema_extensions/enum_extension.graphql"
+    ))
+    .expect("Expected enum type extension to parse");
+
+    let enum_type_extension = match extension {
+        GraphQLTypeSystemExtension::EnumTypeExtension(enum_type_extension) => enum_type_extension,
+        other => panic!("Expected an enum type extension, got {other:?}"),
+    };
+    assert_eq!(enum_type_extension.name.item.to_string(), "State");
+    assert_eq!(
+        directive_names(&enum_type_extension.directives),
+        ["component"]
+    );
+    assert_eq!(
+        enum_type_extension
+            .enum_value_definitions
+            .iter()
+            .map(|enum_value_definition| enum_value_definition.item.value.item.to_string())
+            .collect::<Vec<_>>(),
+        ["ARCHIVED", "DELETED"]
+    );
+}
+
+#[test]
+fn parse_schema_extension() {
+    let extension = parse_extension(include_str!(
+        "fixtures/schema_extensions/schema_extension.graphql"
+    ))
+    .expect("Expected schema extension to parse");
+
+    let schema_extension = match extension {
+        GraphQLTypeSystemExtension::SchemaExtension(schema_extension) => schema_extension,
+        other => panic!("Expected a schema extension, got {other:?}"),
+    };
+    assert_eq!(directive_names(&schema_extension.directives), ["component"]);
+    assert!(schema_extension.query.is_none());
+    assert_eq!(
+        schema_extension
+            .mutation
+            .map(|mutation| mutation.item.to_string()),
+        Some("Mutation".to_string())
+    );
+    assert_eq!(
+        schema_extension
+            .subscription
+            .map(|subscription| subscription.item.to_string()),
+        Some("Subscription".to_string())
+    );
+}
+
+#[test]
+fn empty_extensions_are_parse_errors() {
+    for source in [
+        "extend type User",
+        "extend union SearchResult",
+        "extend enum State",
+        "extend schema",
+    ] {
+        let error = parse_extension(source).expect_err("Expected empty extension to be rejected");
+        assert!(
+            matches!(
+                error.item,
+                SchemaParseError::EmptyTypeSystemExtension { .. }
+            ),
+            "Expected {source:?} to be rejected as empty, got {:?}",
+            error.item
+        );
+    }
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{add_client_field, text_source};
+use common_lang_types::{Diagnostic, ErrorCode, Severity, WithLocation};
+use isograph_config::{ConfigOptions, OptionalValidationLevel};
+use isograph_schema::{UnvalidatedSchema, ValidateSchemaError, ValidatedSchema};
+
+/// Validate a schema containing the client field User.Greeting, which has the
+/// given selections.
+fn validate_greeting(
+    selections: &str,
+    on_unnecessary_unwrap: OptionalValidationLevel,
+) -> (
+    Result<(), Vec<WithLocation<ValidateSchemaError>>>,
+    Vec<Diagnostic>,
+) {
+    let options = ConfigOptions {
+        on_unnecessary_unwrap,
+        ..Default::default()
+    };
+    let mut warnings = vec![];
+
+    let type_system_document = graphql_schema_parser::parse_schema(
+        include_str!("fixtures/unwraps/schema.graphql"),
+        text_source(),
+    )
+    .expect("Expected schema to parse");
+    let mut schema = UnvalidatedSchema::new();
+    schema
+        .process_graphql_type_system_document(type_system_document, &options, &mut warnings)
+        .expect("Expected schema to be processed");
+
+    add_client_field(
+        &mut schema,
+        &format!("field User.Greeting {{\n{selections}\n}}"),
+        "Greeting",
+    );
+
+    let result =
+        ValidatedSchema::validate_and_construct(schema, &options, &mut warnings).map(|_| ());
+    (result, warnings)
+}
+
+fn error_codes(result: Result<(), Vec<WithLocation<ValidateSchemaError>>>) -> Vec<&'static str> {
+    match result {
+        Ok(()) => vec![],
+        Err(errors) => errors.iter().map(|error| error.item.error_code()).collect(),
+    }
+}
+
+#[test]
+fn unwrapping_nullable_fields_is_valid() {
+    let (result, warnings) =
+        validate_greeting("name!\nfriends {\nid\n}!!", OptionalValidationLevel::Warn);
+
+    assert_eq!(error_codes(result), Vec::<&str>::new());
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn unnecessary_unwrap_is_a_warning_if_configured() {
+    let (result, warnings) = validate_greeting("nickname!\ntags!!", OptionalValidationLevel::Warn);
+
+    assert_eq!(error_codes(result), Vec::<&str>::new());
+    let warnings: Vec<_> = warnings
+        .iter()
+        .map(|warning| (warning.code, warning.severity))
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            (
+                "validate_schema/client_field_selection_has_unnecessary_unwrap",
+                Severity::Warning
+            ),
+            (
+                "validate_schema/client_field_selection_has_unnecessary_unwrap",
+                Severity::Warning
+            ),
+        ]
+    );
+}
+
+#[test]
+fn unnecessary_unwrap_is_an_error_if_configured() {
+    let (result, warnings) = validate_greeting("nickname!", OptionalValidationLevel::Error);
+
+    assert_eq!(
+        error_codes(result),
+        ["validate_schema/client_field_selection_has_unnecessary_unwrap"]
+    );
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn unnecessary_unwrap_is_ignored_if_configured() {
+    let (result, warnings) = validate_greeting("nickname!", OptionalValidationLevel::Ignore);
+
+    assert_eq!(error_codes(result), Vec::<&str>::new());
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn too_many_unwraps_are_an_error() {
+    let (result, _) = validate_greeting("name!!\ntags!!!", OptionalValidationLevel::Ignore);
+
+    assert_eq!(
+        error_codes(result),
+        [
+            "validate_schema/client_field_selection_has_too_many_unwraps",
+            "validate_schema/client_field_selection_has_too_many_unwraps",
+        ]
+    );
+}
@@ -2,21 +2,27 @@
 
 const compileTag = require('./compileTag');
 const cosmiconfig = require('cosmiconfig');
+const pathModule = require('path');
 
+// As in the compiler, the config is either an isograph.config.json file, or the
+// `isograph` key of a package.json file. isograph.config.json takes precedence.
 const configExplorer = cosmiconfig('isograph', {
-  searchPlaces: ['isograph.config.json'],
+  searchPlaces: ['isograph.config.json', 'package.json'],
   loaders: {
     '.json': cosmiconfig.loadJson,
   },
 });
 
 let IsographConfig;
+let IsographConfigDirectory;
 const result = configExplorer.searchSync();
 if (result) {
   IsographConfig = result.config;
+  IsographConfigDirectory = pathModule.dirname(result.filepath);
 } else {
   throw new Error(
-    'No config found. Do you have a isograph.config.json file somewhere?',
+    'No config found. Do you have a isograph.config.json file, or an ' +
+      '`isograph` key in your package.json, somewhere?',
   );
 }
 
@@ -31,7 +37,7 @@ module.exports = function BabelPluginIsograph(context) {
 
   const visitor = {
     CallExpression(path) {
-      compileTag(t, path, IsographConfig);
+      compileTag(t, path, IsographConfig, IsographConfigDirectory);
     },
   };
 
@@ -2,13 +2,21 @@
 
 const pathModule = require('path');
 
-function compileTag(t, path, config) {
+function compileTag(t, path, config, configDirectory) {
   const callee = path.node.callee;
   if (t.isIdentifier(callee) && callee.name === 'iso' && path.node.arguments) {
     const { keyword, type, field } = getTypeAndField(path);
     if (keyword === 'entrypoint') {
       // This throws if the tag is invalid
-      compileImportStatement(t, path, type, field, 'entrypoint', config);
+      compileImportStatement(
+        t,
+        path,
+        type,
+        field,
+        'entrypoint',
+        config,
+        configDirectory,
+      );
     } else if (keyword === 'field') {
       // No-op
       return false;
@@ -21,8 +29,10 @@ function compileTag(t, path, config) {
   return false;
 }
 
+// Field declarations may have a description (e.g. "docs" or """docs""") before
+// the type and field name.
 const typeAndFieldRegex = new RegExp(
-  '\\s*(entrypoint|field)\\s*([^\\.\\s]+)\\.([^\\s\\(]+)',
+  '\\s*(entrypoint|field)\\s*(?:"""[\\s\\S]*?"""\\s*|"(?:[^"\\\\\\n]|\\\\.)*"\\s*)?([^\\.\\s]+)\\.([^\\s\\(]+)',
   'm',
 );
 
@@ -41,6 +51,11 @@ function getTypeAndField(path) {
 
   const content = quasis[0].value.raw;
   const typeAndField = typeAndFieldRegex.exec(content);
+  if (typeAndField == null) {
+    throw new Error(
+      'Malformed iso literal. I hope the iso compiler failed to accept this literal!',
+    );
+  }
 
   const keyword = typeAndField[1];
   const type = typeAndField[2];
@@ -54,13 +69,62 @@ function getTypeAndField(path) {
   return { keyword, type, field };
 }
 
-function compileImportStatement(t, path, type, field, artifactType, config) {
+// Paths in the config are relative to the config file. If the config has a
+// `projects` map, artifacts are imported from the artifact directory of the
+// project whose project_root contains the file (or, if project roots are
+// nested, of the innermost such project).
+function getArtifactDirectory(filename, config, configDirectory) {
+  const projects =
+    config.projects != null ? Object.values(config.projects) : [config];
+
+  let matchingProject = null;
+  let matchingProjectRoot = null;
+  for (const project of projects) {
+    const projectRoot = pathModule.resolve(
+      configDirectory,
+      project.project_root,
+    );
+    const fileRelativeToProjectRoot = pathModule.relative(projectRoot, filename);
+    const isWithinProjectRoot =
+      !fileRelativeToProjectRoot.startsWith('..') &&
+      !pathModule.isAbsolute(fileRelativeToProjectRoot);
+    if (
+      (isWithinProjectRoot || projects.length === 1) &&
+      (matchingProjectRoot == null ||
+        projectRoot.length > matchingProjectRoot.length)
+    ) {
+      matchingProject = project;
+      matchingProjectRoot = projectRoot;
+    }
+  }
+
+  if (matchingProject == null) {
+    throw new Error(
+      `BabelPluginIsograph: ${filename} is not within the project_root of ` +
+        'any project in the Isograph config.',
+    );
+  }
+  return pathModule.resolve(
+    configDirectory,
+    matchingProject.artifact_directory ?? matchingProject.project_root,
+  );
+}
+
+function compileImportStatement(
+  t,
+  path,
+  type,
+  field,
+  artifactType,
+  config,
+  configDirectory,
+) {
   const filename = path.state.filename;
   const folder = pathModule.dirname(filename);
-  const cwd = path.state.cwd;
-  const artifactDirectory = pathModule.join(
-    cwd,
-    config.artifact_directory ?? config.project_root,
+  const artifactDirectory = getArtifactDirectory(
+    filename,
+    config,
+    configDirectory,
   );
 
   const fileToArtifactDir = pathModule.relative(folder, artifactDirectory);
@@ -0,0 +1,96 @@
+import { describe, expect, test } from 'vitest';
+import { getParentRecordKey, normalizeData } from './cache';
+import { createIsographEnvironment } from './IsographEnvironment';
+import { ArgumentValue } from './util';
+
+// The expected aliases must be kept in sync with the alias_str_chunks tests of
+// the compiler (crates/tests/tests/alias_str_chunks.rs), which checks that the
+// compiler produces the same aliases.
+const aliasChunksByTestCase: [string, ArgumentValue, string][] = [
+  ['a variable', { kind: 'Variable', name: 'foo' }, 'v_foo'],
+  ['an integer', { kind: 'Literal', value: 10 }, 'l_10'],
+  ['a negative integer', { kind: 'Literal', value: -3 }, 'l__2d_3'],
+  ['a float', { kind: 'Literal', value: 10.5 }, 'l_10_2e_5'],
+  ['a small float', { kind: 'Literal', value: 1e-7 }, 'l_1e_2d_7'],
+  ['a small fraction', { kind: 'Literal', value: 1.5e-7 }, 'l_1_2e_5e_2d_7'],
+  ['a large float', { kind: 'Literal', value: 1e21 }, 'l_1e_2b_21'],
+  [
+    'a float close to zero',
+    { kind: 'Literal', value: 0.000001 },
+    'l_0_2e_000001',
+  ],
+  ['a boolean', { kind: 'Literal', value: false }, 'l_false'],
+  ['null', { kind: 'Literal', value: null }, 'l_null'],
+  ['an enum', { kind: 'Literal', value: 'OPEN' }, 'l_OPEN'],
+  ['a string', { kind: 'Literal', value: 'a_b c' }, 'l_a_5f_b_20_c'],
+  [
+    'a string with escaped characters',
+    { kind: 'Literal', value: 'say "hi"\n' },
+    'l_say_20__22_hi_22__a_',
+  ],
+  ['a list', { kind: 'Literal', value: [1, 2] }, 'l__5b_1_2c_2_5d_'],
+  [
+    'an object',
+    { kind: 'Literal', value: { state: 'OPEN', text: 'a"b' } },
+    'l__7b__22_state_22__3a__22_OPEN_22__2c__22_text_22__3a__22_a_5c__22_b_22__7d_',
+  ],
+];
+
+describe('getNetworkResponseKey', () => {
+  test.each(aliasChunksByTestCase)(
+    'The alias of a field with %s as an argument matches the compiler',
+    (_, argumentValue, expectedAliasChunk) => {
+      const environment = createIsographEnvironment({ __ROOT: {} }, () => {
+        throw new Error('Unexpected network request');
+      });
+      const astNode = {
+        kind: 'Scalar' as const,
+        fieldName: 'field',
+        arguments: [['arg', argumentValue] as [string, ArgumentValue]],
+      };
+      const variables = { foo: 'bar' };
+
+      // The network response is keyed by the alias that the compiler wrote
+      // into the query text.
+      normalizeData(
+        environment,
+        [astNode],
+        { ['field____arg___' + expectedAliasChunk]: 'value' },
+        variables,
+        [],
+      );
+
+      expect(
+        environment.store.__ROOT[getParentRecordKey(astNode, variables)],
+      ).toBe('value');
+    },
+  );
+
+  test('Escaped strings do not collide', () => {
+    const environment = createIsographEnvironment({ __ROOT: {} }, () => {
+      throw new Error('Unexpected network request');
+    });
+    const astNode = {
+      kind: 'Scalar' as const,
+      fieldName: 'field',
+      arguments: [
+        ['arg', { kind: 'Literal', value: 'a_20_b' }] as [
+          string,
+          ArgumentValue,
+        ],
+      ],
+    };
+
+    normalizeData(
+      environment,
+      [astNode],
+      { field____arg___l_a_20_b: 'value' },
+      {},
+      [],
+    );
+
+    expect(environment.store.__ROOT[getParentRecordKey(astNode, {})]).toBe(
+      undefined,
+    );
+  });
+});
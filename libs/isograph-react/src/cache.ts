@@ -25,7 +25,7 @@ import {
   RefetchQueryArtifactWrapper,
 } from './entrypoint';
 import { ReaderLinkedField, ReaderScalarField } from './reader';
-import { Argument, ArgumentValue } from './util';
+import { Argument, ArgumentValue, Condition } from './util';
 
 declare global {
   interface Window {
@@ -176,7 +176,7 @@ type NetworkResponseObject = {
   id?: DataId;
 };
 
-function normalizeData(
+export function normalizeData(
   environment: IsographEnvironment,
   normalizationAst: NormalizationAst,
   networkResponse: NetworkResponseObject,
@@ -269,10 +269,65 @@ function normalizeDataIntoRecord(
         );
         break;
       }
+      case 'InlineFragment': {
+        // The selections of an inline fragment are normalized into the same
+        // record, if the object is of the refined type.
+        if (
+          normalizationNode.concreteTypes.includes(
+            networkResponseParentRecord.__typename as string,
+          )
+        ) {
+          normalizeDataIntoRecord(
+            environment,
+            normalizationNode.selections,
+            networkResponseParentRecord,
+            targetParentRecord,
+            targetParentRecordId,
+            variables,
+            nestedRefetchQueries,
+            mutableEncounteredIds,
+          );
+        }
+        break;
+      }
+      case 'Conditional': {
+        // Fields that are excluded by @skip or @include are not in the network
+        // response, and so are not normalized.
+        if (conditionsArePassed(normalizationNode.conditions, variables)) {
+          normalizeDataIntoRecord(
+            environment,
+            normalizationNode.selections,
+            networkResponseParentRecord,
+            targetParentRecord,
+            targetParentRecordId,
+            variables,
+            nestedRefetchQueries,
+            mutableEncounteredIds,
+          );
+        }
+        break;
+      }
     }
   }
 }
 
+/**
+ * Whether a selection with the given @skip and @include conditions is included,
+ * given the variables of the query.
+ */
+export function conditionsArePassed(
+  conditions: Condition[],
+  variables: { [index: string]: string },
+): boolean {
+  return conditions.every((condition) => {
+    const value =
+      condition.value.kind === 'Literal'
+        ? condition.value.value
+        : (variables[condition.value.name] as any);
+    return condition.kind === 'Skip' ? value !== true : value === true;
+  });
+}
+
 function normalizeScalarField(
   astNode: NormalizationScalarField,
   networkResponseParentRecord: NetworkResponseObject,
@@ -450,7 +505,7 @@ function getStoreKeyChunkForArgumentValue(
 ) {
   switch (argumentValue.kind) {
     case 'Literal': {
-      return argumentValue.value;
+      return getKeyStringForLiteralValue(argumentValue.value);
     }
     case 'Variable': {
       return variables[argumentValue.name];
@@ -464,6 +519,29 @@ function getStoreKeyChunkForArgumentValue(
   }
 }
 
+/**
+ * Strings (and enums, which are represented as strings) are used as-is, and all
+ * other values are serialized as JSON. This must be kept in sync with the compiler.
+ */
+function getKeyStringForLiteralValue(value: any): string {
+  return typeof value === 'string' ? value : JSON.stringify(value);
+}
+
+/**
+ * Network response keys are aliases, and thus must be valid GraphQL names.
+ * Underscores are also escaped, so that distinct values always result in
+ * distinct aliases. This must be kept in sync with the compiler.
+ */
+function escapeAliasStrChunk(keyString: string): string {
+  return Array.from(keyString)
+    .map((char) =>
+      /[a-zA-Z0-9]/.test(char)
+        ? char
+        : '_' + char.codePointAt(0)!.toString(16) + '_',
+    )
+    .join('');
+}
+
 function getStoreKeyChunkForArgument(
   argument: Argument,
   variables: { [index: string]: string },
@@ -483,7 +561,11 @@ function getNetworkResponseKey(
       let argumentValueChunk;
       switch (argumentValue.kind) {
         case 'Literal': {
-          argumentValueChunk = 'l_' + argumentValue.value;
+          argumentValueChunk =
+            'l_' +
+            escapeAliasStrChunk(
+              getKeyStringForLiteralValue(argumentValue.value),
+            );
           break;
         }
         case 'Variable': {
@@ -0,0 +1,38 @@
+import { describe, expect, test } from 'vitest';
+import { IsographEntrypoint, getVariablesWithDefaults } from './entrypoint';
+
+const entrypoint: IsographEntrypoint<any, any> = {
+  kind: 'Entrypoint',
+  queryText: '',
+  normalizationAst: [],
+  variableDefaults: { first: 10, withAvatar: true },
+  nestedRefetchQueries: [],
+  readerArtifact: {
+    kind: 'ReaderArtifact',
+    readerAst: [],
+    resolver: (data) => data,
+    variant: { kind: 'Eager' },
+  },
+};
+
+describe('getVariablesWithDefaults', () => {
+  test('Variables that are not passed take their default values', () => {
+    expect(getVariablesWithDefaults(entrypoint, { id: '1' })).toEqual({
+      id: '1',
+      first: 10,
+      withAvatar: true,
+    });
+  });
+
+  test('Variables that are passed as undefined take their default values', () => {
+    expect(
+      getVariablesWithDefaults(entrypoint, { withAvatar: undefined }),
+    ).toEqual({ first: 10, withAvatar: true });
+  });
+
+  test('Passed variables take precedence over default values', () => {
+    expect(
+      getVariablesWithDefaults(entrypoint, { first: 5, withAvatar: false }),
+    ).toEqual({ first: 5, withAvatar: false });
+  });
+});
@@ -1,5 +1,6 @@
+import { Variable } from './FragmentReference';
 import { ReaderArtifact } from './reader';
-import { Arguments } from './util';
+import { Arguments, Condition } from './util';
 
 // This type should be treated as an opaque type.
 export type IsographEntrypoint<
@@ -9,13 +10,18 @@ export type IsographEntrypoint<
   kind: 'Entrypoint';
   queryText: string;
   normalizationAst: NormalizationAst;
+  // The default values of the entrypoint's variables, which are used for
+  // variables that are not passed.
+  variableDefaults: { [index: string]: Variable };
   readerArtifact: ReaderArtifact<TReadFromStore, TClientFieldValue>;
   nestedRefetchQueries: RefetchQueryArtifactWrapper[];
 };
 
 export type NormalizationAstNode =
   | NormalizationScalarField
-  | NormalizationLinkedField;
+  | NormalizationLinkedField
+  | NormalizationInlineFragment
+  | NormalizationConditional;
 export type NormalizationAst = NormalizationAstNode[];
 
 export type NormalizationScalarField = {
@@ -31,6 +37,20 @@ export type NormalizationLinkedField = {
   selections: NormalizationAst;
 };
 
+export type NormalizationInlineFragment = {
+  kind: 'InlineFragment';
+  type: string;
+  // The __typename values of the objects to which the type refinement applies.
+  concreteTypes: ReadonlyArray<string>;
+  selections: NormalizationAst;
+};
+
+export type NormalizationConditional = {
+  kind: 'Conditional';
+  conditions: Condition[];
+  selections: NormalizationAst;
+};
+
 // This is more like an entrypoint, but one specifically for a refetch query/mutation
 export type RefetchQueryArtifact = {
   kind: 'RefetchQuery';
@@ -58,6 +78,25 @@ export function assertIsEntrypoint<
   if (typeof value === 'function') throw new Error('Not a string');
 }
 
+/**
+ * Variables that are not passed (or are passed as undefined) take their
+ * default values, as they do on the server.
+ */
+export function getVariablesWithDefaults(
+  entrypoint: IsographEntrypoint<any, any>,
+  variables: { [index: string]: Variable },
+): { [index: string]: Variable } {
+  const variablesWithDefaults = { ...variables };
+  for (const [name, defaultValue] of Object.entries(
+    entrypoint.variableDefaults,
+  )) {
+    if (variablesWithDefaults[name] === undefined) {
+      variablesWithDefaults[name] = defaultValue;
+    }
+  }
+  return variablesWithDefaults;
+}
+
 export type ExtractReadFromStore<Type> =
   Type extends IsographEntrypoint<infer X, any> ? X : never;
 export type ExtractResolverResult<Type> =
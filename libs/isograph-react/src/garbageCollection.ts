@@ -6,7 +6,7 @@ import {
   StoreRecord,
   assertLink,
 } from './IsographEnvironment';
-import { getParentRecordKey } from './cache';
+import { conditionsArePassed, getParentRecordKey } from './cache';
 import { NormalizationAst } from './entrypoint';
 
 export type RetainedQuery = {
@@ -113,6 +113,35 @@ function recordReachableIdsFromRecord(
           }
         }
 
+        continue;
+      case 'InlineFragment':
+        // The selections of an inline fragment were only normalized into the
+        // record if the object is of the refined type.
+        const typename = currentRecord['__typename'];
+        if (
+          typeof typename === 'string' &&
+          !selection.concreteTypes.includes(typename)
+        ) {
+          continue;
+        }
+        recordReachableIdsFromRecord(
+          store,
+          currentRecord,
+          mutableRetainedIds,
+          selection.selections,
+          variables,
+        );
+        continue;
+      case 'Conditional':
+        if (conditionsArePassed(selection.conditions, variables ?? {})) {
+          recordReachableIdsFromRecord(
+            store,
+            currentRecord,
+            mutableRetainedIds,
+            selection.selections,
+            variables,
+          );
+        }
         continue;
       case 'Scalar':
         continue;
@@ -0,0 +1,218 @@
+import { describe, expect, test } from 'vitest';
+import { normalizeData } from './cache';
+import { NormalizationAst } from './entrypoint';
+import { garbageCollectEnvironment, retainQuery } from './garbageCollection';
+import {
+  IsographEnvironment,
+  IsographStore,
+  createIsographEnvironment,
+} from './IsographEnvironment';
+import { readButDoNotEvaluate } from './read';
+import { ReaderAst } from './reader';
+
+// node {
+//   id
+//   __typename
+//   ... on User { name bestFriend { id } }
+//   ... on Actor { login }
+// }
+// where User and Bot implement Actor, and User, Bot and Page implement Node.
+const normalizationAst: NormalizationAst = [
+  {
+    kind: 'Linked',
+    fieldName: 'node',
+    arguments: null,
+    selections: [
+      { kind: 'Scalar', fieldName: 'id', arguments: null },
+      { kind: 'Scalar', fieldName: '__typename', arguments: null },
+      {
+        kind: 'InlineFragment',
+        type: 'User',
+        concreteTypes: ['User'],
+        selections: [
+          { kind: 'Scalar', fieldName: 'name', arguments: null },
+          {
+            kind: 'Linked',
+            fieldName: 'bestFriend',
+            arguments: null,
+            selections: [{ kind: 'Scalar', fieldName: 'id', arguments: null }],
+          },
+        ],
+      },
+      {
+        kind: 'InlineFragment',
+        type: 'Actor',
+        concreteTypes: ['User', 'Bot'],
+        selections: [{ kind: 'Scalar', fieldName: 'login', arguments: null }],
+      },
+    ],
+  },
+];
+
+const readerAst: ReaderAst<unknown> = [
+  { kind: 'Scalar', fieldName: 'id', alias: null, arguments: null },
+  {
+    kind: 'InlineFragment',
+    type: 'User',
+    concreteTypes: ['User'],
+    selections: [
+      { kind: 'Scalar', fieldName: 'name', alias: null, arguments: null },
+    ],
+  },
+  {
+    kind: 'InlineFragment',
+    type: 'Actor',
+    concreteTypes: ['User', 'Bot'],
+    selections: [
+      { kind: 'Scalar', fieldName: 'login', alias: null, arguments: null },
+    ],
+  },
+];
+
+function createEnvironment(store: IsographStore): IsographEnvironment {
+  return createIsographEnvironment(store, () => {
+    throw new Error('Unexpected network request');
+  });
+}
+
+function readRecord(environment: IsographEnvironment, root: string): unknown {
+  return readButDoNotEvaluate(environment, {
+    kind: 'FragmentReference',
+    readerArtifact: {
+      kind: 'ReaderArtifact',
+      readerAst,
+      resolver: (data) => data,
+      variant: { kind: 'Eager' },
+    },
+    root,
+    variables: null,
+    nestedRefetchQueries: [],
+  });
+}
+
+describe('InlineFragment', () => {
+  describe('normalization', () => {
+    test('Normalizes the selections of type refinements that apply', () => {
+      const environment = createEnvironment({ __ROOT: {} });
+      normalizeData(
+        environment,
+        normalizationAst,
+        {
+          node: {
+            id: '1',
+            __typename: 'User',
+            name: 'Alice',
+            bestFriend: { id: '2' },
+            login: 'alice',
+          },
+        },
+        {},
+        [],
+      );
+
+      expect(environment.store['1']).toEqual({
+        id: '1',
+        __typename: 'User',
+        name: 'Alice',
+        bestFriend: { __link: '2' },
+        login: 'alice',
+      });
+      expect(environment.store['2']).toEqual({ id: '2' });
+    });
+
+    test('Normalizes the selections of a type refinement to an abstract type', () => {
+      const environment = createEnvironment({ __ROOT: {} });
+      normalizeData(
+        environment,
+        normalizationAst,
+        { node: { id: '1', __typename: 'Bot', login: 'bot' } },
+        {},
+        [],
+      );
+
+      expect(environment.store['1']).toEqual({
+        id: '1',
+        __typename: 'Bot',
+        login: 'bot',
+      });
+    });
+
+    test('Does not normalize the selections of type refinements that do not apply', () => {
+      const environment = createEnvironment({ __ROOT: {} });
+      normalizeData(
+        environment,
+        normalizationAst,
+        { node: { id: '1', __typename: 'Page' } },
+        {},
+        [],
+      );
+
+      expect(environment.store['1']).toEqual({ id: '1', __typename: 'Page' });
+    });
+  });
+
+  describe('reading', () => {
+    test('Reads the selections of type refinements that apply', () => {
+      const environment = createEnvironment({
+        __ROOT: {},
+        '1': { id: '1', __typename: 'User', name: 'Alice', login: 'alice' },
+        '2': { id: '2', __typename: 'Bot', login: 'bot' },
+      });
+
+      expect(readRecord(environment, '1')).toEqual({
+        id: '1',
+        __typename: 'User',
+        name: 'Alice',
+        login: 'alice',
+      });
+      expect(readRecord(environment, '2')).toEqual({
+        id: '2',
+        __typename: 'Bot',
+        login: 'bot',
+      });
+    });
+
+    test('Does not read the selections of type refinements that do not apply', () => {
+      const environment = createEnvironment({
+        __ROOT: {},
+        '1': { id: '1', __typename: 'Page' },
+      });
+
+      expect(readRecord(environment, '1')).toEqual({
+        id: '1',
+        __typename: 'Page',
+      });
+    });
+  });
+
+  describe('garbage collection', () => {
+    test('Retains records reachable through type refinements that apply', () => {
+      const environment = createEnvironment({
+        __ROOT: { node: { __link: '1' } },
+        '1': { id: '1', __typename: 'User', bestFriend: { __link: '2' } },
+        '2': { id: '2' },
+        '3': { id: '3' },
+      });
+      retainQuery(environment, { normalizationAst, variables: {} });
+      garbageCollectEnvironment(environment);
+
+      expect(Object.keys(environment.store).sort()).toEqual([
+        '1',
+        '2',
+        '__ROOT',
+      ]);
+    });
+
+    test('Does not retain records reachable only through type refinements that do not apply', () => {
+      const environment = createEnvironment({
+        __ROOT: { node: { __link: '1' } },
+        '1': { id: '1', __typename: 'Page', bestFriend: { __link: '2' } },
+        '2': { id: '2' },
+      });
+      retainQuery(environment, { normalizationAst, variables: {} });
+      garbageCollectEnvironment(environment);
+
+      expect(Object.keys(environment.store).sort()).toEqual(['1', '__ROOT']);
+    });
+  });
+});
@@ -0,0 +1,152 @@
+import { describe, expect, test } from 'vitest';
+import {
+  IsographEnvironment,
+  IsographStore,
+  createIsographEnvironment,
+} from './IsographEnvironment';
+import { readButDoNotEvaluate } from './read';
+import { ReaderAst } from './reader';
+
+function createEnvironment(store: IsographStore): IsographEnvironment {
+  return createIsographEnvironment(store, () => {
+    throw new Error('Unexpected network request');
+  });
+}
+
+function read(
+  environment: IsographEnvironment,
+  readerAst: ReaderAst<unknown>,
+): unknown {
+  return readButDoNotEvaluate(environment, {
+    kind: 'FragmentReference',
+    readerArtifact: {
+      kind: 'ReaderArtifact',
+      readerAst,
+      resolver: (data) => data,
+      variant: { kind: 'Eager' },
+    },
+    root: '1',
+    variables: null,
+    nestedRefetchQueries: [],
+  });
+}
+
+describe('unwraps', () => {
+  const environment = createEnvironment({
+    __ROOT: {},
+    '1': {
+      id: '1',
+      name: null,
+      nickname: 'Al',
+      tags: ['a', null],
+      bestFriend: null,
+      friends: [{ __link: '2' }, null],
+    },
+    '2': { id: '2' },
+  });
+
+  test('Reads unwrapped fields that are not null', () => {
+    expect(
+      read(environment, [
+        {
+          kind: 'Scalar',
+          fieldName: 'nickname',
+          alias: null,
+          arguments: null,
+          unwraps: [true],
+        },
+        {
+          kind: 'Scalar',
+          fieldName: 'tags',
+          alias: null,
+          arguments: null,
+          unwraps: [true],
+        },
+      ]),
+    ).toEqual({ nickname: 'Al', tags: ['a', null] });
+  });
+
+  test('Reads null for fields whose nullable levels are not unwrapped', () => {
+    expect(
+      read(environment, [
+        {
+          kind: 'Scalar',
+          fieldName: 'name',
+          alias: null,
+          arguments: null,
+          unwraps: [false],
+        },
+      ]),
+    ).toEqual({ name: null });
+  });
+
+  test('Throws if an unwrapped scalar field is null', () => {
+    expect(() =>
+      read(environment, [
+        {
+          kind: 'Scalar',
+          fieldName: 'name',
+          alias: null,
+          arguments: null,
+          unwraps: [true],
+        },
+      ]),
+    ).toThrow(
+      'Unexpected null for name on root 1, which was selected with `!`.',
+    );
+  });
+
+  test('Throws if an item of a list whose items are unwrapped is null', () => {
+    expect(() =>
+      read(environment, [
+        {
+          kind: 'Scalar',
+          fieldName: 'tags',
+          alias: null,
+          arguments: null,
+          unwraps: [true, true],
+        },
+      ]),
+    ).toThrow(
+      'Unexpected null for tags on root 1, which was selected with `!`.',
+    );
+  });
+
+  test('Throws if an unwrapped linked field is null', () => {
+    expect(() =>
+      read(environment, [
+        {
+          kind: 'Linked',
+          fieldName: 'bestFriend',
+          alias: null,
+          arguments: null,
+          selections: [
+            { kind: 'Scalar', fieldName: 'id', alias: null, arguments: null },
+          ],
+          unwraps: [true],
+        },
+      ]),
+    ).toThrow(
+      'Unexpected null for bestFriend on root 1, which was selected with `!`.',
+    );
+  });
+
+  test('Throws if a link in a list whose items are unwrapped is null', () => {
+    expect(() =>
+      read(environment, [
+        {
+          kind: 'Linked',
+          fieldName: 'friends',
+          alias: null,
+          arguments: null,
+          selections: [
+            { kind: 'Scalar', fieldName: 'id', alias: null, arguments: null },
+          ],
+          unwraps: [false, true],
+        },
+      ]),
+    ).toThrow(
+      'Unexpected null for friends on root 1, which was selected with `!`.',
+    );
+  });
+});
@@ -1,4 +1,8 @@
-import { getParentRecordKey, onNextChange } from './cache';
+import {
+  conditionsArePassed,
+  getParentRecordKey,
+  onNextChange,
+} from './cache';
 import { getOrCreateCachedComponent } from './componentCache';
 import { RefetchQueryArtifactWrapper } from './entrypoint';
 import { FragmentReference } from './FragmentReference';
@@ -8,7 +12,7 @@ import {
   defaultMissingFieldHandler,
   IsographEnvironment,
 } from './IsographEnvironment';
-import { ReaderAst } from './reader';
+import { ReaderAst, Unwraps } from './reader';
 
 export function read<TReadFromStore extends Object, TClientFieldValue>(
   environment: IsographEnvironment,
@@ -107,12 +111,18 @@ function readData<TReadFromStore>(
             reason: 'No value for ' + storeRecordName + ' on root ' + root,
           };
         }
+        if (field.unwraps != null) {
+          assertUnwrapped(value, field.unwraps, storeRecordName, root);
+        }
         target[field.alias ?? field.fieldName] = value;
         break;
       }
       case 'Linked': {
         const storeRecordName = getParentRecordKey(field, variables);
         const value = storeRecord[storeRecordName];
+        if (field.unwraps != null) {
+          assertUnwrapped(value, field.unwraps, storeRecordName, root);
+        }
         if (Array.isArray(value)) {
           const results = [];
           for (const item of value) {
@@ -321,11 +331,92 @@ function readData<TReadFromStore>(
         }
         break;
       }
+      case 'InlineFragment': {
+        const typename = storeRecord['__typename'];
+        if (typename === undefined) {
+          return {
+            kind: 'MissingData',
+            reason: 'No value for __typename on root ' + root,
+          };
+        }
+        target['__typename'] = typename;
+        if (!field.concreteTypes.includes(typename as string)) {
+          break;
+        }
+
+        // The selections of an inline fragment are read from the same record.
+        const data = readData(
+          environment,
+          field.selections,
+          root,
+          variables,
+          nestedRefetchQueries,
+        );
+        if (data.kind === 'MissingData') {
+          return {
+            kind: 'MissingData',
+            reason:
+              'Missing data for ... on ' + field.type + ' on root ' + root,
+            nestedReason: data,
+          };
+        }
+        Object.assign(target, data.data);
+        break;
+      }
+      case 'Conditional': {
+        // Fields that are excluded by @skip or @include are not read, and so
+        // are not treated as missing data.
+        if (!conditionsArePassed(field.conditions, variables)) {
+          break;
+        }
+
+        const data = readData(
+          environment,
+          field.selections,
+          root,
+          variables,
+          nestedRefetchQueries,
+        );
+        if (data.kind === 'MissingData') {
+          return {
+            kind: 'MissingData',
+            reason: 'Missing data for conditional selection on root ' + root,
+            nestedReason: data,
+          };
+        }
+        Object.assign(target, data.data);
+        break;
+      }
     }
   }
   return { kind: 'Success', data: target as any };
 }
 
+// A field selected with `!` is asserted to be non-null. If the server returns null
+// anyway, we throw instead of handing null to code that is typed as non-null.
+function assertUnwrapped(
+  value: unknown,
+  unwraps: Unwraps,
+  storeRecordName: string,
+  root: DataId,
+) {
+  const [isUnwrapped, ...itemUnwraps] = unwraps;
+  if (isUnwrapped && value === null) {
+    throw new Error(
+      'Unexpected null for ' +
+        storeRecordName +
+        ' on root ' +
+        root +
+        ', which was selected with `!`.',
+    );
+  }
+  if (Array.isArray(value) && itemUnwraps.length > 0) {
+    for (const item of value) {
+      assertUnwrapped(item, itemUnwraps, storeRecordName, root);
+    }
+  }
+}
+
 function filterVariables(
   variables: { [index: string]: string },
   allowedVariables: string[],
@@ -1,4 +1,4 @@
-import { Arguments } from './util';
+import { Arguments, Condition } from './util';
 
 // TODO this should probably be at least three distinct types, for @component,
 // non-@component and refetch resolvers
@@ -14,16 +14,23 @@ export type ReaderAstNode =
   | ReaderLinkedField
   | ReaderResolverField
   | ReaderRefetchField
-  | ReaderMutationField;
+  | ReaderMutationField
+  | ReaderInlineFragment
+  | ReaderConditional;
 
 // @ts-ignore
 export type ReaderAst<TReadFromStore> = ReaderAstNode[];
 
+// For fields selected with `!`, whether each level of the value (the value itself,
+// then its list items, and so on) was asserted to be non-null.
+export type Unwraps = boolean[];
+
 export type ReaderScalarField = {
   kind: 'Scalar';
   fieldName: string;
   alias: string | null;
   arguments: Arguments | null;
+  unwraps?: Unwraps;
 };
 export type ReaderLinkedField = {
   kind: 'Linked';
@@ -31,6 +38,21 @@ export type ReaderLinkedField = {
   alias: string | null;
   selections: ReaderAst<unknown>;
   arguments: Arguments | null;
+  unwraps?: Unwraps;
+};
+
+export type ReaderInlineFragment = {
+  kind: 'InlineFragment';
+  type: string;
+  // The __typename values of the objects to which the type refinement applies.
+  concreteTypes: ReadonlyArray<string>;
+  selections: ReaderAst<unknown>;
+};
+
+export type ReaderConditional = {
+  kind: 'Conditional';
+  conditions: Condition[];
+  selections: ReaderAst<unknown>;
 };
 
 export type ReaderResolverVariant =
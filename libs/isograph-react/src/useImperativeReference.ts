@@ -6,6 +6,7 @@ import {
   ExtractReadFromStore,
   ExtractResolverResult,
   IsographEntrypoint,
+  getVariablesWithDefaults,
 } from './entrypoint';
 import { FragmentReference } from './FragmentReference';
 import { useIsographEnvironment } from './IsographEnvironmentProvider';
@@ -36,17 +37,21 @@ export function useImperativeReference<
   return {
     queryReference: state,
     loadQueryReference: (variables: { [index: string]: string }) => {
+      const variablesWithDefaults = getVariablesWithDefaults(
+        entrypoint,
+        variables,
+      );
       const [_networkRequest, disposeNetworkRequest] = makeNetworkRequest(
         environment,
         entrypoint,
-        variables,
+        variablesWithDefaults,
       );
       setState([
         {
           kind: 'FragmentReference',
           readerArtifact: entrypoint.readerArtifact,
           root: ROOT_ID,
-          variables,
+          variables: variablesWithDefaults,
           nestedRefetchQueries: entrypoint.nestedRefetchQueries,
         },
         () => {
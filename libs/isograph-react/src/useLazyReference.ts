@@ -5,6 +5,7 @@ import {
   ExtractReadFromStore,
   ExtractResolverResult,
   assertIsEntrypoint,
+  getVariablesWithDefaults,
 } from './entrypoint';
 import { getOrCreateCacheForArtifact } from './cache';
 import { useLazyDisposableState } from '@isograph/react-disposable-state';
@@ -33,10 +34,11 @@ export function useLazyReference<TEntrypoint>(
     ExtractReadFromStore<TEntrypoint>,
     ExtractResolverResult<TEntrypoint>
   >(entrypoint);
+  const variablesWithDefaults = getVariablesWithDefaults(entrypoint, variables);
   const cache = getOrCreateCacheForArtifact<ExtractResolverResult<TEntrypoint>>(
     environment,
     entrypoint,
-    variables,
+    variablesWithDefaults,
   );
 
   // TODO add comment explaining why we never use this value
@@ -51,7 +53,7 @@ export function useLazyReference<TEntrypoint>(
       kind: 'FragmentReference',
       readerArtifact: entrypoint.readerArtifact,
       root: ROOT_ID,
-      variables,
+      variables: variablesWithDefaults,
       nestedRefetchQueries: entrypoint.nestedRefetchQueries,
     },
   };
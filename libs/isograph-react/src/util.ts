@@ -13,3 +13,9 @@ export type ArgumentValue =
       kind: 'Literal';
       value: any;
     };
+
+// A @skip(if: ...) or @include(if: ...) directive on a selection.
+export type Condition = {
+  kind: 'Skip' | 'Include';
+  value: ArgumentValue;
+};